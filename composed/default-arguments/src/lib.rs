@@ -0,0 +1,175 @@
+//! Default Arguments Middleware
+//!
+//! Fills in arguments that clients routinely omit before a `tools/call` reaches
+//! the downstream tool. Client-provided values always win; only missing
+//! top-level keys are added.
+//!
+//! ## Configuration
+//!
+//! Defaults are read per request from `Context.data` under `default-arguments`,
+//! as a UTF-8 JSON object mapping tool names to default argument objects:
+//! ```json
+//! {"within_radius": {"radius_km": 5}, "route_cost": {"cost_per_unit": 1.8}}
+//! ```
+//! Calls to tools without an entry are forwarded untouched.
+//!
+//! ## Order Dependency
+//!
+//! This middleware MUST come BEFORE the tools whose arguments it fills in.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "default-arguments",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::server::handler::Guest;
+use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasmcp::protocol::server_messages::Context;
+use bindings::wasmcp::server::handler as downstream;
+
+const DEFAULTS_KEY: &str = "default-arguments";
+
+struct DefaultArguments;
+
+impl Guest for DefaultArguments {
+    fn handle_request(
+        ctx: Context,
+        request: (ClientRequest, RequestId),
+        client_stream: Option<&OutputStream>,
+    ) -> Result<ServerResponse, ErrorCode> {
+        let (req, id) = request;
+        match req {
+            ClientRequest::ToolsCall(ref call_req) => {
+                let defaults = match tool_defaults(&ctx, &call_req.name) {
+                    Ok(Some(defaults)) => defaults,
+                    Ok(None) => {
+                        return downstream::handle_request(&ctx, (&req, &id), client_stream);
+                    }
+                    Err(msg) => {
+                        return Err(ErrorCode::InternalError(Error {
+                            id: Some(id),
+                            code: -32603,
+                            message: msg,
+                            data: None,
+                        }));
+                    }
+                };
+
+                let forwarded = match apply_defaults(call_req, &defaults) {
+                    Some(merged) => ClientRequest::ToolsCall(merged),
+                    // Arguments that aren't a JSON object are left for the tool to reject
+                    None => req.clone(),
+                };
+                downstream::handle_request(&ctx, (&forwarded, &id), client_stream)
+            }
+            _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
+        }
+    }
+
+    fn handle_notification(ctx: Context, notification: ClientNotification) {
+        downstream::handle_notification(&ctx, &notification);
+    }
+
+    fn handle_response(ctx: Context, response: Result<(ClientResponse, RequestId), ErrorCode>) {
+        downstream::handle_response(&ctx, response);
+    }
+}
+
+/// Default arguments configured for `tool_name`, if any
+fn tool_defaults(
+    ctx: &Context,
+    tool_name: &str,
+) -> Result<Option<serde_json::Map<String, serde_json::Value>>, String> {
+    let raw = match ctx.data.iter().find(|(k, _)| k == DEFAULTS_KEY) {
+        Some((_, v)) => v,
+        None => return Ok(None),
+    };
+
+    let config: serde_json::Value = serde_json::from_slice(raw)
+        .map_err(|e| format!("Invalid '{}' configuration: {}", DEFAULTS_KEY, e))?;
+
+    match config.get(tool_name) {
+        None => Ok(None),
+        Some(serde_json::Value::Object(defaults)) => Ok(Some(defaults.clone())),
+        Some(_) => Err(format!(
+            "Invalid '{}' configuration: defaults for '{}' must be a JSON object",
+            DEFAULTS_KEY, tool_name
+        )),
+    }
+}
+
+/// Copy of the request with each default added where the client didn't supply
+/// the key. Returns `None` if the client's arguments aren't a JSON object.
+pub(crate) fn apply_defaults(
+    call_req: &CallToolRequest,
+    defaults: &serde_json::Map<String, serde_json::Value>,
+) -> Option<CallToolRequest> {
+    let mut arguments = match &call_req.arguments {
+        None => serde_json::Map::new(),
+        Some(raw) => match serde_json::from_str(raw).ok()? {
+            serde_json::Value::Object(map) => map,
+            _ => return None,
+        },
+    };
+
+    for (key, value) in defaults {
+        arguments
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+
+    Some(CallToolRequest {
+        name: call_req.name.clone(),
+        arguments: Some(serde_json::Value::Object(arguments).to_string()),
+    })
+}
+
+bindings::export!(DefaultArguments with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults(json: &str) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn merged(arguments: Option<&str>, config: &str) -> Option<serde_json::Value> {
+        let call_req = CallToolRequest {
+            name: "within_radius".to_string(),
+            arguments: arguments.map(str::to_string),
+        };
+        apply_defaults(&call_req, &defaults(config))
+            .map(|req| serde_json::from_str(req.arguments.as_deref().unwrap()).unwrap())
+    }
+
+    #[test]
+    fn client_values_win_over_defaults() {
+        assert_eq!(
+            merged(
+                Some(r#"{"radius_km": 1}"#),
+                r#"{"radius_km": 5, "unit": "km"}"#
+            ),
+            Some(serde_json::json!({"radius_km": 1, "unit": "km"}))
+        );
+    }
+
+    #[test]
+    fn missing_arguments_get_all_defaults() {
+        assert_eq!(
+            merged(None, r#"{"radius_km": 5}"#),
+            Some(serde_json::json!({"radius_km": 5}))
+        );
+    }
+
+    #[test]
+    fn non_object_arguments_are_left_alone() {
+        assert_eq!(merged(Some("[1, 2]"), r#"{"radius_km": 5}"#), None);
+        assert_eq!(merged(Some("not json"), r#"{"radius_km": 5}"#), None);
+    }
+}
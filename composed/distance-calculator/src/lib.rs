@@ -5,6 +5,17 @@
 //!
 //! This middleware demonstrates dynamic tool composition by orchestrating
 //! multiple downstream math tool calls without static WIT imports.
+//!
+//! It also provides planar geometry tools computed locally:
+//! - `triangle_2d`: area and centroid of a triangle
+//! - `collinear`: whether three points lie on one line within a tolerance
+//! - `line_intersection`: where the lines through two segments cross
+//! - `angle_at_vertex`: interior angle at a vertex, for planar or GPS points
+//! - `rotate_point`: rotation of a point about an origin
+//! - `transform_points`: batch scale and translate
+//! - `bounding_box_2d`: axis-aligned bounds of a point set
+//! - `convex_hull_2d`: counter-clockwise convex hull (monotone chain)
+//! - `polygon_area_2d`: absolute and signed shoelace area of a polygon
 
 #![allow(warnings)]
 
@@ -23,6 +34,12 @@ use bindings::wasi::io::streams::OutputStream;
 
 struct DistanceCalculator;
 
+/// Default `collinear` tolerance on the cross-product magnitude
+const DEFAULT_COLLINEAR_TOLERANCE: f64 = 1e-9;
+
+/// Lines whose directions differ by less than this (as sin θ) are treated as parallel
+const PARALLEL_EPSILON: f64 = 1e-12;
+
 impl Guest for DistanceCalculator {
     fn handle_request(
         ctx: Context,
@@ -36,11 +53,37 @@ impl Guest for DistanceCalculator {
                 handle_tools_list(list_req, id, &ctx, client_stream)
             }
             ClientRequest::ToolsCall(ref call_req) => {
-                if call_req.name == "distance" {
-                    handle_distance_call(call_req.clone(), id, &ctx, client_stream)
-                } else {
+                match call_req.name.as_str() {
+                    "distance" => handle_distance_call(call_req.clone(), id, &ctx, client_stream),
+                    "triangle_2d" => Ok(ServerResponse::ToolsCall(execute_triangle_2d(
+                        &call_req.arguments,
+                    ))),
+                    "collinear" => Ok(ServerResponse::ToolsCall(execute_collinear(
+                        &call_req.arguments,
+                    ))),
+                    "line_intersection" => Ok(ServerResponse::ToolsCall(
+                        execute_line_intersection(&call_req.arguments),
+                    )),
+                    "angle_at_vertex" => Ok(ServerResponse::ToolsCall(execute_angle_at_vertex(
+                        &call_req.arguments,
+                    ))),
+                    "rotate_point" => Ok(ServerResponse::ToolsCall(execute_rotate_point(
+                        &call_req.arguments,
+                    ))),
+                    "transform_points" => Ok(ServerResponse::ToolsCall(execute_transform_points(
+                        &call_req.arguments,
+                    ))),
+                    "bounding_box_2d" => Ok(ServerResponse::ToolsCall(execute_bounding_box_2d(
+                        &call_req.arguments,
+                    ))),
+                    "convex_hull_2d" => Ok(ServerResponse::ToolsCall(execute_convex_hull_2d(
+                        &call_req.arguments,
+                    ))),
+                    "polygon_area_2d" => Ok(ServerResponse::ToolsCall(execute_polygon_area_2d(
+                        &call_req.arguments,
+                    ))),
                     // Delegate to downstream handler
-                    downstream::handle_request(&ctx, (&req, &id), client_stream)
+                    _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
                 }
             }
             // Delegate all other requests to downstream
@@ -103,6 +146,328 @@ fn handle_tools_list(
         }),
     });
 
+    tools.push(Tool {
+        name: "triangle_2d".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "a": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "b": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "c": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                }
+            },
+            "required": ["a", "b", "c"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Area (shoelace formula) and centroid of a planar triangle. \
+                 Collinear points give zero area."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("2D Triangle".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "collinear".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "a": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "b": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "c": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "tolerance": {"type": "number", "minimum": 0, "description": "Largest cross-product magnitude still treated as collinear (default: 1e-9)"}
+            },
+            "required": ["a", "b", "c"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Test whether three planar points are collinear: the magnitude of the cross product (b - a) × (c - a), \
+                 which is twice the triangle area, must not exceed the tolerance. Returns the cross product and the area."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Collinearity Test".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "line_intersection".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "segment1": {
+                    "type": "object",
+                    "properties": {
+                        "x1": {"type": "number"},
+                        "y1": {"type": "number"},
+                        "x2": {"type": "number"},
+                        "y2": {"type": "number"}
+                    },
+                    "required": ["x1", "y1", "x2", "y2"]
+                },
+                "segment2": {
+                    "type": "object",
+                    "properties": {
+                        "x1": {"type": "number"},
+                        "y1": {"type": "number"},
+                        "x2": {"type": "number"},
+                        "y2": {"type": "number"}
+                    },
+                    "required": ["x1", "y1", "x2", "y2"]
+                }
+            },
+            "required": ["segment1", "segment2"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Intersect the lines through two planar segments using the determinant method. \
+                 Returns the crossing point and whether it lies within each segment, or reports parallel or coincident lines."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("2D Line Intersection".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "angle_at_vertex".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "a": {"type": "object", "description": "First point: {x, y} in 2d mode, {lat, lon} in geo mode"},
+                "vertex": {"type": "object", "description": "Vertex where the angle is measured"},
+                "c": {"type": "object", "description": "Second point"},
+                "mode": {"type": "string", "enum": ["2d", "geo"], "default": "2d", "description": "Coordinate type of the points"}
+            },
+            "required": ["a", "vertex", "c"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Angle a-vertex-c at the vertex, from the dot product of the vectors vertex→a and vertex→c. \
+                 In geo mode the points are projected onto a local equirectangular plane around the vertex first. \
+                 Returns the angle in degrees and radians."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Angle at Vertex".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "rotate_point".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "point": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                },
+                "origin": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"],
+                    "description": "Center of rotation (default: {x: 0, y: 0})"
+                },
+                "angle": {"type": "number", "description": "Rotation angle, counter-clockwise for positive values"},
+                "unit": {"type": "string", "enum": ["degrees", "radians"], "default": "degrees"}
+            },
+            "required": ["point", "angle"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Rotate a planar point about an origin by an angle (counter-clockwise when positive) using the rotation matrix \
+                 [cos θ, -sin θ; sin θ, cos θ]."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Rotate 2D Point".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "transform_points".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "points": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                        "required": ["x", "y"]
+                    },
+                    "description": "Points to transform"
+                },
+                "scale": {
+                    "type": "object",
+                    "properties": {"sx": {"type": "number"}, "sy": {"type": "number"}},
+                    "required": ["sx", "sy"],
+                    "description": "Scale factors, applied first (default: no scaling)"
+                },
+                "origin": {
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"],
+                    "description": "Fixed point of the scaling (default: {x: 0, y: 0})"
+                },
+                "translate": {
+                    "type": "object",
+                    "properties": {"dx": {"type": "number"}, "dy": {"type": "number"}},
+                    "required": ["dx", "dy"],
+                    "description": "Offset, applied after scaling (default: no offset)"
+                }
+            },
+            "required": ["points"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Scale and translate planar points in one call. Each point is first scaled about the origin, \
+                 then translated: p' = origin + (p - origin) * scale + translate."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Transform 2D Points".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "bounding_box_2d".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "points": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                        "required": ["x", "y"]
+                    },
+                    "minItems": 1
+                }
+            },
+            "required": ["points"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Axis-aligned bounding box of planar points: min/max x and y, width, height and center."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("2D Bounding Box".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "convex_hull_2d".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "points": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                        "required": ["x", "y"]
+                    },
+                    "minItems": 1
+                }
+            },
+            "required": ["points"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Convex hull of planar points using Andrew's monotone chain. Returns the hull vertices counter-clockwise, \
+                 without collinear edge points. Fewer than three distinct or all-collinear points give a degenerate hull \
+                 (one point or the two extremes)."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("2D Convex Hull".to_string()),
+        }),
+    });
+
+    tools.push(Tool {
+        name: "polygon_area_2d".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "vertices": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                        "required": ["x", "y"]
+                    },
+                    "minItems": 3,
+                    "description": "Polygon vertices in order; the ring is closed automatically"
+                }
+            },
+            "required": ["vertices"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Area of a planar polygon by the shoelace formula. Returns the absolute area and the signed area \
+                 (positive for counter-clockwise vertex order). For GPS coordinates use a spherical area tool instead."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("2D Polygon Area".to_string()),
+        }),
+    });
+
     Ok(ServerResponse::ToolsList(ListToolsResult {
         tools,
         next_cursor: None,
@@ -139,7 +504,7 @@ fn handle_distance_call(
         client_stream,
     ) {
         Ok(val) => val,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 4: Calculate dy²
@@ -153,7 +518,7 @@ fn handle_distance_call(
         client_stream,
     ) {
         Ok(val) => val,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 5: Calculate sum = dx² + dy²
@@ -167,7 +532,7 @@ fn handle_distance_call(
         client_stream,
     ) {
         Ok(val) => val,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 6: Calculate distance = √sum
@@ -181,7 +546,7 @@ fn handle_distance_call(
         client_stream,
     ) {
         Ok(val) => val,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     Ok(ServerResponse::ToolsCall(success_result(
@@ -189,23 +554,438 @@ fn handle_distance_call(
     )))
 }
 
+fn execute_triangle_2d(arguments: &Option<String>) -> CallToolResult {
+    let mut vertices = Vec::with_capacity(3);
+    for name in ["a", "b", "c"] {
+        match parse_point_2d(arguments, name) {
+            Ok(p) => vertices.push(p),
+            Err(msg) => return error_result(msg),
+        }
+    }
+
+    let signed_area = shoelace_signed_area(&vertices);
+    let centroid = Point2D {
+        x: (vertices[0].x + vertices[1].x + vertices[2].x) / 3.0,
+        y: (vertices[0].y + vertices[1].y + vertices[2].y) / 3.0,
+    };
+
+    structured_result(serde_json::json!({
+        "area": signed_area.abs(),
+        "centroid": {"x": centroid.x, "y": centroid.y},
+        "collinear": signed_area == 0.0
+    }))
+}
+
+fn execute_collinear(arguments: &Option<String>) -> CallToolResult {
+    let mut points = Vec::with_capacity(3);
+    for name in ["a", "b", "c"] {
+        match parse_point_2d(arguments, name) {
+            Ok(p) => points.push(p),
+            Err(msg) => return error_result(msg),
+        }
+    }
+
+    let tolerance = match parse_optional_number(arguments, "tolerance") {
+        Ok(t) => t.unwrap_or(DEFAULT_COLLINEAR_TOLERANCE),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        return error_result("tolerance must be a non-negative number".to_string());
+    }
+
+    let cross_product = cross(points[0], points[1], points[2]);
+
+    structured_result(serde_json::json!({
+        "collinear": cross_product.abs() <= tolerance,
+        "cross_product": cross_product,
+        "area": cross_product.abs() / 2.0,
+        "tolerance": tolerance
+    }))
+}
+
+fn execute_line_intersection(arguments: &Option<String>) -> CallToolResult {
+    let (p1, p2) = match parse_segment_2d(arguments, "segment1") {
+        Ok(s) => s,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (q1, q2) = match parse_segment_2d(arguments, "segment2") {
+        Ok(s) => s,
+        Err(msg) => return error_result(msg),
+    };
+
+    let r = (p2.x - p1.x, p2.y - p1.y);
+    let s = (q2.x - q1.x, q2.y - q1.y);
+    let r_len = r.0.hypot(r.1);
+    let s_len = s.0.hypot(s.1);
+
+    if r_len == 0.0 || s_len == 0.0 {
+        return error_result("Segments must have distinct endpoints".to_string());
+    }
+
+    let determinant = r.0 * s.1 - r.1 * s.0;
+
+    // Relative guard: the determinant is |r||s|·sin(θ) between the directions
+    if determinant.abs() <= PARALLEL_EPSILON * r_len * s_len {
+        let offset = cross(p1, p2, q1).abs() / r_len;
+        let status = if offset <= PARALLEL_EPSILON * r_len.max(s_len) {
+            "coincident"
+        } else {
+            "parallel"
+        };
+        return structured_result(serde_json::json!({
+            "status": status,
+            "point": null,
+            "within_segment1": null,
+            "within_segment2": null,
+            "within_both": null
+        }));
+    }
+
+    let qp = (q1.x - p1.x, q1.y - p1.y);
+    let t = (qp.0 * s.1 - qp.1 * s.0) / determinant;
+    let u = (qp.0 * r.1 - qp.1 * r.0) / determinant;
+    let within_segment1 = (0.0..=1.0).contains(&t);
+    let within_segment2 = (0.0..=1.0).contains(&u);
+
+    structured_result(serde_json::json!({
+        "status": "intersecting",
+        "point": {"x": p1.x + t * r.0, "y": p1.y + t * r.1},
+        "t": t,
+        "u": u,
+        "within_segment1": within_segment1,
+        "within_segment2": within_segment2,
+        "within_both": within_segment1 && within_segment2
+    }))
+}
+
+fn execute_angle_at_vertex(arguments: &Option<String>) -> CallToolResult {
+    let mode = match parse_mode(arguments) {
+        Ok(m) => m,
+        Err(msg) => return error_result(msg),
+    };
+
+    let parse = |name: &str| match mode {
+        "geo" => parse_geo_point(arguments, name),
+        _ => parse_point_2d(arguments, name),
+    };
+
+    let (a, vertex, c) = match (parse("a"), parse("vertex"), parse("c")) {
+        (Ok(a), Ok(vertex), Ok(c)) => (a, vertex, c),
+        (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => return error_result(msg),
+    };
+
+    let (u, v) = if mode == "geo" {
+        (project_local(vertex, a), project_local(vertex, c))
+    } else {
+        (
+            (a.x - vertex.x, a.y - vertex.y),
+            (c.x - vertex.x, c.y - vertex.y),
+        )
+    };
+
+    let (u_len, v_len) = (u.0.hypot(u.1), v.0.hypot(v.1));
+    if u_len == 0.0 || v_len == 0.0 {
+        return error_result(
+            "Points 'a' and 'c' must differ from the vertex, the angle is undefined".to_string(),
+        );
+    }
+
+    let cosine = ((u.0 * v.0 + u.1 * v.1) / (u_len * v_len)).clamp(-1.0, 1.0);
+    let radians = cosine.acos();
+
+    structured_result(serde_json::json!({
+        "angle_degrees": radians.to_degrees(),
+        "angle_radians": radians,
+        "mode": mode
+    }))
+}
+
+fn execute_rotate_point(arguments: &Option<String>) -> CallToolResult {
+    let point = match parse_point_2d(arguments, "point") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let origin = match parse_optional_point_2d(arguments, "origin") {
+        Ok(o) => o.unwrap_or(Point2D { x: 0.0, y: 0.0 }),
+        Err(msg) => return error_result(msg),
+    };
+
+    let angle = match parse_optional_number(arguments, "angle") {
+        Ok(Some(a)) if a.is_finite() => a,
+        Ok(_) => return error_result("Missing or invalid parameter 'angle'".to_string()),
+        Err(msg) => return error_result(msg),
+    };
+
+    let radians = match parse_angle_unit(arguments) {
+        Ok(AngleUnit::Degrees) => angle.to_radians(),
+        Ok(AngleUnit::Radians) => angle,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (sin, cos) = radians.sin_cos();
+    let (dx, dy) = (point.x - origin.x, point.y - origin.y);
+    let rotated = Point2D {
+        x: origin.x + dx * cos - dy * sin,
+        y: origin.y + dx * sin + dy * cos,
+    };
+
+    structured_result(serde_json::json!({
+        "point": {"x": rotated.x, "y": rotated.y},
+        "origin": {"x": origin.x, "y": origin.y},
+        "angle_radians": radians
+    }))
+}
+
+fn execute_transform_points(arguments: &Option<String>) -> CallToolResult {
+    let points = match parse_points_2d(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (sx, sy) = match parse_optional_pair(arguments, "scale", "sx", "sy") {
+        Ok(s) => s.unwrap_or((1.0, 1.0)),
+        Err(msg) => return error_result(msg),
+    };
+
+    let origin = match parse_optional_point_2d(arguments, "origin") {
+        Ok(o) => o.unwrap_or(Point2D { x: 0.0, y: 0.0 }),
+        Err(msg) => return error_result(msg),
+    };
+
+    let (dx, dy) = match parse_optional_pair(arguments, "translate", "dx", "dy") {
+        Ok(t) => t.unwrap_or((0.0, 0.0)),
+        Err(msg) => return error_result(msg),
+    };
+
+    let transformed: Vec<serde_json::Value> = points
+        .iter()
+        .map(|p| {
+            let x = origin.x + (p.x - origin.x) * sx + dx;
+            let y = origin.y + (p.y - origin.y) * sy + dy;
+            serde_json::json!({"x": x, "y": y})
+        })
+        .collect();
+
+    structured_result(serde_json::json!({
+        "points": transformed,
+        "count": transformed.len()
+    }))
+}
+
+fn execute_bounding_box_2d(arguments: &Option<String>) -> CallToolResult {
+    let points = match parse_points_2d(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if points.is_empty() {
+        return error_result("At least 1 point is required".to_string());
+    }
+
+    let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let max_y = points.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max);
+
+    structured_result(serde_json::json!({
+        "min_x": min_x,
+        "min_y": min_y,
+        "max_x": max_x,
+        "max_y": max_y,
+        "width": max_x - min_x,
+        "height": max_y - min_y,
+        "center": {"x": (min_x + max_x) / 2.0, "y": (min_y + max_y) / 2.0}
+    }))
+}
+
+fn execute_convex_hull_2d(arguments: &Option<String>) -> CallToolResult {
+    let points = match parse_points_2d(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if points.is_empty() {
+        return error_result("At least 1 point is required".to_string());
+    }
+
+    let hull = monotone_chain(&points);
+    let area = if hull.len() >= 3 {
+        shoelace_signed_area(&hull)
+    } else {
+        0.0
+    };
+    let vertices: Vec<serde_json::Value> = hull
+        .iter()
+        .map(|p| serde_json::json!({"x": p.x, "y": p.y}))
+        .collect();
+
+    structured_result(serde_json::json!({
+        "hull": vertices,
+        "vertex_count": hull.len(),
+        "area": area,
+        "degenerate": hull.len() < 3
+    }))
+}
+
+fn execute_polygon_area_2d(arguments: &Option<String>) -> CallToolResult {
+    let mut vertices = match parse_points_2d(arguments, "vertices") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    // Accept rings given explicitly closed as well as open ones
+    if vertices.len() > 1 {
+        let (first, last) = (vertices[0], vertices[vertices.len() - 1]);
+        if first.x == last.x && first.y == last.y {
+            vertices.pop();
+        }
+    }
+
+    if vertices.len() < 3 {
+        return error_result("Polygon must have at least 3 distinct vertices".to_string());
+    }
+
+    let signed_area = shoelace_signed_area(&vertices);
+    let orientation = if signed_area > 0.0 {
+        "counter_clockwise"
+    } else if signed_area < 0.0 {
+        "clockwise"
+    } else {
+        "degenerate"
+    };
+
+    structured_result(serde_json::json!({
+        "area": signed_area.abs(),
+        "signed_area": signed_area,
+        "orientation": orientation,
+        "vertex_count": vertices.len()
+    }))
+}
+
+/// Andrew's monotone chain: counter-clockwise hull without collinear points
+pub(crate) fn monotone_chain(points: &[Point2D]) -> Vec<Point2D> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut lower: Vec<Point2D> = Vec::new();
+    for p in &sorted {
+        while lower.len() >= 2 && cross(lower[lower.len() - 2], lower[lower.len() - 1], *p) <= 0.0 {
+            lower.pop();
+        }
+        lower.push(*p);
+    }
+
+    let mut upper: Vec<Point2D> = Vec::new();
+    for p in sorted.iter().rev() {
+        while upper.len() >= 2 && cross(upper[upper.len() - 2], upper[upper.len() - 1], *p) <= 0.0 {
+            upper.pop();
+        }
+        upper.push(*p);
+    }
+
+    // The last point of each chain is the first point of the other
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+enum AngleUnit {
+    Degrees,
+    Radians,
+}
+
+/// Offset of `p` from `origin` on an equirectangular plane tangent at `origin`,
+/// in degrees of latitude; good enough for angles between nearby points
+pub(crate) fn project_local(origin: Point2D, p: Point2D) -> (f64, f64) {
+    // Points carry lon in x and lat in y
+    let delta_lon = (p.x - origin.x + 540.0).rem_euclid(360.0) - 180.0;
+    (delta_lon * origin.y.to_radians().cos(), p.y - origin.y)
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+/// Z component of (a - o) × (b - o): positive when o -> a -> b turns counter-clockwise,
+/// zero when the three points are collinear
+pub(crate) fn cross(o: Point2D, a: Point2D, b: Point2D) -> f64 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+/// Signed area of a closed ring by the shoelace formula; positive when the
+/// vertices run counter-clockwise
+pub(crate) fn shoelace_signed_area(vertices: &[Point2D]) -> f64 {
+    let n = vertices.len();
+    let twice_area: f64 = (0..n)
+        .map(|i| {
+            let (p, q) = (vertices[i], vertices[(i + 1) % n]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum();
+    twice_area / 2.0
+}
+
+/// Why a downstream tool call produced no usable value
+enum DownstreamError {
+    /// No handler in the downstream chain provides the tool
+    NotFound(String),
+    /// The tool ran but returned an error result or an unusable value
+    ToolError(String),
+    /// The downstream chain failed with any other JSON-RPC error
+    Protocol(ErrorCode),
+}
+
+impl DownstreamError {
+    /// Middleware response for a failed downstream call: a missing tool is a
+    /// `MethodNotFound` error rather than an `is_error` tool result
+    fn into_response(self, id: RequestId) -> Result<ServerResponse, ErrorCode> {
+        match self {
+            DownstreamError::NotFound(message) => Err(ErrorCode::MethodNotFound(Error {
+                id: Some(id),
+                code: -32601,
+                message,
+                data: None,
+            })),
+            DownstreamError::ToolError(message) => {
+                Ok(ServerResponse::ToolsCall(error_result(message)))
+            }
+            DownstreamError::Protocol(e) => Err(e),
+        }
+    }
+}
+
 fn call_downstream_tool(
     ctx: &Context,
     tool_request: &CallToolRequest,
     request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<f64, String> {
+) -> Result<f64, DownstreamError> {
     let downstream_req = ClientRequest::ToolsCall(tool_request.clone());
 
     match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
-        Ok(ServerResponse::ToolsCall(result)) => extract_number_from_result(&result),
-        Err(ErrorCode::MethodNotFound(_)) => Err(format!(
+        Ok(ServerResponse::ToolsCall(result)) => {
+            extract_number_from_result(&result).map_err(DownstreamError::ToolError)
+        }
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(format!(
             "Tool '{}' not found. Ensure required components \
              come AFTER this middleware in the pipeline.",
             tool_request.name
+        ))),
+        Err(e) => Err(DownstreamError::Protocol(e)),
+        _ => Err(DownstreamError::ToolError(
+            "Unexpected response type".to_string(),
         )),
-        Err(e) => Err(format!("Error calling '{}': {:?}", tool_request.name, e)),
-        _ => Err("Unexpected response type".to_string()),
     }
 }
 
@@ -240,6 +1020,236 @@ fn parse_distance_args(arguments: &Option<String>) -> Result<(f64, f64, f64, f64
     Ok((x1, y1, x2, y2))
 }
 
+/// Parse a single `{x, y}` object under `name`, requiring finite coordinates
+fn parse_point_2d(arguments: &Option<String>, name: &str) -> Result<Point2D, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let point = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    point_from_json(point).map_err(|msg| format!("{}: {}", name, msg))
+}
+
+fn parse_optional_point_2d(
+    arguments: &Option<String>,
+    name: &str,
+) -> Result<Option<Point2D>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) if v.is_object() => point_from_json(v)
+            .map(Some)
+            .map_err(|msg| format!("{}: {}", name, msg)),
+        Some(_) => Err(format!("Invalid parameter '{}': expected an object", name)),
+    }
+}
+
+/// Parse an array of `{x, y}` objects under `name`, requiring finite coordinates
+fn parse_points_2d(arguments: &Option<String>, name: &str) -> Result<Vec<Point2D>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let points_arr = json
+        .get(name)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    points_arr
+        .iter()
+        .enumerate()
+        .map(|(i, p)| point_from_json(p).map_err(|msg| format!("{}[{}]: {}", name, i, msg)))
+        .collect()
+}
+
+/// Parse an optional object under `name` holding two finite numbers `kx` and `ky`
+fn parse_optional_pair(
+    arguments: &Option<String>,
+    name: &str,
+    kx: &str,
+    ky: &str,
+) -> Result<Option<(f64, f64)>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let value = match json.get(name) {
+        None | Some(serde_json::Value::Null) => return Ok(None),
+        Some(v) if v.is_object() => v,
+        Some(_) => return Err(format!("Invalid parameter '{}': expected an object", name)),
+    };
+
+    let component = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| format!("{}: Missing or non-finite '{}'", name, key))
+    };
+
+    Ok(Some((component(kx)?, component(ky)?)))
+}
+
+/// Parse a `{lat, lon}` object under `name` as a point with x = lon and y = lat
+fn parse_geo_point(arguments: &Option<String>, name: &str) -> Result<Point2D, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let point = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    let coord = |key: &str| {
+        point
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| format!("{}: Missing or non-finite '{}'", name, key))
+    };
+    let (lat, lon) = (coord("lat")?, coord("lon")?);
+
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!(
+            "{}: Latitude must be between -90 and 90 degrees",
+            name
+        ));
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return Err(format!(
+            "{}: Longitude must be between -180 and 180 degrees",
+            name
+        ));
+    }
+
+    Ok(Point2D { x: lon, y: lat })
+}
+
+fn parse_angle_unit(arguments: &Option<String>) -> Result<AngleUnit, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("unit") {
+        None | Some(serde_json::Value::Null) => Ok(AngleUnit::Degrees),
+        Some(v) => match v.as_str() {
+            Some("degrees") => Ok(AngleUnit::Degrees),
+            Some("radians") => Ok(AngleUnit::Radians),
+            _ => Err("Invalid parameter 'unit': expected \"degrees\" or \"radians\"".to_string()),
+        },
+    }
+}
+
+fn parse_mode(arguments: &Option<String>) -> Result<&'static str, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("mode") {
+        None | Some(serde_json::Value::Null) => Ok("2d"),
+        Some(v) => match v.as_str() {
+            Some("2d") => Ok("2d"),
+            Some("geo") => Ok("geo"),
+            _ => Err("Invalid parameter 'mode': expected \"2d\" or \"geo\"".to_string()),
+        },
+    }
+}
+
+/// Parse a `{x1, y1, x2, y2}` segment object under `name`
+fn parse_segment_2d(arguments: &Option<String>, name: &str) -> Result<(Point2D, Point2D), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let segment = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    let coord = |key: &str| {
+        segment
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| format!("{}: Missing or non-finite '{}'", name, key))
+    };
+
+    Ok((
+        Point2D {
+            x: coord("x1")?,
+            y: coord("y1")?,
+        },
+        Point2D {
+            x: coord("x2")?,
+            y: coord("y2")?,
+        },
+    ))
+}
+
+fn point_from_json(value: &serde_json::Value) -> Result<Point2D, String> {
+    let coord = |axis: &str| {
+        value
+            .get(axis)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| format!("Missing or non-finite '{}'", axis))
+    };
+
+    Ok(Point2D {
+        x: coord("x")?,
+        y: coord("y")?,
+    })
+}
+
+fn parse_optional_number(arguments: &Option<String>, name: &str) -> Result<Option<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid parameter '{}': expected a number", name)),
+    }
+}
+
 fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     if result.is_error == Some(true) {
         return Err("Tool call returned error".to_string());
@@ -258,6 +1268,19 @@ fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     Err("No text content found in result".to_string())
 }
 
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
 fn success_result(result: String) -> CallToolResult {
     CallToolResult {
         content: vec![ContentBlock::Text(TextContent {
@@ -283,3 +1306,128 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(DistanceCalculator with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pt(x: f64, y: f64) -> Point2D {
+        Point2D { x, y }
+    }
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn structured(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(
+            result
+                .structured_content
+                .as_deref()
+                .expect("structured content"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn shoelace_sign_follows_orientation() {
+        let ccw = [pt(0.0, 0.0), pt(2.0, 0.0), pt(2.0, 2.0), pt(0.0, 2.0)];
+        assert_eq!(shoelace_signed_area(&ccw), 4.0);
+
+        let cw: Vec<Point2D> = ccw.iter().rev().copied().collect();
+        assert_eq!(shoelace_signed_area(&cw), -4.0);
+    }
+
+    #[test]
+    fn cross_is_zero_for_collinear_points() {
+        assert_eq!(cross(pt(0.0, 0.0), pt(1.0, 1.0), pt(3.0, 3.0)), 0.0);
+        assert!(cross(pt(0.0, 0.0), pt(1.0, 0.0), pt(0.0, 1.0)) > 0.0);
+    }
+
+    #[test]
+    fn monotone_chain_square_with_interior_point() {
+        let hull = monotone_chain(&[
+            pt(0.0, 0.0),
+            pt(1.0, 1.0),
+            pt(2.0, 0.0),
+            pt(2.0, 2.0),
+            pt(0.0, 2.0),
+            pt(1.0, 0.0),
+        ]);
+        assert_eq!(hull.len(), 4);
+        assert!(shoelace_signed_area(&hull) > 0.0);
+    }
+
+    #[test]
+    fn project_local_wraps_across_antimeridian() {
+        let (dx, dy) = project_local(pt(179.0, 0.0), pt(-179.0, 0.0));
+        assert!((dx - 2.0).abs() < 1e-9 && dy == 0.0);
+    }
+
+    #[test]
+    fn crossing_segments_intersect_within_both() {
+        let result = execute_line_intersection(&args(
+            r#"{"segment1": {"x1": 0, "y1": 0, "x2": 2, "y2": 2},
+                "segment2": {"x1": 0, "y1": 2, "x2": 2, "y2": 0}}"#,
+        ));
+        let value = structured(&result);
+        assert_eq!(value["status"], "intersecting");
+        assert_eq!(value["point"], serde_json::json!({"x": 1.0, "y": 1.0}));
+        assert_eq!(value["within_both"], true);
+    }
+
+    #[test]
+    fn parallel_and_coincident_lines() {
+        let parallel = execute_line_intersection(&args(
+            r#"{"segment1": {"x1": 0, "y1": 0, "x2": 1, "y2": 0},
+                "segment2": {"x1": 0, "y1": 1, "x2": 1, "y2": 1}}"#,
+        ));
+        assert_eq!(structured(&parallel)["status"], "parallel");
+
+        let coincident = execute_line_intersection(&args(
+            r#"{"segment1": {"x1": 0, "y1": 0, "x2": 1, "y2": 0},
+                "segment2": {"x1": 2, "y1": 0, "x2": 3, "y2": 0}}"#,
+        ));
+        assert_eq!(structured(&coincident)["status"], "coincident");
+    }
+
+    #[test]
+    fn right_angle_at_vertex() {
+        let result = execute_angle_at_vertex(&args(
+            r#"{"a": {"x": 1, "y": 0}, "vertex": {"x": 0, "y": 0}, "c": {"x": 0, "y": 5}}"#,
+        ));
+        let degrees = structured(&result)["angle_degrees"].as_f64().unwrap();
+        assert!((degrees - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rotate_quarter_turn_about_origin() {
+        let result = execute_rotate_point(&args(
+            r#"{"point": {"x": 2, "y": 1}, "origin": {"x": 1, "y": 1}, "angle": 90}"#,
+        ));
+        let point = &structured(&result)["point"];
+        assert!((point["x"].as_f64().unwrap() - 1.0).abs() < 1e-12);
+        assert!((point["y"].as_f64().unwrap() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn missing_downstream_tool_is_method_not_found() {
+        let not_found = DownstreamError::NotFound("Tool 'X' not found".to_string());
+        match not_found.into_response(RequestId::Number(7)) {
+            Err(ErrorCode::MethodNotFound(e)) => {
+                assert_eq!(e.code, -32601);
+                assert!(matches!(e.id, Some(RequestId::Number(7))));
+            }
+            _ => panic!("expected MethodNotFound"),
+        }
+    }
+
+    #[test]
+    fn downstream_tool_error_is_an_error_result() {
+        let tool_error = DownstreamError::ToolError("Tool 'X' returned an error".to_string());
+        match tool_error.into_response(RequestId::Number(7)) {
+            Ok(ServerResponse::ToolsCall(result)) => assert_eq!(result.is_error, Some(true)),
+            _ => panic!("expected an is_error tool result"),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Geospatial Cache Middleware
+//!
+//! Memoizes `distance` and `bearing` tool results for the lifetime of the component
+//! instance. Calls are keyed on the tool name and the coordinate tuple rounded to
+//! 6 decimal places (~0.1 m), so repeated lookups for fixed landmark pairs skip the
+//! downstream computation entirely.
+//!
+//! ## Context Options
+//!
+//! Read from `Context.data` (values are UTF-8 strings):
+//! - `cache-bypass`: `"true"` or `"1"` forwards the call downstream without touching the cache
+//! - `cache-max-entries`: maximum number of cached results (default: 1024).
+//!   The oldest entries are evicted first once the cap is reached.
+//!
+//! Every intercepted call reports `{"cache": {"hit", "hits", "misses", "size"}}`
+//! in the result `meta`.
+//!
+//! ## Order Dependency
+//!
+//! This middleware MUST come BEFORE the geospatial tools it caches:
+//! ```bash
+//! wasmcp compose geospatial-cache geospatial-distance geospatial-bearing -o server.wasm
+//! ```
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "geospatial-cache",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::server::handler::Guest;
+use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasmcp::protocol::server_messages::Context;
+use bindings::wasmcp::server::handler as downstream;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+const CACHED_TOOLS: [&str; 2] = ["distance", "bearing"];
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+const COORDINATE_SCALE: f64 = 1e6;
+
+const BYPASS_KEY: &str = "cache-bypass";
+const MAX_ENTRIES_KEY: &str = "cache-max-entries";
+
+static CACHE: Mutex<ResultCache> = Mutex::new(ResultCache::new());
+
+struct GeospatialCache;
+
+impl Guest for GeospatialCache {
+    fn handle_request(
+        ctx: Context,
+        request: (ClientRequest, RequestId),
+        client_stream: Option<&OutputStream>,
+    ) -> Result<ServerResponse, ErrorCode> {
+        let (req, id) = request;
+        match req {
+            ClientRequest::ToolsCall(ref call_req)
+                if CACHED_TOOLS.contains(&call_req.name.as_str()) =>
+            {
+                handle_cached_call(call_req, &req, id, &ctx, client_stream)
+            }
+            _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
+        }
+    }
+
+    fn handle_notification(ctx: Context, notification: ClientNotification) {
+        downstream::handle_notification(&ctx, &notification);
+    }
+
+    fn handle_response(ctx: Context, response: Result<(ClientResponse, RequestId), ErrorCode>) {
+        downstream::handle_response(&ctx, response);
+    }
+}
+
+/// Tool name plus the rounded (lat1, lon1, lat2, lon2) tuple
+type CacheKey = (String, [i64; 4]);
+
+/// The parts of a successful tool result worth replaying
+#[derive(Clone)]
+pub(crate) struct CachedResult {
+    text: String,
+    structured_content: Option<String>,
+}
+
+/// Insertion-ordered map with FIFO eviction and hit/miss counters
+pub(crate) struct ResultCache {
+    entries: BTreeMap<CacheKey, CachedResult>,
+    order: VecDeque<CacheKey>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ResultCache {
+    const fn new() -> Self {
+        ResultCache {
+            entries: BTreeMap::new(),
+            order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<CachedResult> {
+        let found = self.entries.get(key).cloned();
+        if found.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        found
+    }
+
+    pub(crate) fn insert(&mut self, key: CacheKey, value: CachedResult, max_entries: usize) {
+        if max_entries == 0 || self.entries.contains_key(&key) {
+            return;
+        }
+        while self.entries.len() >= max_entries {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.order.push_back(key.clone());
+        self.entries.insert(key, value);
+    }
+
+    pub(crate) fn stats(&self, hit: bool) -> serde_json::Value {
+        serde_json::json!({
+            "hit": hit,
+            "hits": self.hits,
+            "misses": self.misses,
+            "size": self.entries.len()
+        })
+    }
+}
+
+fn handle_cached_call(
+    call_req: &CallToolRequest,
+    req: &ClientRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    // Arguments we can't key on are left for the downstream tool to reject
+    let key = match cache_key(call_req) {
+        Some(key) if !context_flag(ctx, BYPASS_KEY) => key,
+        _ => return downstream::handle_request(ctx, (req, &id), client_stream),
+    };
+
+    let max_entries = context_usize(ctx, MAX_ENTRIES_KEY).unwrap_or(DEFAULT_MAX_ENTRIES);
+    let mut cache = CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(cached) = cache.get(&key) {
+        let stats = cache.stats(true);
+        return Ok(ServerResponse::ToolsCall(CallToolResult {
+            content: vec![ContentBlock::Text(TextContent {
+                text: TextData::Text(cached.text),
+                options: None,
+            })],
+            is_error: None,
+            meta: Some(with_cache_meta(None, stats)),
+            structured_content: cached.structured_content,
+        }));
+    }
+
+    match downstream::handle_request(ctx, (req, &id), client_stream)? {
+        ServerResponse::ToolsCall(mut result) => {
+            if let Some(cached) = cacheable(&result) {
+                cache.insert(key, cached, max_entries);
+            }
+            result.meta = Some(with_cache_meta(result.meta.take(), cache.stats(false)));
+            Ok(ServerResponse::ToolsCall(result))
+        }
+        other => Ok(other),
+    }
+}
+
+/// Only successful single-text results are cached
+pub(crate) fn cacheable(result: &CallToolResult) -> Option<CachedResult> {
+    if result.is_error == Some(true) || result.content.len() != 1 {
+        return None;
+    }
+    match &result.content[0] {
+        ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            ..
+        }) => Some(CachedResult {
+            text: text.clone(),
+            structured_content: result.structured_content.clone(),
+        }),
+        _ => None,
+    }
+}
+
+pub(crate) fn cache_key(call_req: &CallToolRequest) -> Option<CacheKey> {
+    let args: serde_json::Value = serde_json::from_str(call_req.arguments.as_ref()?).ok()?;
+
+    let mut rounded = [0i64; 4];
+    for (slot, name) in rounded.iter_mut().zip(["lat1", "lon1", "lat2", "lon2"]) {
+        let value = args.get(name)?.as_f64()?;
+        if !value.is_finite() {
+            return None;
+        }
+        *slot = (value * COORDINATE_SCALE).round() as i64;
+    }
+
+    Some((call_req.name.clone(), rounded))
+}
+
+/// Merge the cache stats into any existing JSON object meta
+pub(crate) fn with_cache_meta(existing: Option<String>, stats: serde_json::Value) -> String {
+    let mut meta = existing
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok())
+        .filter(|m| m.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    meta["cache"] = stats;
+    meta.to_string()
+}
+
+fn context_value<'a>(ctx: &'a Context, key: &str) -> Option<&'a str> {
+    ctx.data
+        .iter()
+        .find(|(k, _)| k == key)
+        .and_then(|(_, v)| std::str::from_utf8(v).ok())
+        .map(str::trim)
+}
+
+fn context_flag(ctx: &Context, key: &str) -> bool {
+    matches!(context_value(ctx, key), Some("true") | Some("1"))
+}
+
+fn context_usize(ctx: &Context, key: &str) -> Option<usize> {
+    context_value(ctx, key).and_then(|v| v.parse().ok())
+}
+
+bindings::export!(GeospatialCache with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: &str, arguments: &str) -> CallToolRequest {
+        CallToolRequest {
+            name: name.to_string(),
+            arguments: Some(arguments.to_string()),
+        }
+    }
+
+    fn cached(text: &str) -> CachedResult {
+        CachedResult {
+            text: text.to_string(),
+            structured_content: None,
+        }
+    }
+
+    #[test]
+    fn cache_key_rounds_coordinates() {
+        let a = cache_key(&request(
+            "distance",
+            r#"{"lat1": 1.0000001, "lon1": 2, "lat2": 3, "lon2": 4}"#,
+        ));
+        let b = cache_key(&request(
+            "distance",
+            r#"{"lat1": 1.0, "lon1": 2, "lat2": 3, "lon2": 4}"#,
+        ));
+        assert!(a.is_some());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn cache_key_separates_tools_and_rejects_missing_fields() {
+        let args = r#"{"lat1": 1, "lon1": 2, "lat2": 3, "lon2": 4}"#;
+        assert_ne!(
+            cache_key(&request("distance", args)),
+            cache_key(&request("bearing", args))
+        );
+        assert_eq!(cache_key(&request("distance", r#"{"lat1": 1}"#)), None);
+    }
+
+    #[test]
+    fn fifo_eviction_and_counters() {
+        let mut cache = ResultCache::new();
+        let key = |n: i64| ("distance".to_string(), [n, 0, 0, 0]);
+
+        cache.insert(key(1), cached("one"), 2);
+        cache.insert(key(2), cached("two"), 2);
+        cache.insert(key(3), cached("three"), 2);
+
+        assert!(cache.get(&key(1)).is_none());
+        assert_eq!(
+            cache.get(&key(3)).map(|c| c.text),
+            Some("three".to_string())
+        );
+        assert_eq!(
+            cache.stats(true),
+            serde_json::json!({"hit": true, "hits": 1, "misses": 1, "size": 2})
+        );
+    }
+
+    #[test]
+    fn errors_are_not_cacheable() {
+        let result = CallToolResult {
+            content: vec![ContentBlock::Text(TextContent {
+                text: TextData::Text("boom".to_string()),
+                options: None,
+            })],
+            is_error: Some(true),
+            meta: None,
+            structured_content: None,
+        };
+        assert!(cacheable(&result).is_none());
+    }
+
+    #[test]
+    fn cache_meta_merges_into_existing_object() {
+        let merged = with_cache_meta(Some(r#"{"trace": 7}"#.to_string()), serde_json::json!(1));
+        let value: serde_json::Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(value, serde_json::json!({"trace": 7, "cache": 1}));
+    }
+}
@@ -157,7 +157,7 @@ fn handle_pythagorean_call(
 
     let a_squared = match call_downstream_tool(ctx, &square_a_req, &id, client_stream) {
         Ok(result) => result,
-        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 2: Call square(b) through downstream handler chain
@@ -168,7 +168,7 @@ fn handle_pythagorean_call(
 
     let b_squared = match call_downstream_tool(ctx, &square_b_req, &id, client_stream) {
         Ok(result) => result,
-        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 3: Add the squared values
@@ -187,7 +187,36 @@ fn handle_pythagorean_call(
                 hypotenuse.to_string(),
             )))
         }
-        Err(msg) => Ok(ServerResponse::ToolsCall(error_result(msg))),
+        Err(e) => e.into_response(id),
+    }
+}
+
+/// Why a downstream tool call produced no usable value
+enum DownstreamError {
+    /// No handler in the downstream chain provides the tool
+    NotFound(String),
+    /// The tool ran but returned an error result or an unusable value
+    ToolError(String),
+    /// The downstream chain failed with any other JSON-RPC error
+    Protocol(ErrorCode),
+}
+
+impl DownstreamError {
+    /// Middleware response for a failed downstream call: a missing tool is a
+    /// `MethodNotFound` error rather than an `is_error` tool result
+    fn into_response(self, id: RequestId) -> Result<ServerResponse, ErrorCode> {
+        match self {
+            DownstreamError::NotFound(message) => Err(ErrorCode::MethodNotFound(Error {
+                id: Some(id),
+                code: -32601,
+                message,
+                data: None,
+            })),
+            DownstreamError::ToolError(message) => {
+                Ok(ServerResponse::ToolsCall(error_result(message)))
+            }
+            DownstreamError::Protocol(e) => Err(e),
+        }
     }
 }
 
@@ -197,25 +226,25 @@ fn call_downstream_tool(
     tool_request: &CallToolRequest,
     request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<f64, String> {
+) -> Result<f64, DownstreamError> {
     // Make the downstream call
     let downstream_req = ClientRequest::ToolsCall(tool_request.clone());
 
     match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
         Ok(ServerResponse::ToolsCall(result)) => {
             // Extract the numeric value from the result
-            extract_number_from_result(&result)
+            extract_number_from_result(&result).map_err(DownstreamError::ToolError)
         }
-        Ok(_) => Err(format!(
+        Ok(_) => Err(DownstreamError::ToolError(format!(
             "Unexpected response type when calling '{}'",
             tool_request.name
-        )),
-        Err(ErrorCode::MethodNotFound(_)) => Err(format!(
+        ))),
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(format!(
             "Tool '{}' not found in downstream handlers. \
              Ensure math comes AFTER pythagorean-middleware in the pipeline.",
             tool_request.name
-        )),
-        Err(e) => Err(format!("Error calling '{}': {:?}", tool_request.name, e)),
+        ))),
+        Err(e) => Err(DownstreamError::Protocol(e)),
     }
 }
 
@@ -17,9 +17,28 @@ use bindings::wasi::io::streams::OutputStream;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasmcp::protocol::server_messages::Context;
 use bindings::wasmcp::server::handler as downstream;
+use std::f64::consts::PI;
 
 struct RouteOptimizer;
 
+/// Mean Earth radius used by the downstream `distance` tool
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Upper bound on the densified route produced by `segment_route`
+const MAX_SEGMENTED_WAYPOINTS: usize = 10_000;
+
+/// `optimize_order` measures every pair of waypoints, so keep the matrix small
+const MAX_ORDER_WAYPOINTS: usize = 100;
+
+/// Upper bound on full 2-opt sweeps over the tour; each sweep is O(n²)
+const MAX_TWO_OPT_PASSES: usize = 1_000;
+
+/// 16-point compass rose clockwise from north; 8-bin histograms use every other point
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
 impl Guest for RouteOptimizer {
     fn handle_request(
         ctx: Context,
@@ -31,13 +50,23 @@ impl Guest for RouteOptimizer {
             ClientRequest::ToolsList(list_req) => {
                 handle_tools_list(list_req, id, &ctx, client_stream)
             }
-            ClientRequest::ToolsCall(ref call_req) => {
-                if call_req.name == "analyze_route" {
-                    handle_analyze_route(call_req.clone(), id, &ctx, client_stream)
-                } else {
-                    downstream::handle_request(&ctx, (&req, &id), client_stream)
+            ClientRequest::ToolsCall(ref call_req) => match call_req.name.as_str() {
+                "analyze_route" => handle_analyze_route(call_req.clone(), id, &ctx, client_stream),
+                "route_cost" => handle_route_cost(call_req.clone(), id, &ctx, client_stream),
+                "segment_route" => handle_segment_route(call_req.clone(), id, &ctx, client_stream),
+                "distance_to_route" => {
+                    handle_distance_to_route(call_req.clone(), id, &ctx, client_stream)
                 }
-            }
+                "optimize_order" => {
+                    handle_optimize_order(call_req.clone(), id, &ctx, client_stream)
+                }
+                "direction_histogram" => {
+                    handle_direction_histogram(call_req.clone(), id, &ctx, client_stream)
+                }
+                "route_score" => handle_route_score(call_req.clone(), id, &ctx, client_stream),
+                "straightness" => handle_straightness(call_req.clone(), id, &ctx, client_stream),
+                _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
+            },
             _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
         }
     }
@@ -93,19 +122,333 @@ fn handle_tools_list(
         }),
     };
 
+    let route_cost_tool = Tool {
+        name: "route_cost".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Route waypoints (at least 2 points)"
+                },
+                "consumption_per_km": {
+                    "type": "number",
+                    "exclusiveMinimum": 0,
+                    "description": "Fuel or energy units consumed per kilometre"
+                },
+                "cost_per_unit": {
+                    "type": "number",
+                    "exclusiveMinimum": 0,
+                    "description": "Cost of one unit of fuel or energy"
+                }
+            },
+            "required": ["waypoints", "consumption_per_km", "cost_per_unit"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Estimate fuel or energy use and operating cost for a route through multiple GPS waypoints. \
+                 Returns total distance, consumption and cost with a per-segment breakdown."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Route Cost Estimator".to_string()),
+        }),
+    };
+
+    let segment_route_tool = Tool {
+        name: "segment_route".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Route waypoints (at least 2 points)"
+                },
+                "max_segment_km": {
+                    "type": "number",
+                    "exclusiveMinimum": 0,
+                    "description": "Maximum length of any leg in the output route"
+                }
+            },
+            "required": ["waypoints", "max_segment_km"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Split a route into legs no longer than max_segment_km. \
+                 Legs that are too long are divided evenly by inserting great-circle interpolated waypoints."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Route Segmenter".to_string()),
+        }),
+    };
+
+    let optimize_order_tool = Tool {
+        name: "optimize_order".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 1,
+                    "maxItems": 100,
+                    "description": "Waypoints to visit; the first one is the starting point"
+                },
+                "two_opt": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Refine the nearest-neighbor tour with 2-opt segment reversals"
+                }
+            },
+            "required": ["waypoints"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Suggest a visiting order for waypoints using the nearest-neighbor heuristic, starting from the first waypoint, \
+                 optionally refined with 2-opt swaps. The result is usually good but not guaranteed optimal. \
+                 Returns the reordered waypoints and the path distance."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Waypoint Order Optimizer".to_string()),
+        }),
+    };
+
+    let distance_to_route_tool = Tool {
+        name: "distance_to_route".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "point": {
+                    "type": "object",
+                    "properties": {
+                        "lat": {"type": "number"},
+                        "lon": {"type": "number"}
+                    },
+                    "required": ["lat", "lon"],
+                    "description": "Point to measure from"
+                },
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Route waypoints in travel order"
+                }
+            },
+            "required": ["point", "waypoints"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Find the closest approach of a point to a multi-segment route. \
+                 Uses the cross-track distance to each great-circle leg, or the distance to the nearer endpoint when the \
+                 perpendicular falls outside the leg. Returns the minimum distance and the index of the nearest segment."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Distance to Route".to_string()),
+        }),
+    };
+
+    let direction_histogram_tool = Tool {
+        name: "direction_histogram".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Route waypoints in travel order"
+                },
+                "bins": {
+                    "type": "integer",
+                    "enum": [8, 16],
+                    "default": 8,
+                    "description": "Number of compass sectors"
+                }
+            },
+            "required": ["waypoints"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Break down how much of a route runs in each compass direction. \
+                 Each leg's distance is added to the 8 or 16-point sector containing its initial bearing. \
+                 Returns the distance and fraction of the total per sector."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Route Direction Histogram".to_string()),
+        }),
+    };
+
+    let route_score_tool = Tool {
+        name: "route_score".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"},
+                            "elevation_m": {"type": "number", "description": "Elevation in meters (give for every waypoint or none)"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Route waypoints in travel order"
+                },
+                "distance_weight": {
+                    "type": "number",
+                    "minimum": 0,
+                    "default": 1,
+                    "description": "Score per kilometre travelled"
+                },
+                "turning_weight": {
+                    "type": "number",
+                    "minimum": 0,
+                    "default": 1,
+                    "description": "Score per degree of heading change between legs"
+                },
+                "climb_weight": {
+                    "type": "number",
+                    "minimum": 0,
+                    "default": 1,
+                    "description": "Score per metre of ascent"
+                }
+            },
+            "required": ["waypoints"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Score a route as a weighted sum of its distance, total turning and total climb, for comparing alternatives. \
+                 Lower is better. Returns the score together with each raw and weighted component."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Route Score".to_string()),
+        }),
+    };
+
+    let straightness_tool = Tool {
+        name: "straightness".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "waypoints": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number"},
+                            "lon": {"type": "number"}
+                        },
+                        "required": ["lat", "lon"]
+                    },
+                    "minItems": 2,
+                    "description": "Track waypoints in travel order"
+                }
+            },
+            "required": ["waypoints"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Measure how straight a track is: the great-circle distance from first to last waypoint divided by the summed leg distances. \
+                 1.0 is perfectly straight; lower values mean more detours. Undefined (null) for a track that never moves."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Route Straightness".to_string()),
+        }),
+    };
+
+    let our_tools = vec![
+        route_tool,
+        route_cost_tool,
+        segment_route_tool,
+        optimize_order_tool,
+        distance_to_route_tool,
+        direction_histogram_tool,
+        route_score_tool,
+        straightness_tool,
+    ];
+
     let downstream_req = ClientRequest::ToolsList(req.clone());
     match downstream::handle_request(ctx, (&downstream_req, &id), client_stream) {
         Ok(ServerResponse::ToolsList(mut downstream_result)) => {
-            downstream_result.tools.push(route_tool);
+            downstream_result.tools.extend(our_tools);
             Ok(ServerResponse::ToolsList(downstream_result))
         }
         Err(ErrorCode::MethodNotFound(_)) => Ok(ServerResponse::ToolsList(ListToolsResult {
-            tools: vec![route_tool],
+            tools: our_tools,
             next_cursor: None,
             meta: None,
         })),
         Err(_) | Ok(_) => Ok(ServerResponse::ToolsList(ListToolsResult {
-            tools: vec![route_tool],
+            tools: our_tools,
             next_cursor: None,
             meta: None,
         })),
@@ -141,15 +484,17 @@ fn handle_analyze_route(
             from.0, from.1, to.0, to.1
         );
 
-        let distance_result = match call_downstream_tool(ctx, "distance", &distance_args, &id, client_stream) {
-            Ok(r) => r,
-            Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
-        };
+        let distance_result =
+            match call_downstream_tool(ctx, "distance", &distance_args, &id, client_stream) {
+                Ok(r) => r,
+                Err(e) => return e.into_response(id),
+            };
 
-        let bearing_result = match call_downstream_tool(ctx, "bearing", &distance_args, &id, client_stream) {
-            Ok(r) => r,
-            Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
-        };
+        let bearing_result =
+            match call_downstream_tool(ctx, "bearing", &distance_args, &id, client_stream) {
+                Ok(r) => r,
+                Err(e) => return e.into_response(id),
+            };
 
         let dist_json: serde_json::Value = serde_json::from_str(&distance_result)
             .unwrap_or_else(|_| serde_json::json!({"distance_km": 0.0}));
@@ -187,48 +532,868 @@ fn handle_analyze_route(
     }))
 }
 
-fn call_downstream_tool(
+fn handle_route_cost(
+    request: CallToolRequest,
+    id: RequestId,
     ctx: &Context,
-    tool_name: &str,
-    arguments: &str,
-    request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<String, String> {
-    let tool_request = CallToolRequest {
-        name: tool_name.to_string(),
-        arguments: Some(arguments.to_string()),
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
     };
 
-    let downstream_req = ClientRequest::ToolsCall(tool_request);
-
-    match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
-        Ok(ServerResponse::ToolsCall(result)) => {
-            if result.is_error == Some(true) {
-                return Err(format!("Tool '{}' returned an error", tool_name));
-            }
-            if let Some(ContentBlock::Text(text)) = result.content.first() {
-                if let TextData::Text(content) = &text.text {
-                    return Ok(content.clone());
-                }
-            }
-            Ok("{}".to_string())
-        }
-        Ok(_) => Err(format!("Unexpected response type from '{}'", tool_name)),
-        Err(ErrorCode::MethodNotFound(_)) => Err(format!(
-            "Tool '{}' not found. Ensure geospatial tools come AFTER route-optimizer in the pipeline.",
-            tool_name
-        )),
-        Err(e) => Err(format!("Error calling '{}': {:?}", tool_name, e)),
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
     }
-}
 
-fn parse_waypoints(arguments: &Option<String>) -> Result<Vec<(f64, f64)>, String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
+    let consumption_per_km = match parse_positive_number(&request.arguments, "consumption_per_km") {
+        Ok(v) => v,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
 
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+    let cost_per_unit = match parse_positive_number(&request.arguments, "cost_per_unit") {
+        Ok(v) => v,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let segment_distances = match route_segment_distances(ctx, &waypoints, &id, client_stream) {
+        Ok(d) => d,
+        Err(e) => return e.into_response(id),
+    };
+
+    let segments: Vec<serde_json::Value> = segment_distances
+        .iter()
+        .enumerate()
+        .map(|(i, distance_km)| {
+            let consumption = distance_km * consumption_per_km;
+            serde_json::json!({
+                "from_index": i,
+                "to_index": i + 1,
+                "distance_km": distance_km,
+                "consumption": consumption,
+                "cost": consumption * cost_per_unit
+            })
+        })
+        .collect();
+
+    let total_distance_km: f64 = segment_distances.iter().sum();
+    let total_consumption = total_distance_km * consumption_per_km;
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "total_distance_km": total_distance_km,
+            "consumption_per_km": consumption_per_km,
+            "cost_per_unit": cost_per_unit,
+            "total_consumption": total_consumption,
+            "total_cost": total_consumption * cost_per_unit,
+            "segments": segments
+        }),
+    )))
+}
+
+fn handle_segment_route(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
+    }
+
+    let max_segment_km = match parse_positive_number(&request.arguments, "max_segment_km") {
+        Ok(v) => v,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let segment_distances = match route_segment_distances(ctx, &waypoints, &id, client_stream) {
+        Ok(d) => d,
+        Err(e) => return e.into_response(id),
+    };
+
+    let Some(pieces) = segment_pieces(&segment_distances, max_segment_km) else {
+        return Ok(ServerResponse::ToolsCall(error_result(format!(
+            "Segmenting would produce more than {} waypoints. Increase max_segment_km",
+            MAX_SEGMENTED_WAYPOINTS
+        ))));
+    };
+
+    let mut densified = vec![waypoints[0]];
+    let mut longest_segment_km: f64 = 0.0;
+    for (i, (&distance_km, &n)) in segment_distances.iter().zip(&pieces).enumerate() {
+        let (from, to) = (waypoints[i], waypoints[i + 1]);
+        for k in 1..n {
+            densified.push(interpolate_great_circle(
+                from,
+                to,
+                distance_km,
+                k as f64 / n as f64,
+            ));
+        }
+        densified.push(to);
+        longest_segment_km = longest_segment_km.max(distance_km / n as f64);
+    }
+
+    let points: Vec<serde_json::Value> = densified
+        .iter()
+        .map(|(lat, lon)| serde_json::json!({"lat": lat, "lon": lon}))
+        .collect();
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "max_segment_km": max_segment_km,
+            "total_distance_km": segment_distances.iter().sum::<f64>(),
+            "original_waypoints": waypoints.len(),
+            "output_waypoints": densified.len(),
+            "inserted_waypoints": densified.len() - waypoints.len(),
+            "longest_segment_km": longest_segment_km,
+            "waypoints": points
+        }),
+    )))
+}
+
+/// Number of equal pieces each leg is split into: the fewest that keep every piece
+/// within `max_segment_km`. `None` if the densified route would exceed
+/// `MAX_SEGMENTED_WAYPOINTS`; counts are summed as f64 so a tiny limit cannot
+/// overflow usize before the check.
+pub(crate) fn segment_pieces(segment_distances: &[f64], max_segment_km: f64) -> Option<Vec<usize>> {
+    let pieces: Vec<f64> = segment_distances
+        .iter()
+        .map(|d| (d / max_segment_km).ceil().max(1.0))
+        .collect();
+
+    if pieces.iter().sum::<f64>() + 1.0 > MAX_SEGMENTED_WAYPOINTS as f64 {
+        return None;
+    }
+
+    Some(pieces.into_iter().map(|n| n as usize).collect())
+}
+
+/// Point at `fraction` of the way along the great circle from `from` to `to`,
+/// given the leg length in km. Coincident or antipodal endpoints fall back to
+/// linear interpolation since the great circle is not unique there.
+pub(crate) fn interpolate_great_circle(
+    from: (f64, f64),
+    to: (f64, f64),
+    distance_km: f64,
+    fraction: f64,
+) -> (f64, f64) {
+    let delta = distance_km / EARTH_RADIUS_KM;
+    let sin_delta = delta.sin();
+    if sin_delta.abs() < 1e-12 {
+        return (
+            from.0 + (to.0 - from.0) * fraction,
+            from.1 + (to.1 - from.1) * fraction,
+        );
+    }
+
+    let (lat1, lon1) = (from.0 * PI / 180.0, from.1 * PI / 180.0);
+    let (lat2, lon2) = (to.0 * PI / 180.0, to.1 * PI / 180.0);
+
+    let a = ((1.0 - fraction) * delta).sin() / sin_delta;
+    let b = (fraction * delta).sin() / sin_delta;
+
+    let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+    let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+    let z = a * lat1.sin() + b * lat2.sin();
+
+    let lat = z.atan2((x * x + y * y).sqrt());
+    let lon = y.atan2(x);
+
+    (lat * 180.0 / PI, lon * 180.0 / PI)
+}
+
+fn handle_optimize_order(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.is_empty() {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "At least 1 waypoint is required".to_string(),
+        )));
+    }
+
+    if waypoints.len() > MAX_ORDER_WAYPOINTS {
+        return Ok(ServerResponse::ToolsCall(error_result(format!(
+            "At most {} waypoints can be ordered",
+            MAX_ORDER_WAYPOINTS
+        ))));
+    }
+
+    let use_two_opt = match parse_optional_bool(&request.arguments, "two_opt") {
+        Ok(v) => v.unwrap_or(true),
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let matrix = match distance_matrix(ctx, &waypoints, &id, client_stream) {
+        Ok(m) => m,
+        Err(e) => return e.into_response(id),
+    };
+
+    let input_order: Vec<usize> = (0..waypoints.len()).collect();
+    let nearest_neighbor = nearest_neighbor_order(&matrix);
+
+    let (order, two_opt) = if use_two_opt {
+        let (improved, passes, converged) = two_opt_improve(&matrix, nearest_neighbor.clone());
+        let summary = serde_json::json!({
+            "passes": passes,
+            "converged": converged,
+            "nearest_neighbor_distance_km": path_length(&matrix, &nearest_neighbor)
+        });
+        (improved, Some(summary))
+    } else {
+        (nearest_neighbor, None)
+    };
+
+    let heuristic = if use_two_opt {
+        "nearest_neighbor+2-opt"
+    } else {
+        "nearest_neighbor"
+    };
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "order": order,
+            "waypoints": order_to_json(&waypoints, &order),
+            "total_distance_km": path_length(&matrix, &order),
+            "input_distance_km": path_length(&matrix, &input_order),
+            "heuristic": heuristic,
+            "two_opt": two_opt
+        }),
+    )))
+}
+
+/// Greedy tour from waypoint 0, always moving to the closest unvisited waypoint.
+/// With fewer than three waypoints the input order is already the only choice.
+pub(crate) fn nearest_neighbor_order(matrix: &[Vec<f64>]) -> Vec<usize> {
+    let n = matrix.len();
+    if n < 3 {
+        return (0..n).collect();
+    }
+
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut current = 0;
+    visited[0] = true;
+    order.push(0);
+
+    while order.len() < n {
+        let next = (0..n)
+            .filter(|&j| !visited[j])
+            .min_by(|&a, &b| matrix[current][a].total_cmp(&matrix[current][b]))
+            .unwrap_or(current);
+        visited[next] = true;
+        order.push(next);
+        current = next;
+    }
+
+    order
+}
+
+/// Repeatedly reverse the sub-path `order[i..=j]` whenever that shortens the open path,
+/// until a full pass finds no improvement or `MAX_TWO_OPT_PASSES` is reached.
+/// The first waypoint stays fixed as the start; the end of the path is free.
+/// Returns the improved order, the number of passes and whether it converged.
+pub(crate) fn two_opt_improve(
+    matrix: &[Vec<f64>],
+    mut order: Vec<usize>,
+) -> (Vec<usize>, usize, bool) {
+    let n = order.len();
+    if n < 4 {
+        return (order, 0, true);
+    }
+
+    for pass in 1..=MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+
+        for i in 1..n - 1 {
+            for j in (i + 1)..n {
+                let (before, first, last) = (order[i - 1], order[i], order[j]);
+                let removed = matrix[before][first];
+                let added = matrix[before][last];
+                // Reversing a suffix only changes the edge into it
+                let (removed, added) = match order.get(j + 1) {
+                    Some(&after) => (removed + matrix[last][after], added + matrix[first][after]),
+                    None => (removed, added),
+                };
+
+                // Tolerance keeps rounding noise from cycling between equal tours
+                if added < removed - 1e-9 {
+                    order[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            return (order, pass, true);
+        }
+    }
+
+    (order, MAX_TWO_OPT_PASSES, false)
+}
+
+/// Total length of the open path visiting `order`
+pub(crate) fn path_length(matrix: &[Vec<f64>], order: &[usize]) -> f64 {
+    order.windows(2).map(|w| matrix[w[0]][w[1]]).sum()
+}
+
+fn order_to_json(waypoints: &[(f64, f64)], order: &[usize]) -> Vec<serde_json::Value> {
+    order
+        .iter()
+        .map(|&i| {
+            let (lat, lon) = waypoints[i];
+            serde_json::json!({"lat": lat, "lon": lon, "original_index": i})
+        })
+        .collect()
+}
+
+fn handle_distance_to_route(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let point = match parse_point(&request.arguments, "point") {
+        Ok(p) => p,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
+    }
+
+    let mut segments = Vec::new();
+    let mut nearest: Option<(usize, f64)> = None;
+
+    for (i, pair) in waypoints.windows(2).enumerate() {
+        let segment = match point_to_segment(ctx, point, pair[0], pair[1], &id, client_stream) {
+            Ok(s) => s,
+            Err(e) => return e.into_response(id),
+        };
+
+        if nearest.map_or(true, |(_, best)| segment.distance_km < best) {
+            nearest = Some((i, segment.distance_km));
+        }
+
+        segments.push(serde_json::json!({
+            "segment_index": i,
+            "distance_km": segment.distance_km,
+            "closest_to": segment.closest_to
+        }));
+    }
+
+    let (nearest_index, distance_km) = nearest.unwrap_or((0, 0.0));
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "distance_km": distance_km,
+            "nearest_segment_index": nearest_index,
+            "nearest_segment": {
+                "from": {"lat": waypoints[nearest_index].0, "lon": waypoints[nearest_index].1},
+                "to": {"lat": waypoints[nearest_index + 1].0, "lon": waypoints[nearest_index + 1].1}
+            },
+            "segments": segments
+        }),
+    )))
+}
+
+fn handle_direction_histogram(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
+    }
+
+    let bins = match parse_compass_bins(&request.arguments) {
+        Ok(b) => b,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let mut bin_distances = vec![0.0; bins];
+    for pair in waypoints.windows(2) {
+        let distance = match downstream_distance_km(ctx, pair[0], pair[1], &id, client_stream) {
+            Ok(d) => d,
+            Err(e) => return e.into_response(id),
+        };
+        // Zero-length legs have no direction and contribute nothing
+        if distance == 0.0 {
+            continue;
+        }
+        let bearing = match downstream_bearing_degrees(ctx, pair[0], pair[1], &id, client_stream) {
+            Ok(b) => b,
+            Err(e) => return e.into_response(id),
+        };
+        bin_distances[compass_bin(bearing, bins)] += distance;
+    }
+
+    let total_distance_km: f64 = bin_distances.iter().sum();
+    let step = COMPASS_POINTS.len() / bins;
+    let sectors: Vec<serde_json::Value> = bin_distances
+        .iter()
+        .enumerate()
+        .map(|(i, distance_km)| {
+            let fraction = if total_distance_km > 0.0 {
+                distance_km / total_distance_km
+            } else {
+                0.0
+            };
+            serde_json::json!({
+                "direction": COMPASS_POINTS[i * step],
+                "center_degrees": i as f64 * 360.0 / bins as f64,
+                "distance_km": distance_km,
+                "fraction": fraction
+            })
+        })
+        .collect();
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "bins": bins,
+            "total_distance_km": total_distance_km,
+            "sectors": sectors
+        }),
+    )))
+}
+
+/// Index of the compass sector containing `bearing`; sector 0 is centred on north
+pub(crate) fn compass_bin(bearing: f64, bins: usize) -> usize {
+    let width = 360.0 / bins as f64;
+    ((bearing.rem_euclid(360.0) + width / 2.0) / width) as usize % bins
+}
+
+fn handle_route_score(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
+    }
+
+    let elevations = match parse_elevations(&request.arguments) {
+        Ok(e) => e,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let distance_weight = match parse_weight(&request.arguments, "distance_weight") {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let turning_weight = match parse_weight(&request.arguments, "turning_weight") {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let climb_weight = match parse_weight(&request.arguments, "climb_weight") {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let segment_distances = match route_segment_distances(ctx, &waypoints, &id, client_stream) {
+        Ok(d) => d,
+        Err(e) => return e.into_response(id),
+    };
+
+    // Zero-length legs have no heading, so turning is measured between moving legs only
+    let mut bearings = Vec::new();
+    for (pair, distance) in waypoints.windows(2).zip(&segment_distances) {
+        if *distance == 0.0 {
+            continue;
+        }
+        match downstream_bearing_degrees(ctx, pair[0], pair[1], &id, client_stream) {
+            Ok(b) => bearings.push(b),
+            Err(e) => return e.into_response(id),
+        }
+    }
+
+    let total_distance_km: f64 = segment_distances.iter().sum();
+    let total_turning_degrees: f64 = bearings.windows(2).map(|b| turn_angle(b[0], b[1])).sum();
+    let (total_climb_m, max_grade_percent) = match &elevations {
+        Some(elevations) => (
+            total_climb(elevations),
+            max_grade_percent(elevations, &segment_distances),
+        ),
+        None => (0.0, None),
+    };
+
+    let distance_score = distance_weight * total_distance_km;
+    let turning_score = turning_weight * total_turning_degrees;
+    let climb_score = climb_weight * total_climb_m;
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "score": distance_score + turning_score + climb_score,
+            "components": {
+                "distance": {
+                    "total_distance_km": total_distance_km,
+                    "weight": distance_weight,
+                    "score": distance_score
+                },
+                "turning": {
+                    "total_turning_degrees": total_turning_degrees,
+                    "weight": turning_weight,
+                    "score": turning_score
+                },
+                "climb": {
+                    "total_climb_m": total_climb_m,
+                    "max_grade_percent": max_grade_percent,
+                    "weight": climb_weight,
+                    "score": climb_score
+                }
+            },
+            "has_elevation": elevations.is_some()
+        }),
+    )))
+}
+
+/// Magnitude of the heading change in degrees (0 to 180) from one leg to the next
+pub(crate) fn turn_angle(from_bearing: f64, to_bearing: f64) -> f64 {
+    ((to_bearing - from_bearing + 180.0).rem_euclid(360.0) - 180.0).abs()
+}
+
+/// Sum of elevation gains between consecutive waypoints; descents do not count
+pub(crate) fn total_climb(elevations: &[f64]) -> f64 {
+    elevations
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).max(0.0))
+        .sum()
+}
+
+/// Steepest uphill grade (%) over legs with horizontal extent, or `None` if there are none
+pub(crate) fn max_grade_percent(elevations: &[f64], distances_km: &[f64]) -> Option<f64> {
+    elevations
+        .windows(2)
+        .zip(distances_km)
+        .filter(|(_, distance)| **distance > 0.0)
+        .map(|(pair, distance)| (pair[1] - pair[0]) / (distance * 1000.0) * 100.0)
+        .reduce(f64::max)
+}
+
+fn handle_straightness(
+    request: CallToolRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let waypoints = match parse_waypoints(&request.arguments) {
+        Ok(w) => w,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if waypoints.len() < 2 {
+        return Ok(ServerResponse::ToolsCall(error_result(
+            "Route must have at least 2 waypoints".to_string(),
+        )));
+    }
+
+    let segment_distances = match route_segment_distances(ctx, &waypoints, &id, client_stream) {
+        Ok(d) => d,
+        Err(e) => return e.into_response(id),
+    };
+
+    let first = waypoints[0];
+    let last = waypoints[waypoints.len() - 1];
+    let direct_distance_km = match downstream_distance_km(ctx, first, last, &id, client_stream) {
+        Ok(d) => d,
+        Err(e) => return e.into_response(id),
+    };
+
+    let total_distance_km: f64 = segment_distances.iter().sum();
+
+    Ok(ServerResponse::ToolsCall(structured_result(
+        serde_json::json!({
+            "straightness": straightness(direct_distance_km, total_distance_km),
+            "direct_distance_km": direct_distance_km,
+            "total_distance_km": total_distance_km,
+            "segment_count": segment_distances.len()
+        }),
+    )))
+}
+
+/// Ratio of the direct distance to the travelled distance, capped at 1 against
+/// rounding; `None` when nothing was travelled
+pub(crate) fn straightness(direct_distance_km: f64, total_distance_km: f64) -> Option<f64> {
+    (total_distance_km > 0.0).then(|| (direct_distance_km / total_distance_km).min(1.0))
+}
+
+struct SegmentApproach {
+    distance_km: f64,
+    /// `start`, `end` or `interior`: which part of the leg is closest to the point
+    closest_to: &'static str,
+}
+
+/// Closest approach of `point` to the great-circle leg `from` -> `to`, from the
+/// downstream `distance` and `bearing` tools. The cross-track distance applies when
+/// the perpendicular foot lands on the leg; otherwise the nearer endpoint wins.
+fn point_to_segment(
+    ctx: &Context,
+    point: (f64, f64),
+    from: (f64, f64),
+    to: (f64, f64),
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<SegmentApproach, DownstreamError> {
+    let to_start = downstream_distance_km(ctx, from, point, id, client_stream)?;
+    let to_end = downstream_distance_km(ctx, to, point, id, client_stream)?;
+    let leg = downstream_distance_km(ctx, from, to, id, client_stream)?;
+
+    let endpoint = if to_start <= to_end {
+        SegmentApproach {
+            distance_km: to_start,
+            closest_to: "start",
+        }
+    } else {
+        SegmentApproach {
+            distance_km: to_end,
+            closest_to: "end",
+        }
+    };
+
+    // Degenerate leg or point on the start: no direction to project onto
+    if leg == 0.0 || to_start == 0.0 {
+        return Ok(endpoint);
+    }
+
+    let leg_bearing = downstream_bearing_degrees(ctx, from, to, id, client_stream)?;
+    let point_bearing = downstream_bearing_degrees(ctx, from, point, id, client_stream)?;
+    let delta = (point_bearing - leg_bearing) * PI / 180.0;
+
+    // Point lies behind the start of the leg
+    if delta.cos() < 0.0 {
+        return Ok(endpoint);
+    }
+
+    let angular_start = to_start / EARTH_RADIUS_KM;
+    let cross_track = (angular_start.sin() * delta.sin()).clamp(-1.0, 1.0).asin();
+    let along_track_km = EARTH_RADIUS_KM
+        * (angular_start.cos() / cross_track.cos())
+            .clamp(-1.0, 1.0)
+            .acos();
+
+    if along_track_km > leg {
+        return Ok(endpoint);
+    }
+
+    Ok(SegmentApproach {
+        distance_km: (cross_track * EARTH_RADIUS_KM)
+            .abs()
+            .min(endpoint.distance_km),
+        closest_to: "interior",
+    })
+}
+
+/// Symmetric matrix of downstream `distance` results between every pair of waypoints
+fn distance_matrix(
+    ctx: &Context,
+    waypoints: &[(f64, f64)],
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<Vec<Vec<f64>>, DownstreamError> {
+    let n = waypoints.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let d = downstream_distance_km(ctx, waypoints[i], waypoints[j], id, client_stream)?;
+            matrix[i][j] = d;
+            matrix[j][i] = d;
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Distance in km of each consecutive leg, measured by the downstream `distance` tool
+fn route_segment_distances(
+    ctx: &Context,
+    waypoints: &[(f64, f64)],
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<Vec<f64>, DownstreamError> {
+    let mut distances = Vec::with_capacity(waypoints.len().saturating_sub(1));
+
+    for pair in waypoints.windows(2) {
+        distances.push(downstream_distance_km(
+            ctx,
+            pair[0],
+            pair[1],
+            id,
+            client_stream,
+        )?);
+    }
+
+    Ok(distances)
+}
+
+/// Great-circle distance in km between two waypoints via the downstream `distance` tool
+fn downstream_distance_km(
+    ctx: &Context,
+    from: (f64, f64),
+    to: (f64, f64),
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<f64, DownstreamError> {
+    let distance_args = format!(
+        r#"{{"lat1": {}, "lon1": {}, "lat2": {}, "lon2": {}}}"#,
+        from.0, from.1, to.0, to.1
+    );
+
+    let distance_result = call_downstream_tool(ctx, "distance", &distance_args, id, client_stream)?;
+    let dist_json: serde_json::Value = serde_json::from_str(&distance_result).map_err(|e| {
+        DownstreamError::ToolError(format!("Invalid response from 'distance': {}", e))
+    })?;
+
+    dist_json["distance_km"].as_f64().ok_or_else(|| {
+        DownstreamError::ToolError("Response from 'distance' is missing 'distance_km'".to_string())
+    })
+}
+
+/// Initial bearing in degrees from `from` to `to` via the downstream `bearing` tool
+fn downstream_bearing_degrees(
+    ctx: &Context,
+    from: (f64, f64),
+    to: (f64, f64),
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<f64, DownstreamError> {
+    let bearing_args = format!(
+        r#"{{"lat1": {}, "lon1": {}, "lat2": {}, "lon2": {}}}"#,
+        from.0, from.1, to.0, to.1
+    );
+
+    let bearing_result = call_downstream_tool(ctx, "bearing", &bearing_args, id, client_stream)?;
+    let bearing_json: serde_json::Value = serde_json::from_str(&bearing_result).map_err(|e| {
+        DownstreamError::ToolError(format!("Invalid response from 'bearing': {}", e))
+    })?;
+
+    bearing_json["bearing_degrees"].as_f64().ok_or_else(|| {
+        DownstreamError::ToolError(
+            "Response from 'bearing' is missing 'bearing_degrees'".to_string(),
+        )
+    })
+}
+
+/// Why a downstream tool call produced no usable value
+enum DownstreamError {
+    /// No handler in the downstream chain provides the tool
+    NotFound(String),
+    /// The tool ran but returned an error result or an unusable value
+    ToolError(String),
+    /// The downstream chain failed with any other JSON-RPC error
+    Protocol(ErrorCode),
+}
+
+impl DownstreamError {
+    /// Middleware response for a failed downstream call: a missing tool is a
+    /// `MethodNotFound` error rather than an `is_error` tool result
+    fn into_response(self, id: RequestId) -> Result<ServerResponse, ErrorCode> {
+        match self {
+            DownstreamError::NotFound(message) => Err(ErrorCode::MethodNotFound(Error {
+                id: Some(id),
+                code: -32601,
+                message,
+                data: None,
+            })),
+            DownstreamError::ToolError(message) => {
+                Ok(ServerResponse::ToolsCall(error_result(message)))
+            }
+            DownstreamError::Protocol(e) => Err(e),
+        }
+    }
+}
+
+fn call_downstream_tool(
+    ctx: &Context,
+    tool_name: &str,
+    arguments: &str,
+    request_id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<String, DownstreamError> {
+    let tool_request = CallToolRequest {
+        name: tool_name.to_string(),
+        arguments: Some(arguments.to_string()),
+    };
+
+    let downstream_req = ClientRequest::ToolsCall(tool_request);
+
+    match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
+        Ok(ServerResponse::ToolsCall(result)) => {
+            if result.is_error == Some(true) {
+                return Err(DownstreamError::ToolError(format!(
+                    "Tool '{}' returned an error",
+                    tool_name
+                )));
+            }
+            if let Some(ContentBlock::Text(text)) = result.content.first() {
+                if let TextData::Text(content) = &text.text {
+                    return Ok(content.clone());
+                }
+            }
+            Ok("{}".to_string())
+        }
+        Ok(_) => Err(DownstreamError::ToolError(format!(
+            "Unexpected response type from '{}'",
+            tool_name
+        ))),
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(format!(
+            "Tool '{}' not found. Ensure geospatial tools come AFTER route-optimizer in the pipeline.",
+            tool_name
+        ))),
+        Err(e) => Err(DownstreamError::Protocol(e)),
+    }
+}
+
+fn parse_waypoints(arguments: &Option<String>) -> Result<Vec<(f64, f64)>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
 
     let waypoints_arr = json
         .get("waypoints")
@@ -253,6 +1418,167 @@ fn parse_waypoints(arguments: &Option<String>) -> Result<Vec<(f64, f64)>, String
     Ok(waypoints)
 }
 
+/// Parse a single `{lat, lon}` object under `name`
+fn parse_point(arguments: &Option<String>, name: &str) -> Result<(f64, f64), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let point = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    let lat = point
+        .get("lat")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lat'", name))?;
+
+    let lon = point
+        .get("lon")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lon'", name))?;
+
+    Ok((lat, lon))
+}
+
+/// Parse the optional `waypoints[].elevation_m` values, which must be given for every
+/// waypoint or for none
+fn parse_elevations(arguments: &Option<String>) -> Result<Option<Vec<f64>>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let waypoints_arr = json
+        .get("waypoints")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid 'waypoints' parameter".to_string())?;
+
+    let elevations: Vec<Option<&serde_json::Value>> = waypoints_arr
+        .iter()
+        .map(|wp| wp.get("elevation_m").filter(|v| !v.is_null()))
+        .collect();
+
+    if elevations.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    elevations
+        .iter()
+        .enumerate()
+        .map(|(i, elevation)| {
+            elevation
+                .and_then(|v| v.as_f64())
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| {
+                    format!(
+                        "Missing or invalid 'waypoints[{}].elevation_m': elevations must be given for every waypoint or none",
+                        i
+                    )
+                })
+        })
+        .collect::<Result<Vec<f64>, String>>()
+        .map(Some)
+}
+
+/// Parse an optional non-negative score weight (default 1)
+fn parse_weight(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(1.0),
+        Some(v) => v
+            .as_f64()
+            .filter(|w| w.is_finite() && *w >= 0.0)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid '{}' parameter: must be a non-negative number",
+                    name
+                )
+            }),
+    }
+}
+
+fn parse_positive_number(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let value = json
+        .get(name)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("'{}' must be a positive number", name));
+    }
+
+    Ok(value)
+}
+
+fn parse_optional_bool(arguments: &Option<String>, name: &str) -> Result<Option<bool>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_bool()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid '{}' parameter: must be a boolean", name)),
+    }
+}
+
+/// Parse the optional histogram resolution `bins`: 8 or 16 (default 8)
+fn parse_compass_bins(arguments: &Option<String>) -> Result<usize, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("bins") {
+        None | Some(serde_json::Value::Null) => Ok(8),
+        Some(v) => match v.as_u64() {
+            Some(8) => Ok(8),
+            Some(16) => Ok(16),
+            _ => Err(format!("Invalid 'bins' parameter: {}. Must be 8 or 16", v)),
+        },
+    }
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
 fn error_result(message: String) -> CallToolResult {
     CallToolResult {
         content: vec![ContentBlock::Text(TextContent {
@@ -266,3 +1592,169 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(RouteOptimizer with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Distance matrix for points on a line
+    fn line_matrix(positions: &[f64]) -> Vec<Vec<f64>> {
+        positions
+            .iter()
+            .map(|a| positions.iter().map(|b| (a - b).abs()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn nearest_neighbor_walks_to_closest_point() {
+        let matrix = line_matrix(&[0.0, 10.0, 1.0, 5.0]);
+        assert_eq!(nearest_neighbor_order(&matrix), vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn short_inputs_keep_their_order() {
+        assert_eq!(
+            nearest_neighbor_order(&line_matrix(&[3.0, 1.0])),
+            vec![0, 1]
+        );
+        assert_eq!(nearest_neighbor_order(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn two_opt_untangles_a_crossing() {
+        let matrix = line_matrix(&[0.0, 3.0, 1.0, 2.0]);
+        let (order, _, converged) = two_opt_improve(&matrix, vec![0, 1, 2, 3]);
+        assert!(converged);
+        assert_eq!(order[0], 0);
+        assert_eq!(path_length(&matrix, &order), 3.0);
+    }
+
+    #[test]
+    fn path_length_sums_consecutive_legs() {
+        let matrix = line_matrix(&[0.0, 4.0, 1.0]);
+        assert_eq!(path_length(&matrix, &[0, 1, 2]), 7.0);
+        assert_eq!(path_length(&matrix, &[0]), 0.0);
+    }
+
+    #[test]
+    fn segment_pieces_rejects_tiny_limit() {
+        assert_eq!(
+            segment_pieces(&[10.0, 0.0, 25.0], 10.0),
+            Some(vec![1, 1, 3])
+        );
+        assert_eq!(segment_pieces(&[1000.0], 1e-7), None);
+        assert_eq!(segment_pieces(&[1000.0], 1e-300), None);
+        assert_eq!(
+            segment_pieces(&[1.0; 3], 1.0 / MAX_SEGMENTED_WAYPOINTS as f64),
+            None
+        );
+    }
+
+    #[test]
+    fn great_circle_interpolation_endpoints_and_midpoint() {
+        let from = (0.0, 0.0);
+        let to = (0.0, 90.0);
+        let distance_km = EARTH_RADIUS_KM * PI / 2.0;
+
+        let (lat, lon) = interpolate_great_circle(from, to, distance_km, 0.5);
+        assert!(lat.abs() < 1e-9 && (lon - 45.0).abs() < 1e-9);
+
+        let (lat, lon) = interpolate_great_circle(from, to, distance_km, 1.0);
+        assert!(lat.abs() < 1e-9 && (lon - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_downstream_tool_is_method_not_found() {
+        let not_found = DownstreamError::NotFound("Tool 'X' not found".to_string());
+        match not_found.into_response(RequestId::Number(7)) {
+            Err(ErrorCode::MethodNotFound(e)) => {
+                assert_eq!(e.code, -32601);
+                assert!(matches!(e.id, Some(RequestId::Number(7))));
+            }
+            _ => panic!("expected MethodNotFound"),
+        }
+    }
+
+    #[test]
+    fn downstream_tool_error_is_an_error_result() {
+        let tool_error = DownstreamError::ToolError("Tool 'X' returned an error".to_string());
+        match tool_error.into_response(RequestId::Number(7)) {
+            Ok(ServerResponse::ToolsCall(result)) => assert_eq!(result.is_error, Some(true)),
+            _ => panic!("expected an is_error tool result"),
+        }
+    }
+
+    #[test]
+    fn compass_bins_wrap_around_north() {
+        assert_eq!(compass_bin(0.0, 8), 0);
+        assert_eq!(compass_bin(350.0, 8), 0);
+        assert_eq!(compass_bin(22.4, 8), 0);
+        assert_eq!(compass_bin(22.6, 8), 1);
+        assert_eq!(compass_bin(180.0, 8), 4);
+        assert_eq!(compass_bin(350.0, 16), 0);
+        assert_eq!(compass_bin(340.0, 16), 15);
+    }
+
+    #[test]
+    fn compass_bin_resolution_is_validated() {
+        assert_eq!(parse_compass_bins(&Some("{}".to_string())), Ok(8));
+        assert_eq!(
+            parse_compass_bins(&Some(r#"{"bins": 16}"#.to_string())),
+            Ok(16)
+        );
+        assert!(parse_compass_bins(&Some(r#"{"bins": 4}"#.to_string())).is_err());
+    }
+
+    #[test]
+    fn turn_angle_takes_the_short_way_round() {
+        assert_eq!(turn_angle(10.0, 350.0), 20.0);
+        assert_eq!(turn_angle(350.0, 10.0), 20.0);
+        assert_eq!(turn_angle(90.0, 270.0), 180.0);
+        assert_eq!(turn_angle(45.0, 45.0), 0.0);
+    }
+
+    #[test]
+    fn climb_counts_only_ascent() {
+        assert_eq!(total_climb(&[100.0, 150.0, 120.0, 200.0]), 130.0);
+        assert_eq!(
+            max_grade_percent(&[100.0, 150.0, 150.0], &[1.0, 0.0]),
+            Some(5.0)
+        );
+        assert_eq!(max_grade_percent(&[100.0, 150.0], &[0.0]), None);
+    }
+
+    #[test]
+    fn elevations_are_all_or_nothing() {
+        let none = r#"{"waypoints": [{"lat": 0, "lon": 0}, {"lat": 1, "lon": 1}]}"#;
+        assert_eq!(parse_elevations(&Some(none.to_string())), Ok(None));
+
+        let all = r#"{"waypoints": [{"lat": 0, "lon": 0, "elevation_m": 5}, {"lat": 1, "lon": 1, "elevation_m": 9}]}"#;
+        assert_eq!(
+            parse_elevations(&Some(all.to_string())),
+            Ok(Some(vec![5.0, 9.0]))
+        );
+
+        let partial =
+            r#"{"waypoints": [{"lat": 0, "lon": 0, "elevation_m": 5}, {"lat": 1, "lon": 1}]}"#;
+        assert!(parse_elevations(&Some(partial.to_string())).is_err());
+    }
+
+    #[test]
+    fn score_weights_are_validated() {
+        assert_eq!(
+            parse_weight(&Some("{}".to_string()), "climb_weight"),
+            Ok(1.0)
+        );
+        assert!(
+            parse_weight(&Some(r#"{"climb_weight": -1}"#.to_string()), "climb_weight").is_err()
+        );
+    }
+
+    #[test]
+    fn straightness_ratio() {
+        assert_eq!(straightness(5.0, 5.0), Some(1.0));
+        assert_eq!(straightness(3.0, 6.0), Some(0.5));
+        assert_eq!(straightness(5.000000001, 5.0), Some(1.0));
+        assert_eq!(straightness(0.0, 0.0), None);
+    }
+}
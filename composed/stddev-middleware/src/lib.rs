@@ -79,6 +79,9 @@ fn handle_tools_list(
         vec![]
     };
 
+    // Replace any native tools of the same names downstream, since calls to them stop here
+    tools.retain(|tool| tool.name != "standard_deviation" && tool.name != "stddev");
+
     // Add our standard deviation tool
     tools.push(Tool {
         name: "standard_deviation".to_string(),
@@ -89,6 +92,12 @@ fn handle_tools_list(
                     "type": "array",
                     "items": {"type": "number"},
                     "description": "Array of numbers"
+                },
+                "ddof": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "default": 0,
+                    "description": "Delta degrees of freedom: 0 for the population, 1 for the sample estimate"
                 }
             },
             "required": ["numbers"]
@@ -98,7 +107,7 @@ fn handle_tools_list(
             meta: None,
             annotations: None,
             description: Some(
-                "Calculate the standard deviation (σ) of an array of numbers: √(variance)"
+                "Calculate the standard deviation (σ) of an array of numbers: √(variance), with ddof passed through to variance"
                     .to_string(),
             ),
             output_schema: None,
@@ -116,6 +125,12 @@ fn handle_tools_list(
                     "type": "array",
                     "items": {"type": "number"},
                     "description": "Array of numbers"
+                },
+                "ddof": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "default": 0,
+                    "description": "Delta degrees of freedom: 0 for the population, 1 for the sample estimate"
                 }
             },
             "required": ["numbers"]
@@ -146,13 +161,13 @@ fn handle_stddev_call(
     // Step 1: Call variance tool
     let variance = match call_variance_tool(ctx, &request.arguments, &id, client_stream) {
         Ok(v) => v,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 2: Call square_root tool on the variance
     let stddev = match call_square_root_tool(ctx, variance, &id, client_stream) {
         Ok(sd) => sd,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     Ok(ServerResponse::ToolsCall(success_result(
@@ -160,12 +175,41 @@ fn handle_stddev_call(
     )))
 }
 
+/// Why a downstream tool call produced no usable value
+enum DownstreamError {
+    /// No handler in the downstream chain provides the tool
+    NotFound(String),
+    /// The tool ran but returned an error result or an unusable value
+    ToolError(String),
+    /// The downstream chain failed with any other JSON-RPC error
+    Protocol(ErrorCode),
+}
+
+impl DownstreamError {
+    /// Middleware response for a failed downstream call: a missing tool is a
+    /// `MethodNotFound` error rather than an `is_error` tool result
+    fn into_response(self, id: RequestId) -> Result<ServerResponse, ErrorCode> {
+        match self {
+            DownstreamError::NotFound(message) => Err(ErrorCode::MethodNotFound(Error {
+                id: Some(id),
+                code: -32601,
+                message,
+                data: None,
+            })),
+            DownstreamError::ToolError(message) => {
+                Ok(ServerResponse::ToolsCall(error_result(message)))
+            }
+            DownstreamError::Protocol(e) => Err(e),
+        }
+    }
+}
+
 fn call_variance_tool(
     ctx: &Context,
     arguments: &Option<String>,
     request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<f64, String> {
+) -> Result<f64, DownstreamError> {
     let tool_request = CallToolRequest {
         name: "variance".to_string(),
         arguments: arguments.clone(),
@@ -174,13 +218,17 @@ fn call_variance_tool(
     let downstream_req = ClientRequest::ToolsCall(tool_request);
 
     match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
-        Ok(ServerResponse::ToolsCall(result)) => extract_number_from_result(&result),
-        Err(ErrorCode::MethodNotFound(_)) => Err(
+        Ok(ServerResponse::ToolsCall(result)) => {
+            extract_number_from_result(&result, "variance").map_err(DownstreamError::ToolError)
+        }
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(
             "Tool 'variance' not found. Ensure variance-middleware comes AFTER this middleware in the pipeline."
                 .to_string(),
-        ),
-        Err(e) => Err(format!("Error calling 'variance': {:?}", e)),
-        _ => Err("Unexpected response type".to_string()),
+        )),
+        Err(e) => Err(DownstreamError::Protocol(e)),
+        _ => Err(DownstreamError::ToolError(
+            "Unexpected response type".to_string(),
+        )),
     }
 }
 
@@ -189,7 +237,7 @@ fn call_square_root_tool(
     value: f64,
     request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<f64, String> {
+) -> Result<f64, DownstreamError> {
     let tool_request = CallToolRequest {
         name: "square_root".to_string(),
         arguments: Some(format!(r#"{{"x": {}}}"#, value)),
@@ -198,21 +246,37 @@ fn call_square_root_tool(
     let downstream_req = ClientRequest::ToolsCall(tool_request);
 
     match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
-        Ok(ServerResponse::ToolsCall(result)) => extract_number_from_result(&result),
-        Err(ErrorCode::MethodNotFound(_)) => Err(
+        Ok(ServerResponse::ToolsCall(result)) => {
+            extract_number_from_result(&result, "result").map_err(DownstreamError::ToolError)
+        }
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(
             "Tool 'square_root' not found. Ensure math component comes AFTER this middleware in the pipeline."
                 .to_string(),
-        ),
-        Err(e) => Err(format!("Error calling 'square_root': {:?}", e)),
-        _ => Err("Unexpected response type".to_string()),
+        )),
+        Err(e) => Err(DownstreamError::Protocol(e)),
+        _ => Err(DownstreamError::ToolError(
+            "Unexpected response type".to_string(),
+        )),
     }
 }
 
-fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
+/// Number from a downstream result: `structured_content[field]` when present, as
+/// returned by the native statistics and math tools, otherwise the text parsed as
+/// a plain number, as returned by variance-middleware
+fn extract_number_from_result(result: &CallToolResult, field: &str) -> Result<f64, String> {
     if result.is_error == Some(true) {
         return Err("Tool call returned error".to_string());
     }
 
+    let structured = result
+        .structured_content
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+        .and_then(|value| value.get(field).and_then(|v| v.as_f64()));
+    if let Some(value) = structured {
+        return Ok(value);
+    }
+
     for content in &result.content {
         if let ContentBlock::Text(text_content) = content {
             if let TextData::Text(text) = &text_content.text {
@@ -251,3 +315,39 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(StdDevMiddleware with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_result(text: &str, structured_content: Option<&str>) -> CallToolResult {
+        CallToolResult {
+            content: vec![ContentBlock::Text(TextContent {
+                text: TextData::Text(text.to_string()),
+                options: None,
+            })],
+            is_error: None,
+            meta: None,
+            structured_content: structured_content.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reads_native_variance_from_structured_content() {
+        let json = r#"{"variance":4.0,"mean":5.0,"count":8,"ddof":0}"#;
+        let result = text_result(json, Some(json));
+        assert_eq!(extract_number_from_result(&result, "variance"), Ok(4.0));
+    }
+
+    #[test]
+    fn falls_back_to_plain_text() {
+        let result = text_result("2.5", None);
+        assert_eq!(extract_number_from_result(&result, "variance"), Ok(2.5));
+
+        let result = text_result("3", Some(r#"{"result":3.0,"operation":"square_root"}"#));
+        assert_eq!(extract_number_from_result(&result, "result"), Ok(3.0));
+
+        let result = text_result(r#"{"other":1}"#, Some(r#"{"other":1}"#));
+        assert!(extract_number_from_result(&result, "variance").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Tool Inventory Middleware
+//!
+//! Provides a read-only `tool_inventory` tool that lists everything the composed
+//! server exposes downstream of this middleware. Tools are counted and grouped by
+//! the `category` key of their `meta` when present, otherwise by a name-based
+//! heuristic into `math`, `statistics`, `geospatial` and `other`.
+//!
+//! ## Order Dependency
+//!
+//! Only tools AFTER this middleware in the pipeline are visible, so place it first:
+//! ```bash
+//! wasmcp compose tool-inventory route-optimizer math statistics geospatial-distance -o server.wasm
+//! ```
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-inventory",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::server::handler::Guest;
+use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasmcp::protocol::server_messages::Context;
+use bindings::wasmcp::server::handler as downstream;
+
+/// Guard against downstream handlers that never stop returning cursors
+const MAX_LIST_PAGES: usize = 100;
+
+/// Name tokens (split on `_` and `-`) for tools without a `meta` category, checked in order
+const CATEGORY_KEYWORDS: [(&str, &[&str]); 3] = [
+    (
+        "geospatial",
+        &[
+            "geo",
+            "geohash",
+            "distance",
+            "bearing",
+            "polygon",
+            "route",
+            "waypoints",
+        ],
+    ),
+    (
+        "statistics",
+        &[
+            "mean",
+            "median",
+            "mode",
+            "variance",
+            "deviation",
+            "summary",
+            "quantile",
+        ],
+    ),
+    (
+        "math",
+        &[
+            "add", "subtract", "multiply", "divide", "power", "root", "log",
+        ],
+    ),
+];
+
+struct ToolInventory;
+
+impl Guest for ToolInventory {
+    fn handle_request(
+        ctx: Context,
+        request: (ClientRequest, RequestId),
+        client_stream: Option<&OutputStream>,
+    ) -> Result<ServerResponse, ErrorCode> {
+        let (req, id) = request;
+        match req {
+            ClientRequest::ToolsList(list_req) => {
+                handle_tools_list(list_req, id, &ctx, client_stream)
+            }
+            ClientRequest::ToolsCall(ref call_req) => {
+                if call_req.name == "tool_inventory" {
+                    handle_tool_inventory(id, &ctx, client_stream)
+                } else {
+                    downstream::handle_request(&ctx, (&req, &id), client_stream)
+                }
+            }
+            _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
+        }
+    }
+
+    fn handle_notification(ctx: Context, notification: ClientNotification) {
+        downstream::handle_notification(&ctx, &notification);
+    }
+
+    fn handle_response(ctx: Context, response: Result<(ClientResponse, RequestId), ErrorCode>) {
+        downstream::handle_response(&ctx, response);
+    }
+}
+
+fn handle_tools_list(
+    req: ListToolsRequest,
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let inventory_tool = Tool {
+        name: "tool_inventory".to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {}
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "List the tools exposed by this server. \
+                 Returns the total count and each tool's name and title, grouped by category (math, statistics, geospatial, other, or the category a tool declares in its meta)."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Tool Inventory".to_string()),
+        }),
+    };
+
+    let downstream_req = ClientRequest::ToolsList(req.clone());
+    match downstream::handle_request(ctx, (&downstream_req, &id), client_stream) {
+        Ok(ServerResponse::ToolsList(mut downstream_result)) => {
+            downstream_result.tools.push(inventory_tool);
+            Ok(ServerResponse::ToolsList(downstream_result))
+        }
+        Err(_) | Ok(_) => Ok(ServerResponse::ToolsList(ListToolsResult {
+            tools: vec![inventory_tool],
+            next_cursor: None,
+            meta: None,
+        })),
+    }
+}
+
+fn handle_tool_inventory(
+    id: RequestId,
+    ctx: &Context,
+    client_stream: Option<&OutputStream>,
+) -> Result<ServerResponse, ErrorCode> {
+    let tools = match list_downstream_tools(ctx, &id, client_stream) {
+        Ok(tools) => tools,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    let mut categories = serde_json::Map::new();
+    for (category, _) in CATEGORY_KEYWORDS {
+        categories.insert(category.to_string(), serde_json::json!([]));
+    }
+    categories.insert("other".to_string(), serde_json::json!([]));
+
+    for tool in &tools {
+        let title = tool.options.as_ref().and_then(|o| o.title.clone());
+        let category = meta_category(tool).unwrap_or_else(|| categorize(&tool.name).to_string());
+        if let serde_json::Value::Array(entries) = categories
+            .entry(category)
+            .or_insert_with(|| serde_json::json!([]))
+        {
+            entries.push(serde_json::json!({"name": tool.name, "title": title}));
+        }
+    }
+
+    let counts: serde_json::Map<String, serde_json::Value> = categories
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.clone(),
+                serde_json::json!(v.as_array().map_or(0, |a| a.len())),
+            )
+        })
+        .collect();
+
+    let result = serde_json::json!({
+        "total": tools.len(),
+        "counts": counts,
+        "categories": categories
+    });
+
+    let text = result.to_string();
+    Ok(ServerResponse::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }))
+}
+
+/// Collect every page of the downstream `tools/list`
+fn list_downstream_tools(
+    ctx: &Context,
+    id: &RequestId,
+    client_stream: Option<&OutputStream>,
+) -> Result<Vec<Tool>, String> {
+    let mut tools = Vec::new();
+    let mut cursor = None;
+
+    for _ in 0..MAX_LIST_PAGES {
+        let list_req = ClientRequest::ToolsList(ListToolsRequest { cursor });
+        match downstream::handle_request(ctx, (&list_req, id), client_stream) {
+            Ok(ServerResponse::ToolsList(page)) => {
+                tools.extend(page.tools);
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => return Ok(tools),
+                }
+            }
+            Ok(_) => return Err("Unexpected response type from tools/list".to_string()),
+            // Nothing downstream handles tools/list: the inventory is empty
+            Err(ErrorCode::MethodNotFound(_)) => return Ok(tools),
+            Err(e) => return Err(format!("Error listing downstream tools: {:?}", e)),
+        }
+    }
+
+    Err(format!(
+        "Downstream tools/list did not finish within {} pages",
+        MAX_LIST_PAGES
+    ))
+}
+
+/// Category a tool declares as `{"category": "..."}` in its `meta`
+fn meta_category(tool: &Tool) -> Option<String> {
+    let meta = tool.options.as_ref()?.meta.as_ref()?;
+    let json: serde_json::Value = serde_json::from_str(meta).ok()?;
+    json.get("category")?.as_str().map(str::to_string)
+}
+
+/// Heuristic category from the tool name: the first category with a keyword
+/// equal to one of the name's tokens. Whole tokens avoid false matches such as
+/// "add" inside "padded".
+fn categorize(name: &str) -> &'static str {
+    let name = name.to_ascii_lowercase();
+    let tokens: Vec<&str> = name.split(['_', '-']).collect();
+    CATEGORY_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| tokens.contains(k)))
+        .map_or("other", |(category, _)| category)
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(ToolInventory with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, meta: Option<&str>) -> Tool {
+        Tool {
+            name: name.to_string(),
+            input_schema: "{}".to_string(),
+            options: Some(ToolOptions {
+                meta: meta.map(str::to_string),
+                annotations: None,
+                description: None,
+                output_schema: None,
+                title: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn categorizes_by_whole_tokens() {
+        assert_eq!(categorize("standard_deviation"), "statistics");
+        assert_eq!(categorize("Five_Number_Summary"), "statistics");
+        assert_eq!(categorize("square_root"), "math");
+        assert_eq!(categorize("point_in_polygon"), "geospatial");
+        assert_eq!(categorize("geohash_encode"), "geospatial");
+        assert_eq!(categorize("padded_rows"), "other");
+        assert_eq!(categorize("convert_units"), "other");
+    }
+
+    #[test]
+    fn meta_category_takes_precedence() {
+        let declared = tool("vector_distance", Some(r#"{"category": "math"}"#));
+        assert_eq!(meta_category(&declared).as_deref(), Some("math"));
+
+        assert_eq!(meta_category(&tool("vector_distance", None)), None);
+        assert_eq!(meta_category(&tool("mean", Some(r#"{"other": 1}"#))), None);
+        assert_eq!(meta_category(&tool("mean", Some("not json"))), None);
+    }
+}
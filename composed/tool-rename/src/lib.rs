@@ -0,0 +1,244 @@
+//! Tool Rename Middleware
+//!
+//! Renames downstream tools so that components exposing the same tool name can
+//! be composed into one server without collisions.
+//!
+//! ## Configuration
+//!
+//! Read per request from `Context.data` under `tool-rename` as a UTF-8 JSON object:
+//! ```json
+//! {"renames": {"distance": "geo_distance"}, "prefix": "geo_"}
+//! ```
+//! - `renames`: exact downstream name -> exposed name
+//! - `prefix`: prepended to every downstream name not listed in `renames`
+//!
+//! ## Round Trip
+//!
+//! `tools/list` responses from downstream are rewritten to the exposed names.
+//! A `tools/call` for an exposed name is mapped back to the downstream name before
+//! forwarding; any other name is forwarded unchanged. Without configuration the
+//! middleware is a pass-through.
+//!
+//! ## Multiple Instances
+//!
+//! Renaming only affects tools downstream of this middleware, so place it directly
+//! in front of the component whose tools should be renamed. Because `Context.data`
+//! is shared by the whole pipeline, a second instance needs its own key: set
+//! `TOOL_RENAME_CONTEXT_KEY` at build time (e.g. `tool-rename-geo`).
+//! To expose `euclid_distance` (distance-calculator) and `geo_distance`
+//! (geospatial-distance):
+//! ```bash
+//! # tool-rename:     {"renames": {"distance": "euclid_distance"}}
+//! # tool-rename-geo: {"renames": {"distance": "geo_distance"}}
+//! wasmcp compose tool-rename distance-calculator tool-rename-geo geospatial-distance math -o server.wasm
+//! ```
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-rename",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::server::handler::Guest;
+use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasmcp::protocol::server_messages::Context;
+use bindings::wasmcp::server::handler as downstream;
+use std::collections::HashMap;
+
+const CONFIG_KEY: &str = match option_env!("TOOL_RENAME_CONTEXT_KEY") {
+    Some(key) => key,
+    None => "tool-rename",
+};
+
+struct ToolRename;
+
+impl Guest for ToolRename {
+    fn handle_request(
+        ctx: Context,
+        request: (ClientRequest, RequestId),
+        client_stream: Option<&OutputStream>,
+    ) -> Result<ServerResponse, ErrorCode> {
+        let (req, id) = request;
+
+        let config = match RenameConfig::from_context(&ctx) {
+            Ok(Some(config)) => config,
+            Ok(None) => return downstream::handle_request(&ctx, (&req, &id), client_stream),
+            Err(msg) => {
+                return Err(ErrorCode::InternalError(Error {
+                    id: Some(id),
+                    code: -32603,
+                    message: msg,
+                    data: None,
+                }));
+            }
+        };
+
+        match req {
+            ClientRequest::ToolsList(_) => {
+                match downstream::handle_request(&ctx, (&req, &id), client_stream)? {
+                    ServerResponse::ToolsList(mut result) => {
+                        for tool in &mut result.tools {
+                            tool.name = config.exposed_name(&tool.name);
+                        }
+                        Ok(ServerResponse::ToolsList(result))
+                    }
+                    other => Ok(other),
+                }
+            }
+            ClientRequest::ToolsCall(ref call_req) => {
+                match config.downstream_name(&call_req.name) {
+                    Some(original) => {
+                        let forwarded = ClientRequest::ToolsCall(CallToolRequest {
+                            name: original,
+                            arguments: call_req.arguments.clone(),
+                        });
+                        downstream::handle_request(&ctx, (&forwarded, &id), client_stream)
+                    }
+                    None => downstream::handle_request(&ctx, (&req, &id), client_stream),
+                }
+            }
+            _ => downstream::handle_request(&ctx, (&req, &id), client_stream),
+        }
+    }
+
+    fn handle_notification(ctx: Context, notification: ClientNotification) {
+        downstream::handle_notification(&ctx, &notification);
+    }
+
+    fn handle_response(ctx: Context, response: Result<(ClientResponse, RequestId), ErrorCode>) {
+        downstream::handle_response(&ctx, response);
+    }
+}
+
+pub(crate) struct RenameConfig {
+    renames: HashMap<String, String>,
+    prefix: Option<String>,
+}
+
+impl RenameConfig {
+    fn from_context(ctx: &Context) -> Result<Option<Self>, String> {
+        let raw = match ctx.data.iter().find(|(k, _)| k == CONFIG_KEY) {
+            Some((_, v)) => v,
+            None => return Ok(None),
+        };
+
+        let json: serde_json::Value = serde_json::from_slice(raw)
+            .map_err(|e| format!("Invalid '{}' configuration: {}", CONFIG_KEY, e))?;
+
+        let mut renames = HashMap::new();
+        if let Some(map) = json.get("renames") {
+            let map = map.as_object().ok_or_else(|| {
+                format!(
+                    "Invalid '{}' configuration: 'renames' must be an object",
+                    CONFIG_KEY
+                )
+            })?;
+            for (from, to) in map {
+                let to = to.as_str().ok_or_else(|| {
+                    format!(
+                        "Invalid '{}' configuration: rename target for '{}' must be a string",
+                        CONFIG_KEY, from
+                    )
+                })?;
+                renames.insert(from.clone(), to.to_string());
+            }
+        }
+
+        let prefix = match json.get("prefix") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(p) => Some(
+                p.as_str()
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid '{}' configuration: 'prefix' must be a string",
+                            CONFIG_KEY
+                        )
+                    })?
+                    .to_string(),
+            ),
+        };
+
+        // Two downstream tools mapped to one exposed name could never be told apart
+        let mut targets: Vec<&String> = renames.values().collect();
+        targets.sort();
+        if let Some(pair) = targets.windows(2).find(|w| w[0] == w[1]) {
+            return Err(format!(
+                "Invalid '{}' configuration: more than one tool renamed to '{}'",
+                CONFIG_KEY, pair[0]
+            ));
+        }
+
+        Ok(Some(RenameConfig { renames, prefix }))
+    }
+
+    /// Name a downstream tool is listed under
+    pub(crate) fn exposed_name(&self, downstream_name: &str) -> String {
+        match (self.renames.get(downstream_name), &self.prefix) {
+            (Some(renamed), _) => renamed.clone(),
+            (None, Some(prefix)) => format!("{}{}", prefix, downstream_name),
+            (None, None) => downstream_name.to_string(),
+        }
+    }
+
+    /// Downstream name for an exposed name, or `None` to forward the call unchanged
+    pub(crate) fn downstream_name(&self, exposed_name: &str) -> Option<String> {
+        if let Some((original, _)) = self.renames.iter().find(|(_, to)| *to == exposed_name) {
+            return Some(original.clone());
+        }
+
+        let prefix = self.prefix.as_deref()?;
+        let stripped = exposed_name.strip_prefix(prefix)?;
+        // Explicitly renamed tools are not also reachable through the prefix
+        (!self.renames.contains_key(stripped)).then(|| stripped.to_string())
+    }
+}
+
+bindings::export!(ToolRename with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(renames: &[(&str, &str)], prefix: Option<&str>) -> RenameConfig {
+        RenameConfig {
+            renames: renames
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+            prefix: prefix.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn explicit_renames_round_trip() {
+        let config = config(&[("distance", "geo_distance")], None);
+        assert_eq!(config.exposed_name("distance"), "geo_distance");
+        assert_eq!(config.exposed_name("bearing"), "bearing");
+        assert_eq!(
+            config.downstream_name("geo_distance"),
+            Some("distance".to_string())
+        );
+        assert_eq!(config.downstream_name("bearing"), None);
+    }
+
+    #[test]
+    fn prefix_applies_to_unlisted_tools() {
+        let config = config(&[("distance", "dist")], Some("geo_"));
+        assert_eq!(config.exposed_name("bearing"), "geo_bearing");
+        assert_eq!(
+            config.downstream_name("geo_bearing"),
+            Some("bearing".to_string())
+        );
+    }
+
+    #[test]
+    fn renamed_tools_are_not_reachable_through_the_prefix() {
+        let config = config(&[("distance", "dist")], Some("geo_"));
+        assert_eq!(config.downstream_name("geo_distance"), None);
+        assert_eq!(config.downstream_name("dist"), Some("distance".to_string()));
+    }
+}
@@ -1,7 +1,7 @@
 //! Variance Middleware Component
 //!
 //! Calculates sample variance of an array of numbers.
-//! Formula: variance = Σ(x - μ)² / n where μ is the mean
+//! Formula: variance = Σ(x - μ)² / (n - ddof) where μ is the mean
 //!
 //! This demonstrates multi-level composition:
 //! - Calls mean() to calculate average
@@ -79,6 +79,9 @@ fn handle_tools_list(
         vec![]
     };
 
+    // Replace any native variance tool downstream, since calls to it stop here
+    tools.retain(|tool| tool.name != "variance");
+
     // Add our variance tool
     tools.push(Tool {
         name: "variance".to_string(),
@@ -89,6 +92,12 @@ fn handle_tools_list(
                     "type": "array",
                     "items": {"type": "number"},
                     "description": "Array of numbers"
+                },
+                "ddof": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "default": 0,
+                    "description": "Delta degrees of freedom: 0 for the population, 1 for the sample estimate"
                 }
             },
             "required": ["numbers"]
@@ -98,7 +107,7 @@ fn handle_tools_list(
             meta: None,
             annotations: None,
             description: Some(
-                "Calculate the variance of an array of numbers: Σ(x - μ)² / n".to_string(),
+                "Calculate the variance of an array of numbers: Σ(x - μ)² / (n - ddof)".to_string(),
             ),
             output_schema: None,
             title: Some("Variance".to_string()),
@@ -130,10 +139,23 @@ fn handle_variance_call(
         )));
     }
 
+    let ddof = match parse_ddof(&request.arguments) {
+        Ok(ddof) => ddof,
+        Err(msg) => return Ok(ServerResponse::ToolsCall(error_result(msg))),
+    };
+
+    if numbers.len() as u64 <= ddof {
+        return Ok(ServerResponse::ToolsCall(error_result(format!(
+            "Error: ddof ({}) must be less than the number of values ({})",
+            ddof,
+            numbers.len()
+        ))));
+    }
+
     // Step 1: Calculate the mean
     let mean = match call_mean_tool(ctx, &numbers, &id, client_stream) {
         Ok(m) => m,
-        Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
+        Err(e) => return e.into_response(id),
     };
 
     // Step 2: Calculate squared differences for each number
@@ -145,22 +167,52 @@ fn handle_variance_call(
         squared_diffs.push(squared);
     }
 
-    // Step 3: Calculate mean of squared differences
-    let variance = squared_diffs.iter().sum::<f64>() / squared_diffs.len() as f64;
+    // Step 3: Divide the sum of squared differences by n - ddof
+    let variance = squared_diffs.iter().sum::<f64>() / (squared_diffs.len() as u64 - ddof) as f64;
 
     Ok(ServerResponse::ToolsCall(success_result(
         variance.to_string(),
     )))
 }
 
+/// Why a downstream tool call produced no usable value
+enum DownstreamError {
+    /// No handler in the downstream chain provides the tool
+    NotFound(String),
+    /// The tool ran but returned an error result or an unusable value
+    ToolError(String),
+    /// The downstream chain failed with any other JSON-RPC error
+    Protocol(ErrorCode),
+}
+
+impl DownstreamError {
+    /// Middleware response for a failed downstream call: a missing tool is a
+    /// `MethodNotFound` error rather than an `is_error` tool result
+    fn into_response(self, id: RequestId) -> Result<ServerResponse, ErrorCode> {
+        match self {
+            DownstreamError::NotFound(message) => Err(ErrorCode::MethodNotFound(Error {
+                id: Some(id),
+                code: -32601,
+                message,
+                data: None,
+            })),
+            DownstreamError::ToolError(message) => {
+                Ok(ServerResponse::ToolsCall(error_result(message)))
+            }
+            DownstreamError::Protocol(e) => Err(e),
+        }
+    }
+}
+
 fn call_mean_tool(
     ctx: &Context,
     numbers: &[f64],
     request_id: &RequestId,
     client_stream: Option<&OutputStream>,
-) -> Result<f64, String> {
+) -> Result<f64, DownstreamError> {
     // Create JSON array for the mean tool
-    let numbers_json = serde_json::to_string(numbers).map_err(|e| format!("JSON error: {}", e))?;
+    let numbers_json = serde_json::to_string(numbers)
+        .map_err(|e| DownstreamError::ToolError(format!("JSON error: {}", e)))?;
 
     let tool_request = CallToolRequest {
         name: "mean".to_string(),
@@ -170,13 +222,17 @@ fn call_mean_tool(
     let downstream_req = ClientRequest::ToolsCall(tool_request);
 
     match downstream::handle_request(ctx, (&downstream_req, request_id), client_stream) {
-        Ok(ServerResponse::ToolsCall(result)) => extract_number_from_result(&result),
-        Err(ErrorCode::MethodNotFound(_)) => Err(
+        Ok(ServerResponse::ToolsCall(result)) => {
+            extract_number_from_result(&result).map_err(DownstreamError::ToolError)
+        }
+        Err(ErrorCode::MethodNotFound(_)) => Err(DownstreamError::NotFound(
             "Tool 'mean' not found. Ensure statistics component comes AFTER this middleware in the pipeline."
                 .to_string(),
-        ),
-        Err(e) => Err(format!("Error calling 'mean': {:?}", e)),
-        _ => Err("Unexpected response type".to_string()),
+        )),
+        Err(e) => Err(DownstreamError::Protocol(e)),
+        _ => Err(DownstreamError::ToolError(
+            "Unexpected response type".to_string(),
+        )),
     }
 }
 
@@ -204,6 +260,23 @@ fn parse_numbers(arguments: &Option<String>) -> Result<Vec<f64>, String> {
     numbers
 }
 
+/// Optional `ddof`, defaulting to 0 (population variance)
+fn parse_ddof(arguments: &Option<String>) -> Result<u64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("ddof") {
+        None | Some(serde_json::Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .ok_or_else(|| "Invalid parameter 'ddof': expected a non-negative integer".to_string()),
+    }
+}
+
 fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     if result.is_error == Some(true) {
         return Err("Tool call returned error".to_string());
@@ -247,3 +320,22 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(VarianceMiddleware with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ddof_defaults_to_population() {
+        assert_eq!(
+            parse_ddof(&Some(r#"{"numbers": [1, 2]}"#.to_string())),
+            Ok(0)
+        );
+        assert_eq!(
+            parse_ddof(&Some(r#"{"numbers": [1, 2], "ddof": 1}"#.to_string())),
+            Ok(1)
+        );
+        assert!(parse_ddof(&Some(r#"{"numbers": [1, 2], "ddof": -1}"#.to_string())).is_err());
+        assert!(parse_ddof(&Some(r#"{"numbers": [1, 2], "ddof": 0.5}"#.to_string())).is_err());
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! Calculate bearing/heading between GPS coordinates.
 //! Returns bearing in degrees, radians, and compass direction.
+//! Also solves constant-bearing intercepts of a moving target and gives the
+//! bearing to a target relative to one's own heading. Offsets a coordinate by a
+//! bearing and distance for dead reckoning, and buffers a route into a corridor
+//! polygon.
 
 mod bindings {
     wit_bindgen::generate!({
@@ -17,6 +21,12 @@ use std::f64::consts::PI;
 
 struct GeospatialBearing;
 
+/// Mean Earth radius, matching the distance tool
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Upper bound on the waypoints accepted by `route_corridor`
+const MAX_CORRIDOR_WAYPOINTS: usize = 10_000;
+
 impl Guest for GeospatialBearing {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
@@ -24,31 +34,239 @@ impl Guest for GeospatialBearing {
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
         Ok(ListToolsResult {
-            tools: vec![Tool {
-                name: "bearing".to_string(),
-                input_schema: r#"{
-                    "type": "object",
-                    "properties": {
-                        "lat1": {"type": "number", "description": "Latitude of start point (-90 to 90)"},
-                        "lon1": {"type": "number", "description": "Longitude of start point (-180 to 180)"},
-                        "lat2": {"type": "number", "description": "Latitude of end point (-90 to 90)"},
-                        "lon2": {"type": "number", "description": "Longitude of end point (-180 to 180)"}
-                    },
-                    "required": ["lat1", "lon1", "lat2", "lon2"]
-                }"#
-                .to_string(),
-                options: Some(ToolOptions {
-                    meta: None,
-                    annotations: None,
-                    description: Some(
-                        "Calculate bearing/heading from one GPS coordinate to another. \
-                         Returns bearing in degrees (0-360), radians, and compass direction (N, NE, E, etc.)."
-                            .to_string(),
-                    ),
-                    output_schema: None,
-                    title: Some("GPS Bearing Calculator".to_string()),
-                }),
-            }],
+            tools: vec![
+                Tool {
+                    name: "bearing".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "description": "Latitude of start point (-90 to 90)"},
+                            "lon1": {"type": "number", "description": "Longitude of start point (-180 to 180)"},
+                            "lat2": {"type": "number", "description": "Latitude of end point (-90 to 90)"},
+                            "lon2": {"type": "number", "description": "Longitude of end point (-180 to 180)"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate bearing/heading from one GPS coordinate to another. \
+                             Returns bearing in degrees (0-360), radians, and compass direction (N, NE, E, etc.)."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("GPS Bearing Calculator".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "bearing_pair".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "description": "Latitude of start point (-90 to 90)"},
+                            "lon1": {"type": "number", "description": "Longitude of start point (-180 to 180)"},
+                            "lat2": {"type": "number", "description": "Latitude of end point (-90 to 90)"},
+                            "lon2": {"type": "number", "description": "Longitude of end point (-180 to 180)"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate the initial bearing from one GPS coordinate to another together with its reciprocal. \
+                             Returns both bearings in degrees (0-360) and their compass directions."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("GPS Bearing Pair".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "heading_alignment".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "bearing1": {"type": "number", "description": "First heading in degrees (alternative to segment1)"},
+                            "bearing2": {"type": "number", "description": "Second heading in degrees (alternative to segment2)"},
+                            "segment1": {
+                                "type": "object",
+                                "properties": {
+                                    "lat1": {"type": "number"},
+                                    "lon1": {"type": "number"},
+                                    "lat2": {"type": "number"},
+                                    "lon2": {"type": "number"}
+                                },
+                                "required": ["lat1", "lon1", "lat2", "lon2"],
+                                "description": "First track as start and end coordinates (alternative to bearing1)"
+                            },
+                            "segment2": {
+                                "type": "object",
+                                "properties": {
+                                    "lat1": {"type": "number"},
+                                    "lon1": {"type": "number"},
+                                    "lat2": {"type": "number"},
+                                    "lon2": {"type": "number"}
+                                },
+                                "required": ["lat1", "lon1", "lat2", "lon2"],
+                                "description": "Second track as start and end coordinates (alternative to bearing2)"
+                            }
+                        }
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Compare two headings, given as bearings or as segment endpoints. \
+                             Returns the cosine similarity of their direction vectors (1 = aligned, -1 = opposite) and the angular difference."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Heading Alignment".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "intercept_heading".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "pursuer": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Pursuer position"
+                            },
+                            "pursuer_speed": {"type": "number", "minimum": 0, "description": "Pursuer speed in km/h"},
+                            "target": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Target position"
+                            },
+                            "target_bearing": {"type": "number", "description": "Target course in degrees"},
+                            "target_speed": {"type": "number", "minimum": 0, "description": "Target speed in km/h"}
+                        },
+                        "required": ["pursuer", "pursuer_speed", "target", "target_bearing", "target_speed"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Solve the intercept triangle for a pursuer chasing a target on a constant course. \
+                             Speeds are in km/h. Returns the heading to steer, time to intercept in hours and the intercept point, \
+                             or reports that no intercept is possible. Uses a local flat-earth approximation, accurate over short ranges."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Intercept Heading".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "relative_bearing".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "minimum": -90, "maximum": 90, "description": "Own latitude"},
+                            "lon1": {"type": "number", "minimum": -180, "maximum": 180, "description": "Own longitude"},
+                            "heading": {"type": "number", "description": "Own heading in degrees"},
+                            "lat2": {"type": "number", "minimum": -90, "maximum": 90, "description": "Target latitude"},
+                            "lon2": {"type": "number", "minimum": -180, "maximum": 180, "description": "Target longitude"}
+                        },
+                        "required": ["lat1", "lon1", "heading", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Bearing to a target relative to own heading, normalized to [-180, 180] (positive = to the right), \
+                             with a clock-position label such as \"2 o'clock\" (12 o'clock = dead ahead)."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Relative Bearing".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "offset_coordinate".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "origin": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Starting position"
+                            },
+                            "bearing_degrees": {"type": "number", "minimum": 0, "maximum": 360, "description": "Direction of travel in degrees clockwise from north"},
+                            "distance_km": {"type": "number", "minimum": 0, "description": "Distance travelled in km"}
+                        },
+                        "required": ["origin", "bearing_degrees", "distance_km"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Move a coordinate by a bearing and distance using the spherical direct formula. \
+                             Returns the new position (longitude normalized to [-180, 180]) and the latitude and longitude deltas."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Offset Coordinate".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "route_corridor".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "waypoints": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                        "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 2,
+                                "description": "Route waypoints in travel order"
+                            },
+                            "width_km": {"type": "number", "exclusiveMinimum": 0, "description": "Total corridor width in km; each side extends width_km / 2 from the route"}
+                        },
+                        "required": ["waypoints", "width_km"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Buffer a route into a corridor polygon. Each segment's endpoints are offset left and right, perpendicular to the segment bearing, \
+                             and the two sides are stitched into one ring (implicitly closed) suitable for point-in-polygon checks. \
+                             Joins between segments are not rounded, so the outline is approximate at sharp turns."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Route Corridor".to_string()),
+                    }),
+                },
+            ],
             next_cursor: None,
             meta: None,
         })
@@ -61,6 +279,12 @@ impl Guest for GeospatialBearing {
     ) -> Option<CallToolResult> {
         match request.name.as_str() {
             "bearing" => Some(execute_bearing(&request.arguments)),
+            "bearing_pair" => Some(execute_bearing_pair(&request.arguments)),
+            "heading_alignment" => Some(execute_heading_alignment(&request.arguments)),
+            "intercept_heading" => Some(execute_intercept_heading(&request.arguments)),
+            "relative_bearing" => Some(execute_relative_bearing(&request.arguments)),
+            "offset_coordinate" => Some(execute_offset_coordinate(&request.arguments)),
+            "route_corridor" => Some(execute_route_corridor(&request.arguments)),
             _ => None,
         }
     }
@@ -92,7 +316,348 @@ fn execute_bearing(arguments: &Option<String>) -> CallToolResult {
     success_result(result.to_string())
 }
 
-fn calculate_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+fn execute_bearing_pair(arguments: &Option<String>) -> CallToolResult {
+    let (lat1, lon1, lat2, lon2) = match parse_bearing_args(arguments) {
+        Ok(coords) => coords,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat1, lon1, lat2, lon2) {
+        return error_result(msg);
+    }
+
+    let initial = calculate_bearing(lat1, lon1, lat2, lon2);
+    let reciprocal = (initial + 180.0) % 360.0;
+
+    let result = serde_json::json!({
+        "initial_bearing_degrees": initial,
+        "initial_compass_direction": degrees_to_compass(initial),
+        "reciprocal_bearing_degrees": reciprocal,
+        "reciprocal_compass_direction": degrees_to_compass(reciprocal)
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_heading_alignment(arguments: &Option<String>) -> CallToolResult {
+    let bearing1 = match parse_heading(arguments, "bearing1", "segment1") {
+        Ok(b) => b,
+        Err(msg) => return error_result(msg),
+    };
+
+    let bearing2 = match parse_heading(arguments, "bearing2", "segment2") {
+        Ok(b) => b,
+        Err(msg) => return error_result(msg),
+    };
+
+    // Unit vectors (east, north) for each heading; their dot product is cos(Δθ)
+    let (e1, n1) = ((bearing1 * PI / 180.0).sin(), (bearing1 * PI / 180.0).cos());
+    let (e2, n2) = ((bearing2 * PI / 180.0).sin(), (bearing2 * PI / 180.0).cos());
+    let cosine_similarity = (e1 * e2 + n1 * n2).clamp(-1.0, 1.0);
+
+    let result = serde_json::json!({
+        "bearing1_degrees": bearing1,
+        "bearing2_degrees": bearing2,
+        "cosine_similarity": cosine_similarity,
+        "angular_difference_degrees": angular_difference(bearing1, bearing2)
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_intercept_heading(arguments: &Option<String>) -> CallToolResult {
+    let (pursuer_lat, pursuer_lon) = match parse_position(arguments, "pursuer") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (target_lat, target_lon) = match parse_position(arguments, "target") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(pursuer_lat, pursuer_lon, target_lat, target_lon) {
+        return error_result(msg);
+    }
+
+    let pursuer_speed = match parse_non_negative(arguments, "pursuer_speed") {
+        Ok(s) => s,
+        Err(msg) => return error_result(msg),
+    };
+
+    let target_speed = match parse_non_negative(arguments, "target_speed") {
+        Ok(s) => s,
+        Err(msg) => return error_result(msg),
+    };
+
+    let target_bearing = match parse_finite(arguments, "target_bearing") {
+        Ok(b) => b.rem_euclid(360.0),
+        Err(msg) => return error_result(msg),
+    };
+
+    let range_km = haversine_distance(pursuer_lat, pursuer_lon, target_lat, target_lon);
+    if range_km == 0.0 {
+        return structured_result(serde_json::json!({
+            "possible": true,
+            "heading_degrees": null,
+            "time_to_intercept_hours": 0.0,
+            "pursuer_distance_km": 0.0,
+            "intercept_point": {"lat": target_lat, "lon": target_lon}
+        }));
+    }
+
+    // Local east/north plane centred on the pursuer
+    let line_of_sight =
+        calculate_bearing(pursuer_lat, pursuer_lon, target_lat, target_lon) * PI / 180.0;
+    let course = target_bearing * PI / 180.0;
+    let (rx, ry) = (
+        range_km * line_of_sight.sin(),
+        range_km * line_of_sight.cos(),
+    );
+    let (vx, vy) = (target_speed * course.sin(), target_speed * course.cos());
+
+    // |r + v·t| = pursuer_speed·t  =>  (|v|² - s²)t² + 2(r·v)t + |r|² = 0
+    let time_hours = match earliest_positive_root(
+        target_speed * target_speed - pursuer_speed * pursuer_speed,
+        2.0 * (rx * vx + ry * vy),
+        range_km * range_km,
+    ) {
+        Some(t) => t,
+        None => {
+            return structured_result(serde_json::json!({
+                "possible": false,
+                "reason": "The pursuer is too slow to close the distance to the target on its current course"
+            }));
+        }
+    };
+
+    let (ix, iy) = (rx + vx * time_hours, ry + vy * time_hours);
+    let heading = (ix.atan2(iy) * 180.0 / PI + 360.0) % 360.0;
+    let pursuer_distance_km = pursuer_speed * time_hours;
+    let (intercept_lat, intercept_lon) =
+        destination_point(pursuer_lat, pursuer_lon, heading, pursuer_distance_km);
+
+    structured_result(serde_json::json!({
+        "possible": true,
+        "heading_degrees": heading,
+        "compass_direction": degrees_to_compass(heading),
+        "time_to_intercept_hours": time_hours,
+        "pursuer_distance_km": pursuer_distance_km,
+        "intercept_point": {"lat": intercept_lat, "lon": intercept_lon}
+    }))
+}
+
+/// Smallest strictly positive root of `a·t² + b·t + c = 0`, if any
+pub(crate) fn earliest_positive_root(a: f64, b: f64, c: f64) -> Option<f64> {
+    if a.abs() < 1e-12 {
+        // Equal speeds: linear in t
+        if b.abs() < 1e-12 {
+            return None;
+        }
+        let t = -c / b;
+        return (t > 0.0).then_some(t);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let sqrt_d = discriminant.sqrt();
+    let roots = [(-b - sqrt_d) / (2.0 * a), (-b + sqrt_d) / (2.0 * a)];
+    roots
+        .into_iter()
+        .filter(|t| *t > 0.0)
+        .min_by(|x, y| x.total_cmp(y))
+}
+
+fn execute_relative_bearing(arguments: &Option<String>) -> CallToolResult {
+    let (lat1, lon1, lat2, lon2) = match parse_bearing_args(arguments) {
+        Ok(coords) => coords,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat1, lon1, lat2, lon2) {
+        return error_result(msg);
+    }
+
+    if lat1 == lat2 && lon1 == lon2 {
+        return error_result(
+            "Own and target positions are identical, bearing is undefined".to_string(),
+        );
+    }
+
+    let heading = match parse_finite(arguments, "heading") {
+        Ok(h) => h.rem_euclid(360.0),
+        Err(msg) => return error_result(msg),
+    };
+
+    let absolute = calculate_bearing(lat1, lon1, lat2, lon2);
+    let relative = normalize_relative(absolute - heading);
+    let clock = clock_position(relative);
+
+    let result = serde_json::json!({
+        "absolute_bearing_degrees": absolute,
+        "heading_degrees": heading,
+        "relative_bearing_degrees": relative,
+        "clock_position": clock,
+        "clock_label": format!("{} o'clock", clock)
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_offset_coordinate(arguments: &Option<String>) -> CallToolResult {
+    let (lat, lon) = match parse_position(arguments, "origin") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat, lon, lat, lon) {
+        return error_result(msg);
+    }
+
+    let bearing = match parse_finite(arguments, "bearing_degrees") {
+        Ok(b) => b,
+        Err(msg) => return error_result(msg),
+    };
+
+    if !(0.0..=360.0).contains(&bearing) {
+        return error_result("'bearing_degrees' must be between 0 and 360".to_string());
+    }
+
+    let distance_km = match parse_non_negative(arguments, "distance_km") {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (new_lat, new_lon) = destination_point(lat, lon, bearing, distance_km);
+
+    structured_result(serde_json::json!({
+        "lat": new_lat,
+        "lon": new_lon,
+        "d_lat": new_lat - lat,
+        // Shortest signed longitude change, so crossing the antimeridian stays small
+        "d_lon": normalize_relative(new_lon - lon)
+    }))
+}
+
+fn execute_route_corridor(arguments: &Option<String>) -> CallToolResult {
+    let waypoints = match parse_waypoints(arguments) {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    if waypoints.len() < 2 {
+        return error_result("Route must have at least 2 waypoints".to_string());
+    }
+
+    if waypoints.len() > MAX_CORRIDOR_WAYPOINTS {
+        return error_result(format!(
+            "Too many waypoints: {}. At most {} are supported",
+            waypoints.len(),
+            MAX_CORRIDOR_WAYPOINTS
+        ));
+    }
+
+    for (i, &(lat, lon)) in waypoints.iter().enumerate() {
+        if let Err(msg) = validate_coordinates(lat, lon, lat, lon) {
+            return error_result(format!("Waypoint {}: {}", i, msg));
+        }
+    }
+
+    let width_km = match parse_finite(arguments, "width_km") {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    if width_km <= 0.0 {
+        return error_result("'width_km' must be greater than 0".to_string());
+    }
+
+    let Some((polygon, segment_count)) = corridor_polygon(&waypoints, width_km / 2.0) else {
+        return error_result("Route has no length: all waypoints are the same point".to_string());
+    };
+
+    let vertices: Vec<serde_json::Value> = polygon
+        .iter()
+        .map(|&(lat, lon)| serde_json::json!({"lat": lat, "lon": lon}))
+        .collect();
+
+    structured_result(serde_json::json!({
+        "width_km": width_km,
+        "segment_count": segment_count,
+        "vertex_count": vertices.len(),
+        "polygon": vertices
+    }))
+}
+
+/// Corridor ring around the route: the left-hand offsets of every segment's start
+/// and end in travel order, then the right-hand offsets in reverse. Each end is
+/// offset perpendicular to the segment's bearing at that end. Zero-length
+/// segments have no bearing and are skipped; returns `None` if all are.
+pub(crate) fn corridor_polygon(
+    waypoints: &[(f64, f64)],
+    half_width_km: f64,
+) -> Option<(Vec<(f64, f64)>, usize)> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for pair in waypoints.windows(2) {
+        let ((lat1, lon1), (lat2, lon2)) = (pair[0], pair[1]);
+        if haversine_distance(lat1, lon1, lat2, lon2) == 0.0 {
+            continue;
+        }
+        let initial = calculate_bearing(lat1, lon1, lat2, lon2);
+        let final_bearing = (calculate_bearing(lat2, lon2, lat1, lon1) + 180.0) % 360.0;
+
+        left.push(destination_point(lat1, lon1, initial - 90.0, half_width_km));
+        left.push(destination_point(
+            lat2,
+            lon2,
+            final_bearing - 90.0,
+            half_width_km,
+        ));
+        right.push(destination_point(lat1, lon1, initial + 90.0, half_width_km));
+        right.push(destination_point(
+            lat2,
+            lon2,
+            final_bearing + 90.0,
+            half_width_km,
+        ));
+    }
+
+    if left.is_empty() {
+        return None;
+    }
+
+    let segment_count = left.len() / 2;
+    left.extend(right.into_iter().rev());
+    Some((left, segment_count))
+}
+
+/// Wrap an angle difference into [-180, 180]
+pub(crate) fn normalize_relative(degrees: f64) -> f64 {
+    let wrapped = (degrees + 180.0).rem_euclid(360.0) - 180.0;
+    // rem_euclid maps +180 to -180; keep dead astern on the right-hand side
+    if wrapped == -180.0 { 180.0 } else { wrapped }
+}
+
+/// Nearest clock hour for a relative bearing, 12 being dead ahead
+pub(crate) fn clock_position(relative_degrees: f64) -> u32 {
+    match (relative_degrees / 30.0).round().rem_euclid(12.0) as u32 {
+        0 => 12,
+        hour => hour,
+    }
+}
+
+/// Smallest angle between two bearings, in [0, 180]
+pub(crate) fn angular_difference(bearing1: f64, bearing2: f64) -> f64 {
+    let diff = (bearing2 - bearing1).rem_euclid(360.0);
+    if diff > 180.0 { 360.0 - diff } else { diff }
+}
+
+pub(crate) fn calculate_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     let lat1_rad = lat1 * PI / 180.0;
     let lat2_rad = lat2 * PI / 180.0;
     let delta_lon = (lon2 - lon1) * PI / 180.0;
@@ -105,7 +670,43 @@ fn calculate_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     (bearing_rad * 180.0 / PI + 360.0) % 360.0
 }
 
-fn degrees_to_compass(degrees: f64) -> String {
+pub(crate) fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let lat1_rad = lat1 * PI / 180.0;
+    let lat2_rad = lat2 * PI / 180.0;
+    let delta_lat = (lat2 - lat1) * PI / 180.0;
+    let delta_lon = (lon2 - lon1) * PI / 180.0;
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    EARTH_RADIUS_KM * 2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Point reached by travelling `distance_km` along a great circle from
+/// (`lat`, `lon`) with initial bearing `bearing_deg`; longitude in [-180, 180)
+pub(crate) fn destination_point(
+    lat: f64,
+    lon: f64,
+    bearing_deg: f64,
+    distance_km: f64,
+) -> (f64, f64) {
+    let lat_rad = lat * PI / 180.0;
+    let lon_rad = lon * PI / 180.0;
+    let bearing_rad = bearing_deg * PI / 180.0;
+    let angular = distance_km / EARTH_RADIUS_KM;
+
+    let lat2 = (lat_rad.sin() * angular.cos() + lat_rad.cos() * angular.sin() * bearing_rad.cos())
+        .clamp(-1.0, 1.0)
+        .asin();
+    let lon2 = lon_rad
+        + (bearing_rad.sin() * angular.sin() * lat_rad.cos())
+            .atan2(angular.cos() - lat_rad.sin() * lat2.sin());
+
+    let lon2_deg = (lon2 * 180.0 / PI + 540.0).rem_euclid(360.0) - 180.0;
+    (lat2 * 180.0 / PI, lon2_deg)
+}
+
+pub(crate) fn degrees_to_compass(degrees: f64) -> String {
     let directions = [
         "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
         "NW", "NNW",
@@ -115,7 +716,12 @@ fn degrees_to_compass(degrees: f64) -> String {
     directions[index].to_string()
 }
 
-fn validate_coordinates(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<(), String> {
+pub(crate) fn validate_coordinates(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+) -> Result<(), String> {
     // Check for NaN or Infinite
     if lat1.is_nan() || lat1.is_infinite()
         || lon1.is_nan() || lon1.is_infinite()
@@ -126,12 +732,12 @@ fn validate_coordinates(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<()
     }
 
     // Validate latitude range
-    if lat1 < -90.0 || lat1 > 90.0 || lat2 < -90.0 || lat2 > 90.0 {
+    if !(-90.0..=90.0).contains(&lat1) || !(-90.0..=90.0).contains(&lat2) {
         return Err("Latitude must be between -90 and 90 degrees".to_string());
     }
 
     // Validate longitude range
-    if lon1 < -180.0 || lon1 > 180.0 || lon2 < -180.0 || lon2 > 180.0 {
+    if !(-180.0..=180.0).contains(&lon1) || !(-180.0..=180.0).contains(&lon2) {
         return Err("Longitude must be between -180 and 180 degrees".to_string());
     }
 
@@ -169,6 +775,157 @@ fn parse_bearing_args(arguments: &Option<String>) -> Result<(f64, f64, f64, f64)
     Ok((lat1, lon1, lat2, lon2))
 }
 
+/// Parse a `{lat, lon}` object under `name`
+fn parse_position(arguments: &Option<String>, name: &str) -> Result<(f64, f64), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let position = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    let lat = position
+        .get("lat")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lat'", name))?;
+
+    let lon = position
+        .get("lon")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lon'", name))?;
+
+    Ok((lat, lon))
+}
+
+/// Parse the `waypoints` array of `{lat, lon}` objects
+fn parse_waypoints(arguments: &Option<String>) -> Result<Vec<(f64, f64)>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let waypoints_arr = json
+        .get("waypoints")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid parameter 'waypoints'".to_string())?;
+
+    let mut waypoints = Vec::new();
+    for (i, wp) in waypoints_arr.iter().enumerate() {
+        let lat = wp
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'waypoints[{}].lat'", i))?;
+
+        let lon = wp
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'waypoints[{}].lon'", i))?;
+
+        waypoints.push((lat, lon));
+    }
+
+    Ok(waypoints)
+}
+
+fn parse_finite(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    json.get(name)
+        .and_then(|v| v.as_f64())
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+}
+
+fn parse_non_negative(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let speed = parse_finite(arguments, name)?;
+    if speed < 0.0 {
+        return Err(format!("'{}' must not be negative", name));
+    }
+    Ok(speed)
+}
+
+/// Heading in [0, 360) from either a bearing in degrees or a segment's endpoints
+fn parse_heading(
+    arguments: &Option<String>,
+    bearing_key: &str,
+    segment_key: &str,
+) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match (json.get(bearing_key), json.get(segment_key)) {
+        (Some(_), Some(_)) => Err(format!(
+            "Provide either '{}' or '{}', not both",
+            bearing_key, segment_key
+        )),
+        (Some(bearing), None) => {
+            let bearing = bearing
+                .as_f64()
+                .filter(|b| b.is_finite())
+                .ok_or_else(|| format!("Missing or invalid parameter '{}'", bearing_key))?;
+            Ok(bearing.rem_euclid(360.0))
+        }
+        (None, Some(segment)) => {
+            let coord = |name: &str| {
+                segment
+                    .get(name)
+                    .and_then(|v| v.as_f64())
+                    .ok_or_else(|| format!("Missing or invalid '{}.{}'", segment_key, name))
+            };
+            let (lat1, lon1, lat2, lon2) = (
+                coord("lat1")?,
+                coord("lon1")?,
+                coord("lat2")?,
+                coord("lon2")?,
+            );
+
+            validate_coordinates(lat1, lon1, lat2, lon2)
+                .map_err(|msg| format!("{}: {}", segment_key, msg))?;
+            if lat1 == lat2 && lon1 == lon2 {
+                return Err(format!(
+                    "{}: start and end points are identical, heading is undefined",
+                    segment_key
+                ));
+            }
+
+            Ok(calculate_bearing(lat1, lon1, lat2, lon2))
+        }
+        (None, None) => Err(format!(
+            "Missing heading: provide '{}' or '{}'",
+            bearing_key, segment_key
+        )),
+    }
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
 fn success_result(result: String) -> CallToolResult {
     CallToolResult {
         content: vec![ContentBlock::Text(TextContent {
@@ -194,3 +951,154 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(GeospatialBearing with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn structured(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(
+            result
+                .structured_content
+                .as_deref()
+                .expect("structured content"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn cardinal_bearings() {
+        assert!((calculate_bearing(0.0, 0.0, 1.0, 0.0) - 0.0).abs() < 1e-9);
+        assert!((calculate_bearing(0.0, 0.0, 0.0, 1.0) - 90.0).abs() < 1e-9);
+        assert!((calculate_bearing(0.0, 0.0, -1.0, 0.0) - 180.0).abs() < 1e-9);
+        assert!((calculate_bearing(0.0, 0.0, 0.0, -1.0) - 270.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compass_sectors_wrap_at_north() {
+        assert_eq!(degrees_to_compass(0.0), "N");
+        assert_eq!(degrees_to_compass(359.0), "N");
+        assert_eq!(degrees_to_compass(45.0), "NE");
+        assert_eq!(degrees_to_compass(191.0), "S");
+    }
+
+    #[test]
+    fn angular_difference_takes_short_way_round() {
+        assert_eq!(angular_difference(350.0, 10.0), 20.0);
+        assert_eq!(angular_difference(0.0, 180.0), 180.0);
+    }
+
+    #[test]
+    fn relative_bearing_normalization_and_clock() {
+        assert_eq!(normalize_relative(270.0), -90.0);
+        assert_eq!(normalize_relative(-180.0), 180.0);
+        assert_eq!(clock_position(60.0), 2);
+        assert_eq!(clock_position(-60.0), 10);
+        assert_eq!(clock_position(5.0), 12);
+    }
+
+    #[test]
+    fn destination_round_trips_with_haversine() {
+        let (lat, lon) = destination_point(10.0, 20.0, 45.0, 500.0);
+        assert!((haversine_distance(10.0, 20.0, lat, lon) - 500.0).abs() < 1e-6);
+        assert!((calculate_bearing(10.0, 20.0, lat, lon) - 45.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn earliest_positive_root_skips_negative_roots() {
+        // (t + 1)(t - 2)
+        assert_eq!(earliest_positive_root(1.0, -1.0, -2.0), Some(2.0));
+        // t² + 1 has no real roots
+        assert_eq!(earliest_positive_root(1.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn stationary_target_is_intercepted_head_on() {
+        let result = execute_intercept_heading(&args(
+            r#"{"pursuer": {"lat": 0, "lon": 0}, "pursuer_speed": 100,
+                "target": {"lat": 0, "lon": 1}, "target_bearing": 0, "target_speed": 0}"#,
+        ));
+        let solution = structured(&result);
+        assert_eq!(solution["possible"], true);
+        assert!((solution["heading_degrees"].as_f64().unwrap() - 90.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn faster_target_moving_away_cannot_be_intercepted() {
+        let result = execute_intercept_heading(&args(
+            r#"{"pursuer": {"lat": 0, "lon": 0}, "pursuer_speed": 10,
+                "target": {"lat": 0, "lon": 1}, "target_bearing": 90, "target_speed": 20}"#,
+        ));
+        assert_eq!(structured(&result)["possible"], false);
+    }
+
+    #[test]
+    fn validate_coordinates_rejects_bad_longitude() {
+        assert!(validate_coordinates(0.0, 181.0, 0.0, 0.0).is_err());
+        assert!(validate_coordinates(0.0, 0.0, 0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn offset_coordinate_moves_north_and_wraps_east() {
+        // One degree of latitude along a meridian
+        let km_per_degree = EARTH_RADIUS_KM * PI / 180.0;
+        let result = execute_offset_coordinate(&args(&format!(
+            r#"{{"origin": {{"lat": 10, "lon": 20}}, "bearing_degrees": 0, "distance_km": {}}}"#,
+            km_per_degree
+        )));
+        let value = structured(&result);
+        assert!((value["d_lat"].as_f64().unwrap() - 1.0).abs() < 1e-9);
+        assert!(value["d_lon"].as_f64().unwrap().abs() < 1e-9);
+
+        let result = execute_offset_coordinate(&args(&format!(
+            r#"{{"origin": {{"lat": 0, "lon": 179.5}}, "bearing_degrees": 90, "distance_km": {}}}"#,
+            km_per_degree
+        )));
+        let value = structured(&result);
+        assert!((value["lon"].as_f64().unwrap() + 179.5).abs() < 1e-9);
+        assert!((value["d_lon"].as_f64().unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn offset_coordinate_validates_inputs() {
+        let negative = execute_offset_coordinate(&args(
+            r#"{"origin": {"lat": 0, "lon": 0}, "bearing_degrees": 90, "distance_km": -1}"#,
+        ));
+        assert_eq!(negative.is_error, Some(true));
+
+        let bearing = execute_offset_coordinate(&args(
+            r#"{"origin": {"lat": 0, "lon": 0}, "bearing_degrees": 400, "distance_km": 1}"#,
+        ));
+        assert_eq!(bearing.is_error, Some(true));
+    }
+
+    #[test]
+    fn corridor_around_eastward_segment() {
+        let (polygon, segments) = corridor_polygon(&[(0.0, 0.0), (0.0, 1.0)], 1.0).unwrap();
+        assert_eq!(segments, 1);
+        assert_eq!(polygon.len(), 4);
+
+        // Left of an eastward route is north, right is south, each 1 km from the line
+        let offset_deg = 1.0 / EARTH_RADIUS_KM * 180.0 / PI;
+        let expected = [
+            (offset_deg, 0.0),
+            (offset_deg, 1.0),
+            (-offset_deg, 1.0),
+            (-offset_deg, 0.0),
+        ];
+        for (&(lat, lon), (exp_lat, exp_lon)) in polygon.iter().zip(expected) {
+            assert!((lat - exp_lat).abs() < 1e-9 && (lon - exp_lon).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn corridor_skips_zero_length_segments() {
+        let route = [(0.0, 0.0), (0.0, 0.0), (0.0, 1.0)];
+        assert_eq!(corridor_polygon(&route, 1.0).unwrap().1, 1);
+        assert!(corridor_polygon(&[(1.0, 1.0), (1.0, 1.0)], 1.0).is_none());
+    }
+}
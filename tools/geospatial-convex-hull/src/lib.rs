@@ -0,0 +1,371 @@
+//! Geospatial Convex Hull Tool
+//!
+//! Compute the convex hull of a set of GPS coordinates using Andrew's monotone chain.
+//! Can also return the spherical area enclosed by the hull in a single call.
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "geospatial-convex-hull",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+use std::f64::consts::PI;
+
+struct GeospatialConvexHull;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+impl Guest for GeospatialConvexHull {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        _request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        Ok(ListToolsResult {
+            tools: vec![
+                Tool {
+                    name: "convex_hull".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "GPS points to wrap (at least 3 points)"
+                            }
+                        },
+                        "required": ["points"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Compute the convex hull of a set of GPS points. \
+                             Returns the hull vertices in counter-clockwise order."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Convex Hull".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "hull_area".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "GPS points to wrap (at least 3 points)"
+                            }
+                        },
+                        "required": ["points"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Compute the convex hull of a set of GPS points and the spherical area it encloses. \
+                             Returns the hull vertices and the area in km² in a single call."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Convex Hull Area".to_string()),
+                    }),
+                },
+            ],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "convex_hull" => Some(execute_convex_hull(&request.arguments)),
+            "hull_area" => Some(execute_hull_area(&request.arguments)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Point {
+    lat: f64,
+    lon: f64,
+}
+
+fn execute_convex_hull(arguments: &Option<String>) -> CallToolResult {
+    let hull = match parse_and_build_hull(arguments) {
+        Ok(hull) => hull,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = serde_json::json!({
+        "hull": points_to_json(&hull),
+        "vertex_count": hull.len(),
+        "algorithm_used": "monotone_chain"
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_hull_area(arguments: &Option<String>) -> CallToolResult {
+    let hull = match parse_and_build_hull(arguments) {
+        Ok(hull) => hull,
+        Err(msg) => return error_result(msg),
+    };
+
+    let area_km2 = spherical_polygon_area(&hull);
+
+    let result = serde_json::json!({
+        "hull": points_to_json(&hull),
+        "vertex_count": hull.len(),
+        "area_km2": area_km2,
+        "area_mi2": area_km2 * 0.386102,
+        "algorithm_used": "monotone_chain"
+    });
+
+    success_result(result.to_string())
+}
+
+/// Parse the points, validate them and return their convex hull
+fn parse_and_build_hull(arguments: &Option<String>) -> Result<Vec<Point>, String> {
+    let points = parse_points(arguments)?;
+
+    if points.len() < 3 {
+        return Err("At least 3 points are required to build a convex hull".to_string());
+    }
+
+    for (i, p) in points.iter().enumerate() {
+        validate_point(p).map_err(|msg| format!("Point {}: {}", i, msg))?;
+    }
+
+    let hull = monotone_chain(&points);
+    if hull.len() < 3 {
+        return Err(
+            "Convex hull is degenerate: all points are coincident or collinear".to_string(),
+        );
+    }
+
+    Ok(hull)
+}
+
+/// Andrew's monotone chain, treating longitude as x and latitude as y.
+/// Returns the hull in counter-clockwise order without repeating the first vertex.
+/// Collinear points on the hull boundary are dropped.
+pub(crate) fn monotone_chain(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.lon.total_cmp(&b.lon).then(a.lat.total_cmp(&b.lat)));
+    sorted.dedup_by(|a, b| a.lon == b.lon && a.lat == b.lat);
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut lower: Vec<Point> = Vec::new();
+    for p in &sorted {
+        while lower.len() >= 2 && cross(&lower[lower.len() - 2], &lower[lower.len() - 1], p) <= 0.0
+        {
+            lower.pop();
+        }
+        lower.push(*p);
+    }
+
+    let mut upper: Vec<Point> = Vec::new();
+    for p in sorted.iter().rev() {
+        while upper.len() >= 2 && cross(&upper[upper.len() - 2], &upper[upper.len() - 1], p) <= 0.0
+        {
+            upper.pop();
+        }
+        upper.push(*p);
+    }
+
+    // The last point of each chain is the first point of the other
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+/// Z component of (a - o) × (b - o); positive when o → a → b turns counter-clockwise
+pub(crate) fn cross(o: &Point, a: &Point, b: &Point) -> f64 {
+    (a.lon - o.lon) * (b.lat - o.lat) - (a.lat - o.lat) * (b.lon - o.lon)
+}
+
+/// Area of a polygon on a spherical Earth in km², using the
+/// line-integral formula Σ (λ2 - λ1)(2 + sin φ1 + sin φ2) · R² / 2
+pub(crate) fn spherical_polygon_area(polygon: &[Point]) -> f64 {
+    let n = polygon.len();
+    let mut total = 0.0;
+
+    for i in 0..n {
+        let p1 = &polygon[i];
+        let p2 = &polygon[(i + 1) % n];
+
+        let delta_lon = (p2.lon - p1.lon) * PI / 180.0;
+        let lat1_rad = p1.lat * PI / 180.0;
+        let lat2_rad = p2.lat * PI / 180.0;
+
+        total += delta_lon * (2.0 + lat1_rad.sin() + lat2_rad.sin());
+    }
+
+    (total * EARTH_RADIUS_KM * EARTH_RADIUS_KM / 2.0).abs()
+}
+
+pub(crate) fn points_to_json(points: &[Point]) -> Vec<serde_json::Value> {
+    points
+        .iter()
+        .map(|p| serde_json::json!({"lat": p.lat, "lon": p.lon}))
+        .collect()
+}
+
+pub(crate) fn validate_point(point: &Point) -> Result<(), String> {
+    if point.lat.is_nan() || point.lat.is_infinite() {
+        return Err("Latitude cannot be NaN or infinite".to_string());
+    }
+    if point.lon.is_nan() || point.lon.is_infinite() {
+        return Err("Longitude cannot be NaN or infinite".to_string());
+    }
+    if !(-90.0..=90.0).contains(&point.lat) {
+        return Err(format!(
+            "Invalid latitude: {}. Must be between -90 and 90",
+            point.lat
+        ));
+    }
+    if !(-180.0..=180.0).contains(&point.lon) {
+        return Err(format!(
+            "Invalid longitude: {}. Must be between -180 and 180",
+            point.lon
+        ));
+    }
+    Ok(())
+}
+
+fn parse_points(arguments: &Option<String>) -> Result<Vec<Point>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let points_arr = json
+        .get("points")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid 'points' parameter".to_string())?;
+
+    let mut points = Vec::new();
+    for (i, p) in points_arr.iter().enumerate() {
+        let lat = p
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'points[{}].lat'", i))?;
+
+        let lon = p
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'points[{}].lon'", i))?;
+
+        points.push(Point { lat, lon });
+    }
+
+    Ok(points)
+}
+
+fn success_result(result: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(result),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: None,
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(GeospatialConvexHull with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(lat: f64, lon: f64) -> Point {
+        Point { lat, lon }
+    }
+
+    #[test]
+    fn hull_drops_interior_points() {
+        let hull = monotone_chain(&[
+            p(0.0, 0.0),
+            p(0.0, 2.0),
+            p(2.0, 2.0),
+            p(2.0, 0.0),
+            p(1.0, 1.0),
+        ]);
+        assert_eq!(hull.len(), 4);
+        assert!(!hull.iter().any(|q| q.lat == 1.0 && q.lon == 1.0));
+    }
+
+    #[test]
+    fn hull_is_counter_clockwise() {
+        let hull = monotone_chain(&[p(0.0, 0.0), p(0.0, 1.0), p(1.0, 0.0)]);
+        assert!(cross(&hull[0], &hull[1], &hull[2]) > 0.0);
+    }
+
+    #[test]
+    fn collinear_points_collapse_to_extremes() {
+        let hull = monotone_chain(&[p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0)]);
+        assert_eq!(hull.len(), 2);
+    }
+
+    #[test]
+    fn duplicates_are_removed() {
+        assert_eq!(monotone_chain(&[p(1.0, 1.0), p(1.0, 1.0)]).len(), 1);
+    }
+
+    #[test]
+    fn validate_point_rejects_out_of_range() {
+        assert!(validate_point(&p(0.0, 200.0)).is_err());
+        assert!(validate_point(&p(f64::NAN, 0.0)).is_err());
+        assert!(validate_point(&p(0.0, 0.0)).is_ok());
+    }
+}
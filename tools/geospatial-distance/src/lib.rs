@@ -2,6 +2,12 @@
 //!
 //! Calculate distance between GPS coordinates using Haversine formula.
 //! Returns results in kilometers, miles, and nautical miles.
+//! Also computes per-segment grades for routes with elevation data and the
+//! central angle between two points, and interpolates great-circle waypoints
+//! between two points at a fixed spacing. Radius checks test whether a point
+//! lies inside a circular zone. Triangle areas use the spherical excess.
+//! Rhumb lines (constant bearing) are compared against the great-circle path.
+//! Timestamped tracks can be scanned for stops where the position lingers.
 
 mod bindings {
     wit_bindgen::generate!({
@@ -17,6 +23,26 @@ use std::f64::consts::PI;
 
 struct GeospatialDistance;
 
+/// Mean Earth radius in km
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Segments shorter than this (in meters) are treated as zero-length
+const ZERO_LENGTH_EPSILON_M: f64 = 1e-6;
+
+/// Defaults and limits for the iterative geometric median
+const DEFAULT_MEDIAN_TOLERANCE_KM: f64 = 1e-6;
+const DEFAULT_MAX_ITERATIONS: u64 = 100;
+const MAX_ITERATIONS_CAP: u64 = 10_000;
+
+/// Default grade magnitude (%) separating flat terrain from climbs and descents
+const DEFAULT_FLAT_THRESHOLD_PERCENT: f64 = 2.0;
+
+/// Upper bound on the waypoints emitted by `great_circle_waypoints`
+const MAX_GREAT_CIRCLE_WAYPOINTS: usize = 10_000;
+
+/// Upper bound on the points checked by one `within_radius_batch` call
+const MAX_BATCH_POINTS: usize = 10_000;
+
 impl Guest for GeospatialDistance {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
@@ -24,31 +50,465 @@ impl Guest for GeospatialDistance {
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
         Ok(ListToolsResult {
-            tools: vec![Tool {
-                name: "distance".to_string(),
-                input_schema: r#"{
-                    "type": "object",
-                    "properties": {
-                        "lat1": {"type": "number", "description": "Latitude of first point (-90 to 90)"},
-                        "lon1": {"type": "number", "description": "Longitude of first point (-180 to 180)"},
-                        "lat2": {"type": "number", "description": "Latitude of second point (-90 to 90)"},
-                        "lon2": {"type": "number", "description": "Longitude of second point (-180 to 180)"}
-                    },
-                    "required": ["lat1", "lon1", "lat2", "lon2"]
-                }"#
-                .to_string(),
-                options: Some(ToolOptions {
-                    meta: None,
-                    annotations: None,
-                    description: Some(
-                        "Calculate distance between two GPS coordinates using Haversine formula. \
-                         Returns distance in kilometers, miles, and nautical miles with 99.8% accuracy."
-                            .to_string(),
-                    ),
-                    output_schema: None,
-                    title: Some("GPS Distance Calculator".to_string()),
-                }),
-            }],
+            tools: vec![
+                Tool {
+                    name: "distance".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "description": "Latitude of first point (-90 to 90)"},
+                            "lon1": {"type": "number", "description": "Longitude of first point (-180 to 180)"},
+                            "lat2": {"type": "number", "description": "Latitude of second point (-90 to 90)"},
+                            "lon2": {"type": "number", "description": "Longitude of second point (-180 to 180)"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate distance between two GPS coordinates using Haversine formula. \
+                             Returns distance in kilometers, miles, and nautical miles with 99.8% accuracy."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("GPS Distance Calculator".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "route_grades".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "waypoints": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"},
+                                        "elevation_m": {"type": "number", "description": "Elevation in meters"}
+                                    },
+                                    "required": ["lat", "lon", "elevation_m"]
+                                },
+                                "minItems": 2,
+                                "description": "Route waypoints with elevations (at least 2 points)"
+                            }
+                        },
+                        "required": ["waypoints"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate the grade of each leg of a route with elevations. \
+                             Uses Haversine horizontal distance and returns per-segment grade (%) plus max and average grade."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Route Grades".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "angular_distance".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "description": "Latitude of first point (-90 to 90)"},
+                            "lon1": {"type": "number", "description": "Longitude of first point (-180 to 180)"},
+                            "lat2": {"type": "number", "description": "Latitude of second point (-90 to 90)"},
+                            "lon2": {"type": "number", "description": "Longitude of second point (-180 to 180)"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate the great-circle central angle between two GPS coordinates. \
+                             Returns the angle in degrees and radians, independent of the Earth's radius."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Angular Distance".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "terrain_breakdown".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "waypoints": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"},
+                                        "elevation_m": {"type": "number", "description": "Elevation in meters"}
+                                    },
+                                    "required": ["lat", "lon", "elevation_m"]
+                                },
+                                "minItems": 2,
+                                "description": "Route waypoints with elevations (at least 2 points)"
+                            },
+                            "threshold_percent": {
+                                "type": "number",
+                                "minimum": 0,
+                                "description": "Grade magnitude (%) above which a segment counts as uphill or downhill (default: 2)"
+                            }
+                        },
+                        "required": ["waypoints"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Break a route with elevations down by terrain. \
+                             Returns the distance and fraction of the route that is uphill, downhill, and flat."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Terrain Breakdown".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "geometric_median".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 1,
+                                "description": "GPS points to summarize"
+                            },
+                            "tolerance_km": {
+                                "type": "number",
+                                "exclusiveMinimum": 0,
+                                "description": "Stop when an iteration moves the estimate less than this (default: 1e-6 km)"
+                            },
+                            "max_iterations": {
+                                "type": "integer",
+                                "minimum": 1,
+                                "maximum": 10000,
+                                "description": "Iteration cap (default: 100)"
+                            }
+                        },
+                        "required": ["points"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Find the geometric median of GPS points: the location minimizing the total great-circle distance to all points. \
+                             Uses Weiszfeld's algorithm and is robust to outliers, unlike the centroid."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Geometric Median".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "great_circle_waypoints".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "minimum": -90, "maximum": 90, "description": "Start latitude"},
+                            "lon1": {"type": "number", "minimum": -180, "maximum": 180, "description": "Start longitude"},
+                            "lat2": {"type": "number", "minimum": -90, "maximum": 90, "description": "End latitude"},
+                            "lon2": {"type": "number", "minimum": -180, "maximum": 180, "description": "End longitude"},
+                            "spacing_km": {"type": "number", "exclusiveMinimum": 0, "description": "Distance between consecutive waypoints in km"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2", "spacing_km"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Generate waypoints along the great circle between two points every spacing_km, using spherical interpolation. \
+                             Returns each waypoint with its cumulative distance from the start; the end point is always included."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Great Circle Waypoints".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "within_radius".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "center": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Center of the circular zone"
+                            },
+                            "point": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Point to test"
+                            },
+                            "radius_km": {"type": "number", "minimum": 0, "description": "Zone radius in km"}
+                        },
+                        "required": ["center", "point", "radius_km"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Check whether a point lies within radius_km of a center using the Haversine distance. \
+                             Points exactly on the boundary count as within. A simple alternative to polygon geofencing for circular zones."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Within Radius".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "within_radius_batch".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "center": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Center of the circular zone"
+                            },
+                            "radius_km": {"type": "number", "minimum": 0, "description": "Zone radius in km"},
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "maxItems": 10000,
+                                "description": "Points to test"
+                            }
+                        },
+                        "required": ["center", "radius_km", "points"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Check many points against one circular zone. Returns each point's distance and whether it is within radius_km, \
+                             plus the count inside. Invalid points get a per-point error instead of failing the whole batch."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Within Radius (Batch)".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "k_nearest".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "query": {
+                                "type": "object",
+                                "properties": {
+                                    "lat": {"type": "number", "minimum": -90, "maximum": 90},
+                                    "lon": {"type": "number", "minimum": -180, "maximum": 180}
+                                },
+                                "required": ["lat", "lon"],
+                                "description": "Point to search from"
+                            },
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 1,
+                                "description": "Candidate points; extra fields (e.g. name, id) are passed through"
+                            },
+                            "k": {"type": "integer", "minimum": 1, "description": "Number of closest points to return (at most the number of points)"}
+                        },
+                        "required": ["query", "points", "k"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Find the k points closest to a query point by Haversine distance. \
+                             Returns them sorted by ascending distance, each with its original fields, index and distance_km."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("K Nearest Points".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "cluster_summary".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 1,
+                                "description": "GPS points in the cluster"
+                            }
+                        },
+                        "required": ["points"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Summarize a cluster of GPS points: the geographic centroid (average of 3D unit vectors), \
+                             the mean and maximum Haversine distance from the centroid, and the latitude/longitude bounding box."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Cluster Summary".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "triangle_area".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {
+                                "type": "object",
+                                "properties": {"lat": {"type": "number"}, "lon": {"type": "number"}},
+                                "required": ["lat", "lon"]
+                            },
+                            "b": {
+                                "type": "object",
+                                "properties": {"lat": {"type": "number"}, "lon": {"type": "number"}},
+                                "required": ["lat", "lon"]
+                            },
+                            "c": {
+                                "type": "object",
+                                "properties": {"lat": {"type": "number"}, "lon": {"type": "number"}},
+                                "required": ["lat", "lon"]
+                            }
+                        },
+                        "required": ["a", "b", "c"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Area of the spherical triangle formed by three GPS points, in km². \
+                             Computes the interior angles with the spherical law of cosines and uses the spherical excess. \
+                             Degenerate (collinear or coincident) triangles have zero area."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Triangle Area".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "rhumb_line".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat1": {"type": "number", "description": "Latitude of first point (-90 to 90)"},
+                            "lon1": {"type": "number", "description": "Longitude of first point (-180 to 180)"},
+                            "lat2": {"type": "number", "description": "Latitude of second point (-90 to 90)"},
+                            "lon2": {"type": "number", "description": "Longitude of second point (-180 to 180)"}
+                        },
+                        "required": ["lat1", "lon1", "lat2", "lon2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Rhumb-line (loxodrome) distance and constant bearing between two GPS coordinates, \
+                             using the Mercator stretched latitude. Includes the great-circle distance and how much \
+                             longer the constant-bearing path is."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Rhumb Line".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "detect_stops".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "points": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"},
+                                        "timestamp": {"type": "number", "description": "Time in seconds (e.g. Unix time); must not decrease along the track"}
+                                    },
+                                    "required": ["lat", "lon", "timestamp"]
+                                },
+                                "description": "Track points in time order"
+                            },
+                            "radius_m": {"type": "number", "exclusiveMinimum": 0, "description": "Maximum distance in meters from the first point of a stop"},
+                            "min_duration_s": {"type": "number", "exclusiveMinimum": 0, "description": "Minimum time in seconds the track must stay within radius_m"}
+                        },
+                        "required": ["points", "radius_m", "min_duration_s"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Find where a timestamped track lingers: runs of consecutive points staying within radius_m of the run's first point (Haversine) \
+                             for at least min_duration_s. Returns each stop's centroid, start and end times, duration and point range."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Detect Stops".to_string()),
+                    }),
+                },
+            ],
             next_cursor: None,
             meta: None,
         })
@@ -61,6 +521,18 @@ impl Guest for GeospatialDistance {
     ) -> Option<CallToolResult> {
         match request.name.as_str() {
             "distance" => Some(execute_distance(&request.arguments)),
+            "route_grades" => Some(execute_route_grades(&request.arguments)),
+            "angular_distance" => Some(execute_angular_distance(&request.arguments)),
+            "terrain_breakdown" => Some(execute_terrain_breakdown(&request.arguments)),
+            "geometric_median" => Some(execute_geometric_median(&request.arguments)),
+            "great_circle_waypoints" => Some(execute_great_circle_waypoints(&request.arguments)),
+            "within_radius" => Some(execute_within_radius(&request.arguments)),
+            "within_radius_batch" => Some(execute_within_radius_batch(&request.arguments)),
+            "k_nearest" => Some(execute_k_nearest(&request.arguments)),
+            "cluster_summary" => Some(execute_cluster_summary(&request.arguments)),
+            "triangle_area" => Some(execute_triangle_area(&request.arguments)),
+            "rhumb_line" => Some(execute_rhumb_line(&request.arguments)),
+            "detect_stops" => Some(execute_detect_stops(&request.arguments)),
             _ => None,
         }
     }
@@ -94,9 +566,893 @@ fn execute_distance(arguments: &Option<String>) -> CallToolResult {
     success_result(result.to_string())
 }
 
-fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
-    const EARTH_RADIUS_KM: f64 = 6371.0;
+fn execute_angular_distance(arguments: &Option<String>) -> CallToolResult {
+    let (lat1, lon1, lat2, lon2) = match parse_distance_args(arguments) {
+        Ok(coords) => coords,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat1, lon1, lat2, lon2) {
+        return error_result(msg);
+    }
+
+    let angle_rad = central_angle(lat1, lon1, lat2, lon2);
+
+    let result = serde_json::json!({
+        "angle_degrees": angle_rad * 180.0 / PI,
+        "angle_radians": angle_rad
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_rhumb_line(arguments: &Option<String>) -> CallToolResult {
+    let (lat1, lon1, lat2, lon2) = match parse_distance_args(arguments) {
+        Ok(coords) => coords,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat1, lon1, lat2, lon2) {
+        return error_result(msg);
+    }
 
+    let (rhumb_km, bearing) = rhumb_line(lat1, lon1, lat2, lon2);
+    let great_circle_km = haversine_distance(lat1, lon1, lat2, lon2);
+
+    let path = if rhumb_km == 0.0 {
+        "same_point"
+    } else if bearing == 0.0 || bearing == 180.0 {
+        "north_south"
+    } else if lat1 == lat2 {
+        "east_west"
+    } else {
+        "oblique"
+    };
+
+    let extra_percent = if great_circle_km > 0.0 {
+        Some((rhumb_km - great_circle_km) / great_circle_km * 100.0)
+    } else {
+        None
+    };
+
+    structured_result(serde_json::json!({
+        "rhumb_distance_km": rhumb_km,
+        "rhumb_bearing_degrees": bearing,
+        "great_circle_distance_km": great_circle_km,
+        "extra_distance_km": rhumb_km - great_circle_km,
+        "extra_percent": extra_percent,
+        "path": path
+    }))
+}
+
+/// Rhumb-line distance in km and constant bearing in degrees [0, 360).
+/// Δψ = ln(tan(π/4 + φ₂/2) / tan(π/4 + φ₁/2)) is the stretched-latitude difference;
+/// on an east-west line it vanishes and q falls back to cos φ.
+pub(crate) fn rhumb_line(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+    let lat1_rad = lat1 * PI / 180.0;
+    let lat2_rad = lat2 * PI / 180.0;
+    let delta_lat = lat2_rad - lat1_rad;
+
+    // Cross the antimeridian when that is the shorter way round
+    let mut delta_lon = (lon2 - lon1) * PI / 180.0;
+    if delta_lon.abs() > PI {
+        delta_lon -= delta_lon.signum() * 2.0 * PI;
+    }
+
+    let delta_psi = ((PI / 4.0 + lat2_rad / 2.0).tan() / (PI / 4.0 + lat1_rad / 2.0).tan()).ln();
+    let q = if delta_psi.abs() > 1e-12 {
+        delta_lat / delta_psi
+    } else {
+        lat1_rad.cos()
+    };
+
+    let distance = (delta_lat * delta_lat + q * q * delta_lon * delta_lon).sqrt() * EARTH_RADIUS_KM;
+    let bearing = (delta_lon.atan2(delta_psi) * 180.0 / PI).rem_euclid(360.0);
+
+    (distance, bearing)
+}
+
+#[derive(Debug)]
+pub(crate) struct Waypoint3D {
+    lat: f64,
+    lon: f64,
+    elevation_m: f64,
+}
+
+#[derive(Debug)]
+pub(crate) struct SegmentGrade {
+    horizontal_distance_km: f64,
+    elevation_delta_m: f64,
+    /// `None` for zero-length segments where the grade is undefined
+    grade_percent: Option<f64>,
+}
+
+fn execute_route_grades(arguments: &Option<String>) -> CallToolResult {
+    let waypoints = match parse_waypoints_3d(arguments) {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_waypoints_3d(&waypoints) {
+        return error_result(msg);
+    }
+
+    let grades = compute_segment_grades(&waypoints);
+
+    let total_distance_km: f64 = grades.iter().map(|g| g.horizontal_distance_km).sum();
+    let net_elevation_m = waypoints[waypoints.len() - 1].elevation_m - waypoints[0].elevation_m;
+
+    let defined: Vec<f64> = grades.iter().filter_map(|g| g.grade_percent).collect();
+    let max_grade = defined.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let min_grade = defined.iter().cloned().fold(f64::INFINITY, f64::min);
+
+    // Average grade is the net elevation change over the total horizontal distance
+    let average_grade = if total_distance_km > 0.0 {
+        Some(net_elevation_m / (total_distance_km * 1000.0) * 100.0)
+    } else {
+        None
+    };
+
+    let segments: Vec<serde_json::Value> = grades
+        .iter()
+        .enumerate()
+        .map(|(i, g)| {
+            serde_json::json!({
+                "from_index": i,
+                "to_index": i + 1,
+                "horizontal_distance_km": g.horizontal_distance_km,
+                "elevation_delta_m": g.elevation_delta_m,
+                "grade_percent": g.grade_percent,
+                "zero_length": g.grade_percent.is_none()
+            })
+        })
+        .collect();
+
+    let result = serde_json::json!({
+        "total_distance_km": total_distance_km,
+        "net_elevation_m": net_elevation_m,
+        "max_grade_percent": if defined.is_empty() { None } else { Some(max_grade) },
+        "min_grade_percent": if defined.is_empty() { None } else { Some(min_grade) },
+        "average_grade_percent": average_grade,
+        "segments": segments
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_terrain_breakdown(arguments: &Option<String>) -> CallToolResult {
+    let waypoints = match parse_waypoints_3d(arguments) {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_waypoints_3d(&waypoints) {
+        return error_result(msg);
+    }
+
+    let threshold = match parse_optional_number(arguments, "threshold_percent") {
+        Ok(t) => t.unwrap_or(DEFAULT_FLAT_THRESHOLD_PERCENT),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !threshold.is_finite() || threshold < 0.0 {
+        return error_result("threshold_percent must be a non-negative number".to_string());
+    }
+
+    let mut uphill_km = 0.0;
+    let mut downhill_km = 0.0;
+    let mut flat_km = 0.0;
+    let (mut uphill_segments, mut downhill_segments, mut flat_segments) = (0, 0, 0);
+
+    // Zero-length segments have no grade and contribute no distance; count them as flat
+    for g in compute_segment_grades(&waypoints) {
+        match g.grade_percent {
+            Some(grade) if grade > threshold => {
+                uphill_km += g.horizontal_distance_km;
+                uphill_segments += 1;
+            }
+            Some(grade) if grade < -threshold => {
+                downhill_km += g.horizontal_distance_km;
+                downhill_segments += 1;
+            }
+            _ => {
+                flat_km += g.horizontal_distance_km;
+                flat_segments += 1;
+            }
+        }
+    }
+
+    let total_distance_km = uphill_km + downhill_km + flat_km;
+    let fraction = |km: f64| {
+        if total_distance_km > 0.0 {
+            km / total_distance_km
+        } else {
+            0.0
+        }
+    };
+
+    structured_result(serde_json::json!({
+        "total_distance_km": total_distance_km,
+        "threshold_percent": threshold,
+        "uphill": {
+            "distance_km": uphill_km,
+            "fraction": fraction(uphill_km),
+            "segments": uphill_segments
+        },
+        "downhill": {
+            "distance_km": downhill_km,
+            "fraction": fraction(downhill_km),
+            "segments": downhill_segments
+        },
+        "flat": {
+            "distance_km": flat_km,
+            "fraction": fraction(flat_km),
+            "segments": flat_segments
+        }
+    }))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Point {
+    lat: f64,
+    lon: f64,
+}
+
+fn execute_geometric_median(arguments: &Option<String>) -> CallToolResult {
+    let points = match parse_points(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if points.is_empty() {
+        return error_result("At least 1 point is required".to_string());
+    }
+
+    for (i, p) in points.iter().enumerate() {
+        if let Err(msg) = validate_coordinate(p.lat, p.lon) {
+            return error_result(format!("Point {}: {}", i, msg));
+        }
+    }
+
+    let tolerance_km = match parse_optional_number(arguments, "tolerance_km") {
+        Ok(t) => t.unwrap_or(DEFAULT_MEDIAN_TOLERANCE_KM),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !tolerance_km.is_finite() || tolerance_km <= 0.0 {
+        return error_result("tolerance_km must be a positive number".to_string());
+    }
+
+    let max_iterations = match parse_max_iterations(arguments) {
+        Ok(m) => m,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (median, iterations, converged) = weiszfeld(&points, tolerance_km, max_iterations);
+    let total_distance_km: f64 = points
+        .iter()
+        .map(|p| haversine_distance(median.lat, median.lon, p.lat, p.lon))
+        .sum();
+
+    let result = serde_json::json!({
+        "median": {"lat": median.lat, "lon": median.lon},
+        "total_distance_km": total_distance_km,
+        "point_count": points.len(),
+        "iterations": iterations,
+        "converged": converged
+    });
+
+    success_result(result.to_string())
+}
+
+fn execute_great_circle_waypoints(arguments: &Option<String>) -> CallToolResult {
+    let (lat1, lon1, lat2, lon2) = match parse_distance_args(arguments) {
+        Ok(coords) => coords,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinates(lat1, lon1, lat2, lon2) {
+        return error_result(msg);
+    }
+
+    let spacing_km = match parse_optional_number(arguments, "spacing_km") {
+        Ok(Some(s)) => s,
+        Ok(None) => return error_result("Missing or invalid parameter 'spacing_km'".to_string()),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !spacing_km.is_finite() || spacing_km <= 0.0 {
+        return error_result("spacing_km must be a positive number".to_string());
+    }
+
+    let start = Point {
+        lat: lat1,
+        lon: lon1,
+    };
+    let end = Point {
+        lat: lat2,
+        lon: lon2,
+    };
+    let total_distance_km = haversine_distance(lat1, lon1, lat2, lon2);
+
+    // Coincident endpoints: the route is a single point
+    if total_distance_km * 1000.0 < ZERO_LENGTH_EPSILON_M {
+        return structured_result(serde_json::json!({
+            "total_distance_km": 0.0,
+            "spacing_km": spacing_km,
+            "waypoint_count": 1,
+            "waypoints": [{"lat": lat1, "lon": lon1, "cumulative_distance_km": 0.0}]
+        }));
+    }
+
+    let angle = central_angle(lat1, lon1, lat2, lon2);
+    if (PI - angle).abs() < 1e-9 {
+        return error_result(
+            "Start and end points are antipodal: the great circle between them is not unique"
+                .to_string(),
+        );
+    }
+
+    // Check in f64 before casting: a tiny spacing gives a quotient far beyond usize
+    let intervals = (total_distance_km / spacing_km).ceil();
+    if intervals + 1.0 > MAX_GREAT_CIRCLE_WAYPOINTS as f64 {
+        return error_result(format!(
+            "spacing_km too small: more than {} waypoints needed",
+            MAX_GREAT_CIRCLE_WAYPOINTS
+        ));
+    }
+    let intervals = intervals as usize;
+
+    let a = to_unit_vector(start);
+    let b = to_unit_vector(end);
+
+    let mut waypoints = Vec::with_capacity(intervals + 1);
+    for i in 0..=intervals {
+        // The last interval may be shorter than spacing_km so the end point lands exactly
+        let cumulative_km = (i as f64 * spacing_km).min(total_distance_km);
+        let point = if i == 0 {
+            start
+        } else if i == intervals {
+            end
+        } else {
+            slerp(a, b, angle, cumulative_km / total_distance_km)
+        };
+        waypoints.push(serde_json::json!({
+            "lat": point.lat,
+            "lon": point.lon,
+            "cumulative_distance_km": cumulative_km
+        }));
+    }
+
+    structured_result(serde_json::json!({
+        "total_distance_km": total_distance_km,
+        "spacing_km": spacing_km,
+        "waypoint_count": waypoints.len(),
+        "waypoints": waypoints
+    }))
+}
+
+/// Spherical linear interpolation between unit vectors `a` and `b` separated by
+/// `angle` radians; `fraction` 0 gives `a` and 1 gives `b`
+pub(crate) fn slerp(a: [f64; 3], b: [f64; 3], angle: f64, fraction: f64) -> Point {
+    let sin_angle = angle.sin();
+    let wa = ((1.0 - fraction) * angle).sin() / sin_angle;
+    let wb = (fraction * angle).sin() / sin_angle;
+    from_unit_vector(sum_vectors([(a, wa), (b, wb)].into_iter()))
+}
+
+fn execute_within_radius(arguments: &Option<String>) -> CallToolResult {
+    let center = match parse_point(arguments, "center") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let point = match parse_point(arguments, "point") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinate(center.lat, center.lon) {
+        return error_result(format!("center: {}", msg));
+    }
+
+    if let Err(msg) = validate_coordinate(point.lat, point.lon) {
+        return error_result(format!("point: {}", msg));
+    }
+
+    let radius_km = match parse_radius_km(arguments) {
+        Ok(r) => r,
+        Err(msg) => return error_result(msg),
+    };
+
+    let distance_km = haversine_distance(center.lat, center.lon, point.lat, point.lon);
+
+    structured_result(serde_json::json!({
+        "within": distance_km <= radius_km,
+        "distance_km": distance_km,
+        "radius_km": radius_km
+    }))
+}
+
+fn execute_within_radius_batch(arguments: &Option<String>) -> CallToolResult {
+    let center = match parse_point(arguments, "center") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinate(center.lat, center.lon) {
+        return error_result(format!("center: {}", msg));
+    }
+
+    let radius_km = match parse_radius_km(arguments) {
+        Ok(r) => r,
+        Err(msg) => return error_result(msg),
+    };
+
+    let entries = match parse_point_entries(arguments, "points") {
+        Ok(e) => e,
+        Err(msg) => return error_result(msg),
+    };
+
+    if entries.len() > MAX_BATCH_POINTS {
+        return error_result(format!(
+            "Too many points: {}. At most {} are allowed",
+            entries.len(),
+            MAX_BATCH_POINTS
+        ));
+    }
+
+    let mut inside_count = 0;
+    let mut error_count = 0;
+    let results: Vec<serde_json::Value> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let point = entry
+                .clone()
+                .and_then(|p| validate_coordinate(p.lat, p.lon).map(|_| p));
+            match point {
+                Ok(p) => {
+                    let distance_km = haversine_distance(center.lat, center.lon, p.lat, p.lon);
+                    let within = distance_km <= radius_km;
+                    if within {
+                        inside_count += 1;
+                    }
+                    serde_json::json!({"index": i, "within": within, "distance_km": distance_km})
+                }
+                Err(msg) => {
+                    error_count += 1;
+                    serde_json::json!({"index": i, "error": msg})
+                }
+            }
+        })
+        .collect();
+
+    structured_result(serde_json::json!({
+        "radius_km": radius_km,
+        "total": entries.len(),
+        "inside_count": inside_count,
+        "error_count": error_count,
+        "results": results
+    }))
+}
+
+fn execute_k_nearest(arguments: &Option<String>) -> CallToolResult {
+    let query = match parse_point(arguments, "query") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinate(query.lat, query.lon) {
+        return error_result(format!("query: {}", msg));
+    }
+
+    let points = match parse_points(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    for (i, p) in points.iter().enumerate() {
+        if let Err(msg) = validate_coordinate(p.lat, p.lon) {
+            return error_result(format!("Point {}: {}", i, msg));
+        }
+    }
+
+    let k = match parse_k(arguments, points.len()) {
+        Ok(k) => k,
+        Err(msg) => return error_result(msg),
+    };
+
+    // Re-read the raw objects so caller-supplied fields survive into the output
+    let originals = match parse_raw_array(arguments, "points") {
+        Ok(o) => o,
+        Err(msg) => return error_result(msg),
+    };
+
+    let mut ranked: Vec<(usize, f64)> = points
+        .iter()
+        .map(|p| haversine_distance(query.lat, query.lon, p.lat, p.lon))
+        .enumerate()
+        .collect();
+    // Stable sort keeps input order between equidistant points
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let nearest: Vec<serde_json::Value> = ranked
+        .iter()
+        .take(k)
+        .map(|&(index, distance_km)| {
+            let mut entry = originals[index].clone();
+            if let Some(obj) = entry.as_object_mut() {
+                obj.insert("index".to_string(), serde_json::json!(index));
+                obj.insert("distance_km".to_string(), serde_json::json!(distance_km));
+            }
+            entry
+        })
+        .collect();
+
+    structured_result(serde_json::json!({
+        "query": {"lat": query.lat, "lon": query.lon},
+        "k": k,
+        "nearest": nearest
+    }))
+}
+
+fn execute_cluster_summary(arguments: &Option<String>) -> CallToolResult {
+    let points = match parse_points(arguments, "points") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if points.is_empty() {
+        return error_result("At least 1 point is required".to_string());
+    }
+
+    for (i, p) in points.iter().enumerate() {
+        if let Err(msg) = validate_coordinate(p.lat, p.lon) {
+            return error_result(format!("Point {}: {}", i, msg));
+        }
+    }
+
+    let centroid = match normalize(sum_vectors(
+        points.iter().map(|p| (to_unit_vector(*p), 1.0)),
+    )) {
+        Some(v) => from_unit_vector(v),
+        None => {
+            return error_result(
+                "Centroid is undefined: the points are spread evenly around the globe".to_string(),
+            );
+        }
+    };
+
+    let distances: Vec<f64> = points
+        .iter()
+        .map(|p| haversine_distance(centroid.lat, centroid.lon, p.lat, p.lon))
+        .collect();
+    let mean_distance_km = distances.iter().sum::<f64>() / distances.len() as f64;
+    let max_distance_km = distances.iter().cloned().fold(0.0, f64::max);
+
+    // Plain min/max bounds; a cluster straddling the antimeridian gets a box spanning the globe
+    let min_lat = points.iter().map(|p| p.lat).fold(f64::INFINITY, f64::min);
+    let max_lat = points
+        .iter()
+        .map(|p| p.lat)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let min_lon = points.iter().map(|p| p.lon).fold(f64::INFINITY, f64::min);
+    let max_lon = points
+        .iter()
+        .map(|p| p.lon)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    structured_result(serde_json::json!({
+        "point_count": points.len(),
+        "centroid": {"lat": centroid.lat, "lon": centroid.lon},
+        "mean_distance_km": mean_distance_km,
+        "max_distance_km": max_distance_km,
+        "bounding_box": {
+            "min_lat": min_lat,
+            "max_lat": max_lat,
+            "min_lon": min_lon,
+            "max_lon": max_lon
+        }
+    }))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TrackPoint {
+    lat: f64,
+    lon: f64,
+    timestamp: f64,
+}
+
+/// Indices of the first and last track point in a stop, inclusive
+#[derive(Debug, PartialEq)]
+pub(crate) struct Stop {
+    start: usize,
+    end: usize,
+}
+
+fn execute_detect_stops(arguments: &Option<String>) -> CallToolResult {
+    let track = match parse_track(arguments) {
+        Ok(t) => t,
+        Err(msg) => return error_result(msg),
+    };
+
+    for (i, p) in track.iter().enumerate() {
+        if let Err(msg) = validate_coordinate(p.lat, p.lon) {
+            return error_result(format!("Point {}: {}", i, msg));
+        }
+        if !p.timestamp.is_finite() {
+            return error_result(format!("Point {}: timestamp must be finite", i));
+        }
+    }
+
+    if let Some(i) = track
+        .windows(2)
+        .position(|pair| pair[1].timestamp < pair[0].timestamp)
+    {
+        return error_result(format!(
+            "Timestamps must not decrease: point {} is earlier than point {}",
+            i + 1,
+            i
+        ));
+    }
+
+    let radius_m = match parse_positive(arguments, "radius_m") {
+        Ok(r) => r,
+        Err(msg) => return error_result(msg),
+    };
+
+    let min_duration_s = match parse_positive(arguments, "min_duration_s") {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    let stops: Vec<serde_json::Value> = detect_stops(&track, radius_m / 1000.0, min_duration_s)
+        .iter()
+        .map(|stop| {
+            let points = &track[stop.start..=stop.end];
+            // Stops span at most a few radii, so the vector centroid never degenerates
+            let centroid = normalize(sum_vectors(points.iter().map(|p| {
+                (
+                    to_unit_vector(Point {
+                        lat: p.lat,
+                        lon: p.lon,
+                    }),
+                    1.0,
+                )
+            })))
+            .map(from_unit_vector)
+            .unwrap_or(Point {
+                lat: points[0].lat,
+                lon: points[0].lon,
+            });
+            serde_json::json!({
+                "location": {"lat": centroid.lat, "lon": centroid.lon},
+                "start_time": points[0].timestamp,
+                "end_time": points[points.len() - 1].timestamp,
+                "duration_s": points[points.len() - 1].timestamp - points[0].timestamp,
+                "start_index": stop.start,
+                "end_index": stop.end,
+                "point_count": points.len()
+            })
+        })
+        .collect();
+
+    let total_stop_duration_s: f64 = stops
+        .iter()
+        .filter_map(|stop| stop["duration_s"].as_f64())
+        .sum();
+
+    structured_result(serde_json::json!({
+        "stop_count": stops.len(),
+        "total_stop_duration_s": total_stop_duration_s,
+        "stops": stops
+    }))
+}
+
+/// Scan the track for stops: starting from an anchor point, extend the run while
+/// each following point stays within `radius_km` of the anchor. A run lasting at
+/// least `min_duration_s` is a stop and scanning resumes after it; otherwise the
+/// anchor moves on by one point.
+pub(crate) fn detect_stops(track: &[TrackPoint], radius_km: f64, min_duration_s: f64) -> Vec<Stop> {
+    let mut stops = Vec::new();
+    let mut start = 0;
+
+    while start < track.len() {
+        let anchor = track[start];
+        let mut end = start;
+        while end + 1 < track.len()
+            && haversine_distance(
+                anchor.lat,
+                anchor.lon,
+                track[end + 1].lat,
+                track[end + 1].lon,
+            ) <= radius_km
+        {
+            end += 1;
+        }
+
+        if track[end].timestamp - anchor.timestamp >= min_duration_s {
+            stops.push(Stop { start, end });
+            start = end + 1;
+        } else {
+            start += 1;
+        }
+    }
+
+    stops
+}
+
+fn execute_triangle_area(arguments: &Option<String>) -> CallToolResult {
+    let mut vertices = Vec::with_capacity(3);
+    for name in ["a", "b", "c"] {
+        let point = match parse_point(arguments, name) {
+            Ok(p) => p,
+            Err(msg) => return error_result(msg),
+        };
+        if let Err(msg) = validate_coordinate(point.lat, point.lon) {
+            return error_result(format!("{}: {}", name, msg));
+        }
+        vertices.push(point);
+    }
+    let (pa, pb, pc) = (vertices[0], vertices[1], vertices[2]);
+
+    // Sides as central angles, each opposite the vertex of the same letter
+    let side_a = central_angle(pb.lat, pb.lon, pc.lat, pc.lon);
+    let side_b = central_angle(pa.lat, pa.lon, pc.lat, pc.lon);
+    let side_c = central_angle(pa.lat, pa.lon, pb.lat, pb.lon);
+
+    let (angles, excess) = match spherical_angles(side_a, side_b, side_c) {
+        Some(angles) => {
+            let excess = (angles.iter().sum::<f64>() - PI).max(0.0);
+            (Some(angles), excess)
+        }
+        None => (None, 0.0),
+    };
+
+    structured_result(serde_json::json!({
+        "area_km2": excess * EARTH_RADIUS_KM * EARTH_RADIUS_KM,
+        "spherical_excess_radians": excess,
+        "side_lengths_km": {
+            "ab": side_c * EARTH_RADIUS_KM,
+            "bc": side_a * EARTH_RADIUS_KM,
+            "ca": side_b * EARTH_RADIUS_KM
+        },
+        "angles_degrees": angles.map(|[a, b, c]| serde_json::json!({
+            "a": a * 180.0 / PI,
+            "b": b * 180.0 / PI,
+            "c": c * 180.0 / PI
+        })),
+        "degenerate": angles.is_none() || excess == 0.0
+    }))
+}
+
+/// Interior angles from the three sides (central angles) via the spherical law
+/// of cosines: cos A = (cos a - cos b cos c) / (sin b sin c). `None` when a side
+/// is too short or too close to π for the angles to be defined.
+pub(crate) fn spherical_angles(a: f64, b: f64, c: f64) -> Option<[f64; 3]> {
+    let angle = |opposite: f64, s1: f64, s2: f64| {
+        let denominator = s1.sin() * s2.sin();
+        if denominator.abs() < 1e-12 {
+            return None;
+        }
+        Some(
+            ((opposite.cos() - s1.cos() * s2.cos()) / denominator)
+                .clamp(-1.0, 1.0)
+                .acos(),
+        )
+    };
+
+    Some([angle(a, b, c)?, angle(b, a, c)?, angle(c, a, b)?])
+}
+
+/// Weiszfeld's algorithm on the sphere: each step is the inverse-distance
+/// weighted mean of the points' unit vectors, projected back onto the sphere.
+/// Points coinciding with the current estimate are left out of the weights to
+/// avoid dividing by zero. Returns the estimate, iterations used and whether
+/// the step size fell below the tolerance.
+pub(crate) fn weiszfeld(
+    points: &[Point],
+    tolerance_km: f64,
+    max_iterations: u64,
+) -> (Point, u64, bool) {
+    let vectors: Vec<[f64; 3]> = points.iter().map(|p| to_unit_vector(*p)).collect();
+
+    // All points coincident: the median is that point
+    let first = points[0];
+    if points.iter().all(|p| {
+        haversine_distance(first.lat, first.lon, p.lat, p.lon) * 1000.0 < ZERO_LENGTH_EPSILON_M
+    }) {
+        return (first, 0, true);
+    }
+
+    // Start from the normalised centroid, or the first point if the centroid is degenerate
+    let mut estimate =
+        normalize(sum_vectors(vectors.iter().map(|v| (*v, 1.0)))).map_or(first, from_unit_vector);
+
+    for iteration in 1..=max_iterations {
+        let weighted = sum_vectors(points.iter().zip(&vectors).filter_map(|(p, v)| {
+            let d = haversine_distance(estimate.lat, estimate.lon, p.lat, p.lon);
+            (d * 1000.0 >= ZERO_LENGTH_EPSILON_M).then(|| (*v, 1.0 / d))
+        }));
+
+        let next = match normalize(weighted) {
+            Some(v) => from_unit_vector(v),
+            None => return (estimate, iteration, false),
+        };
+
+        let step_km = haversine_distance(estimate.lat, estimate.lon, next.lat, next.lon);
+        estimate = next;
+        if step_km < tolerance_km {
+            return (estimate, iteration, true);
+        }
+    }
+
+    (estimate, max_iterations, false)
+}
+
+pub(crate) fn to_unit_vector(p: Point) -> [f64; 3] {
+    let lat = p.lat * PI / 180.0;
+    let lon = p.lon * PI / 180.0;
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+pub(crate) fn from_unit_vector(v: [f64; 3]) -> Point {
+    let lat = v[2].atan2((v[0] * v[0] + v[1] * v[1]).sqrt());
+    let lon = v[1].atan2(v[0]);
+    Point {
+        lat: lat * 180.0 / PI,
+        lon: lon * 180.0 / PI,
+    }
+}
+
+pub(crate) fn sum_vectors(weighted: impl Iterator<Item = ([f64; 3], f64)>) -> [f64; 3] {
+    weighted.fold([0.0; 3], |acc, (v, w)| {
+        [acc[0] + v[0] * w, acc[1] + v[1] * w, acc[2] + v[2] * w]
+    })
+}
+
+/// Scale to unit length; `None` when the vector is too short to have a direction
+pub(crate) fn normalize(v: [f64; 3]) -> Option<[f64; 3]> {
+    let norm = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if norm < 1e-12 {
+        None
+    } else {
+        Some([v[0] / norm, v[1] / norm, v[2] / norm])
+    }
+}
+
+/// Grade of each leg as elevation_delta / horizontal_distance * 100
+pub(crate) fn compute_segment_grades(waypoints: &[Waypoint3D]) -> Vec<SegmentGrade> {
+    waypoints
+        .windows(2)
+        .map(|pair| {
+            let (from, to) = (&pair[0], &pair[1]);
+            let horizontal_distance_km = haversine_distance(from.lat, from.lon, to.lat, to.lon);
+            let elevation_delta_m = to.elevation_m - from.elevation_m;
+
+            // Guard against zero-length segments (repeated waypoints)
+            let grade_percent = if horizontal_distance_km * 1000.0 < ZERO_LENGTH_EPSILON_M {
+                None
+            } else {
+                Some(elevation_delta_m / (horizontal_distance_km * 1000.0) * 100.0)
+            };
+
+            SegmentGrade {
+                horizontal_distance_km,
+                elevation_delta_m,
+                grade_percent,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    EARTH_RADIUS_KM * central_angle(lat1, lon1, lat2, lon2)
+}
+
+/// Great-circle central angle in radians (the Haversine `c` term)
+pub(crate) fn central_angle(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     let lat1_rad = lat1 * PI / 180.0;
     let lat2_rad = lat2 * PI / 180.0;
     let delta_lat = (lat2 - lat1) * PI / 180.0;
@@ -105,28 +1461,48 @@ fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     let a = (delta_lat / 2.0).sin().powi(2)
         + lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
 
-    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
 
-    EARTH_RADIUS_KM * c
+/// Require at least 2 waypoints with valid coordinates and finite elevations
+pub(crate) fn validate_waypoints_3d(waypoints: &[Waypoint3D]) -> Result<(), String> {
+    if waypoints.len() < 2 {
+        return Err("Route must have at least 2 waypoints".to_string());
+    }
+
+    for (i, wp) in waypoints.iter().enumerate() {
+        validate_coordinate(wp.lat, wp.lon).map_err(|msg| format!("Waypoint {}: {}", i, msg))?;
+        if !wp.elevation_m.is_finite() {
+            return Err(format!("Waypoint {}: elevation_m must be finite", i));
+        }
+    }
+
+    Ok(())
 }
 
-fn validate_coordinates(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<(), String> {
+pub(crate) fn validate_coordinates(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+) -> Result<(), String> {
+    validate_coordinate(lat1, lon1)?;
+    validate_coordinate(lat2, lon2)
+}
+
+pub(crate) fn validate_coordinate(lat: f64, lon: f64) -> Result<(), String> {
     // Check for NaN or Infinite
-    if lat1.is_nan() || lat1.is_infinite()
-        || lon1.is_nan() || lon1.is_infinite()
-        || lat2.is_nan() || lat2.is_infinite()
-        || lon2.is_nan() || lon2.is_infinite()
-    {
+    if !lat.is_finite() || !lon.is_finite() {
         return Err("Input contains invalid values (NaN or Infinite)".to_string());
     }
 
     // Validate latitude range
-    if lat1 < -90.0 || lat1 > 90.0 || lat2 < -90.0 || lat2 > 90.0 {
+    if !(-90.0..=90.0).contains(&lat) {
         return Err("Latitude must be between -90 and 90 degrees".to_string());
     }
 
     // Validate longitude range
-    if lon1 < -180.0 || lon1 > 180.0 || lon2 < -180.0 || lon2 > 180.0 {
+    if !(-180.0..=180.0).contains(&lon) {
         return Err("Longitude must be between -180 and 180 degrees".to_string());
     }
 
@@ -164,6 +1540,300 @@ fn parse_distance_args(arguments: &Option<String>) -> Result<(f64, f64, f64, f64
     Ok((lat1, lon1, lat2, lon2))
 }
 
+fn parse_waypoints_3d(arguments: &Option<String>) -> Result<Vec<Waypoint3D>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let waypoints_arr = json
+        .get("waypoints")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid 'waypoints' parameter".to_string())?;
+
+    let mut waypoints = Vec::new();
+    for (i, wp) in waypoints_arr.iter().enumerate() {
+        let lat = wp
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'waypoints[{}].lat'", i))?;
+
+        let lon = wp
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'waypoints[{}].lon'", i))?;
+
+        let elevation_m = wp
+            .get("elevation_m")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'waypoints[{}].elevation_m'", i))?;
+
+        waypoints.push(Waypoint3D {
+            lat,
+            lon,
+            elevation_m,
+        });
+    }
+
+    Ok(waypoints)
+}
+
+/// Parse an array of `{lat, lon}` objects under `name`
+fn parse_points(arguments: &Option<String>, name: &str) -> Result<Vec<Point>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let points_arr = json
+        .get(name)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    let mut points = Vec::new();
+    for (i, p) in points_arr.iter().enumerate() {
+        let lat = p
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid '{}[{}].lat'", name, i))?;
+
+        let lon = p
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid '{}[{}].lon'", name, i))?;
+
+        points.push(Point { lat, lon });
+    }
+
+    Ok(points)
+}
+
+/// Parse a single `{lat, lon}` object under `name`
+fn parse_point(arguments: &Option<String>, name: &str) -> Result<Point, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let point = json
+        .get(name)
+        .filter(|v| v.is_object())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    let lat = point
+        .get("lat")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lat'", name))?;
+
+    let lon = point
+        .get("lon")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid '{}.lon'", name))?;
+
+    Ok(Point { lat, lon })
+}
+
+/// Parse an array of `{lat, lon}` objects under `name`, keeping a per-entry
+/// error for malformed items instead of rejecting the whole array
+fn parse_point_entries(
+    arguments: &Option<String>,
+    name: &str,
+) -> Result<Vec<Result<Point, String>>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let points_arr = json
+        .get(name)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    Ok(points_arr
+        .iter()
+        .map(|p| {
+            let lat = p
+                .get("lat")
+                .and_then(|v| v.as_f64())
+                .ok_or_else(|| "Missing or invalid 'lat'".to_string())?;
+            let lon = p
+                .get("lon")
+                .and_then(|v| v.as_f64())
+                .ok_or_else(|| "Missing or invalid 'lon'".to_string())?;
+            Ok(Point { lat, lon })
+        })
+        .collect())
+}
+
+/// Parse the `points` array of `{lat, lon, timestamp}` objects
+fn parse_track(arguments: &Option<String>) -> Result<Vec<TrackPoint>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let points_arr = json
+        .get("points")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid 'points' parameter".to_string())?;
+
+    let mut track = Vec::new();
+    for (i, p) in points_arr.iter().enumerate() {
+        let lat = p
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'points[{}].lat'", i))?;
+
+        let lon = p
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'points[{}].lon'", i))?;
+
+        let timestamp = p
+            .get("timestamp")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid 'points[{}].timestamp'", i))?;
+
+        track.push(TrackPoint {
+            lat,
+            lon,
+            timestamp,
+        });
+    }
+
+    Ok(track)
+}
+
+/// Parse `k` for nearest-neighbor queries, requiring `1 <= k <= count`
+fn parse_k(arguments: &Option<String>, count: usize) -> Result<usize, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let k = json
+        .get("k")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'k'".to_string())?;
+
+    if k < 1 || k > count as u64 {
+        return Err(format!(
+            "Invalid k: {}. Must be between 1 and the number of points ({})",
+            k, count
+        ));
+    }
+
+    Ok(k as usize)
+}
+
+fn parse_raw_array(
+    arguments: &Option<String>,
+    name: &str,
+) -> Result<Vec<serde_json::Value>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    json.get(name)
+        .and_then(|v| v.as_array())
+        .cloned()
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))
+}
+
+fn parse_radius_km(arguments: &Option<String>) -> Result<f64, String> {
+    let radius_km = parse_optional_number(arguments, "radius_km")?
+        .ok_or_else(|| "Missing or invalid parameter 'radius_km'".to_string())?;
+
+    if !radius_km.is_finite() || radius_km < 0.0 {
+        return Err("radius_km must be a non-negative number".to_string());
+    }
+
+    Ok(radius_km)
+}
+
+/// Parse the required number under `name`, which must be finite and positive
+fn parse_positive(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let value = parse_optional_number(arguments, name)?
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("{} must be a positive number", name));
+    }
+
+    Ok(value)
+}
+
+/// Parse the optional `max_iterations` for iterative methods
+fn parse_max_iterations(arguments: &Option<String>) -> Result<u64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let max_iterations = match json.get("max_iterations") {
+        None | Some(serde_json::Value::Null) => return Ok(DEFAULT_MAX_ITERATIONS),
+        Some(v) => v.as_u64().ok_or_else(|| {
+            "Invalid parameter 'max_iterations': expected a positive integer".to_string()
+        })?,
+    };
+
+    if !(1..=MAX_ITERATIONS_CAP).contains(&max_iterations) {
+        return Err(format!(
+            "Invalid max_iterations: {}. Must be between 1 and {}",
+            max_iterations, MAX_ITERATIONS_CAP
+        ));
+    }
+
+    Ok(max_iterations)
+}
+
+fn parse_optional_number(arguments: &Option<String>, name: &str) -> Result<Option<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid parameter '{}': expected a number", name)),
+    }
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
 fn success_result(result: String) -> CallToolResult {
     CallToolResult {
         content: vec![ContentBlock::Text(TextContent {
@@ -189,3 +1859,211 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(GeospatialDistance with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONDON: (f64, f64) = (51.5074, -0.1278);
+    const PARIS: (f64, f64) = (48.8566, 2.3522);
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn structured(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(
+            result
+                .structured_content
+                .as_deref()
+                .expect("structured content"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn haversine_london_to_paris() {
+        let d = haversine_distance(LONDON.0, LONDON.1, PARIS.0, PARIS.1);
+        assert!((d - 344.0).abs() < 1.0, "got {}", d);
+    }
+
+    #[test]
+    fn haversine_is_symmetric_and_zero_on_identity() {
+        let there = haversine_distance(LONDON.0, LONDON.1, PARIS.0, PARIS.1);
+        let back = haversine_distance(PARIS.0, PARIS.1, LONDON.0, LONDON.1);
+        assert!((there - back).abs() < 1e-9);
+        assert_eq!(haversine_distance(10.0, 20.0, 10.0, 20.0), 0.0);
+    }
+
+    #[test]
+    fn central_angle_pole_to_equator_is_quarter_turn() {
+        assert!((central_angle(90.0, 0.0, 0.0, 45.0) - PI / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn validate_coordinate_rejects_out_of_range_and_nan() {
+        assert!(validate_coordinate(91.0, 0.0).is_err());
+        assert!(validate_coordinate(0.0, -180.5).is_err());
+        assert!(validate_coordinate(f64::NAN, 0.0).is_err());
+        assert!(validate_coordinate(-90.0, 180.0).is_ok());
+    }
+
+    #[test]
+    fn unit_vector_round_trip() {
+        let p = Point {
+            lat: 37.5,
+            lon: -122.25,
+        };
+        let back = from_unit_vector(to_unit_vector(p));
+        assert!((back.lat - p.lat).abs() < 1e-9 && (back.lon - p.lon).abs() < 1e-9);
+    }
+
+    #[test]
+    fn slerp_midpoint_along_equator() {
+        let a = to_unit_vector(Point { lat: 0.0, lon: 0.0 });
+        let b = to_unit_vector(Point {
+            lat: 0.0,
+            lon: 90.0,
+        });
+        let mid = slerp(a, b, PI / 2.0, 0.5);
+        assert!(mid.lat.abs() < 1e-9 && (mid.lon - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn great_circle_waypoints_rejects_tiny_spacing() {
+        for spacing in ["1e-300", "0.0001"] {
+            let json = format!(
+                r#"{{"lat1": 0, "lon1": 0, "lat2": 0, "lon2": 10, "spacing_km": {}}}"#,
+                spacing
+            );
+            let result = execute_great_circle_waypoints(&Some(json));
+            assert_eq!(result.is_error, Some(true), "{}", spacing);
+        }
+
+        let json = r#"{"lat1": 0, "lon1": 0, "lat2": 0, "lon2": 10, "spacing_km": 100}"#;
+        let result = execute_great_circle_waypoints(&Some(json.to_string()));
+        assert_eq!(result.is_error, None);
+    }
+
+    #[test]
+    fn segment_grades_flag_zero_length_segments() {
+        let grades = compute_segment_grades(&[
+            Waypoint3D {
+                lat: 0.0,
+                lon: 0.0,
+                elevation_m: 0.0,
+            },
+            Waypoint3D {
+                lat: 0.0,
+                lon: 0.0,
+                elevation_m: 10.0,
+            },
+        ]);
+        assert_eq!(grades[0].grade_percent, None);
+        assert_eq!(grades[0].elevation_delta_m, 10.0);
+    }
+
+    #[test]
+    fn weiszfeld_median_of_coincident_points() {
+        let p = Point { lat: 1.0, lon: 2.0 };
+        let (median, iterations, converged) = weiszfeld(&[p, p, p], 1e-6, 100);
+        assert_eq!(
+            (median.lat, median.lon, iterations, converged),
+            (1.0, 2.0, 0, true)
+        );
+    }
+
+    #[test]
+    fn octant_triangle_covers_an_eighth_of_the_sphere() {
+        let result = execute_triangle_area(&args(
+            r#"{"a": {"lat": 90, "lon": 0}, "b": {"lat": 0, "lon": 0}, "c": {"lat": 0, "lon": 90}}"#,
+        ));
+        let area = structured(&result)["area_km2"].as_f64().unwrap();
+        let expected = 4.0 * PI * EARTH_RADIUS_KM * EARTH_RADIUS_KM / 8.0;
+        assert!((area - expected).abs() / expected < 1e-9);
+    }
+
+    #[test]
+    fn within_radius_counts_boundary_as_inside() {
+        let result = execute_within_radius(&args(
+            r#"{"center": {"lat": 0, "lon": 0}, "point": {"lat": 0, "lon": 0}, "radius_km": 0}"#,
+        ));
+        assert_eq!(structured(&result)["within"], true);
+    }
+
+    #[test]
+    fn k_nearest_rejects_k_larger_than_input() {
+        let result = execute_k_nearest(&args(
+            r#"{"query": {"lat": 0, "lon": 0}, "points": [{"lat": 1, "lon": 1}], "k": 2}"#,
+        ));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn rhumb_line_special_cases() {
+        // Due north along a meridian matches the great circle
+        let (distance, bearing) = rhumb_line(0.0, 10.0, 10.0, 10.0);
+        assert!((distance - haversine_distance(0.0, 10.0, 10.0, 10.0)).abs() < 1e-9);
+        assert_eq!(bearing, 0.0);
+
+        // Due west along a parallel: Δλ · R · cos φ
+        let (distance, bearing) = rhumb_line(60.0, 10.0, 60.0, 0.0);
+        let expected = 10.0 * PI / 180.0 * EARTH_RADIUS_KM * (60.0 * PI / 180.0).cos();
+        assert!((distance - expected).abs() < 1e-9);
+        assert_eq!(bearing, 270.0);
+    }
+
+    #[test]
+    fn rhumb_line_is_never_shorter_than_great_circle() {
+        let result = execute_rhumb_line(&args(
+            r#"{"lat1": 51.5, "lon1": -0.1, "lat2": 40.7, "lon2": -74.0}"#,
+        ));
+        let value = structured(&result);
+        assert!(value["extra_distance_km"].as_f64().unwrap() > 0.0);
+        assert_eq!(value["path"], "oblique");
+
+        // Crossing the antimeridian takes the short way east
+        let (_, bearing) = rhumb_line(0.0, 179.0, 0.0, -179.0);
+        assert_eq!(bearing, 90.0);
+    }
+
+    fn track(points: &[(f64, f64, f64)]) -> Vec<TrackPoint> {
+        points
+            .iter()
+            .map(|&(lat, lon, timestamp)| TrackPoint {
+                lat,
+                lon,
+                timestamp,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detect_stops_finds_lingering_runs() {
+        // ~11 m per 0.0001° of latitude: linger at the origin, move off, linger again
+        let points = track(&[
+            (0.0, 0.0, 0.0),
+            (0.0001, 0.0, 60.0),
+            (0.0, 0.0001, 120.0),
+            (0.01, 0.0, 130.0),
+            (0.02, 0.0, 140.0),
+            (0.02, 0.0001, 400.0),
+        ]);
+        let stops = detect_stops(&points, 0.05, 100.0);
+        assert_eq!(
+            stops,
+            vec![Stop { start: 0, end: 2 }, Stop { start: 4, end: 5 }]
+        );
+
+        assert!(detect_stops(&points, 0.05, 1000.0).is_empty());
+    }
+
+    #[test]
+    fn detect_stops_rejects_decreasing_timestamps() {
+        let result = execute_detect_stops(&args(
+            r#"{"points": [{"lat": 0, "lon": 0, "timestamp": 10}, {"lat": 0, "lon": 0, "timestamp": 5}],
+                "radius_m": 50, "min_duration_s": 60}"#,
+        ));
+        assert_eq!(result.is_error, Some(true));
+    }
+}
@@ -0,0 +1,500 @@
+//! Geospatial Geohash Tool
+//!
+//! Encode GPS coordinates as geohash strings and decode them back to cells.
+//! Geohashes interleave longitude and latitude bisection bits and emit them in
+//! base 32, so nearby points usually share a common prefix.
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "geospatial-geohash",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+
+struct GeospatialGeohash;
+
+/// Geohash base-32 alphabet (omits a, i, l and o)
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+const MIN_PRECISION: u64 = 1;
+const MAX_PRECISION: u64 = 12;
+
+/// Neighbor lookup tables indexed by [direction][geohash length parity]:
+/// the character at position i of the table maps to the base-32 character of
+/// the adjacent cell whose index is the position of the input character.
+const NEIGHBORS: [[&str; 2]; 4] = [
+    [
+        "p0r21436x8zb9dcf5h7kjnmqesgutwvy",
+        "bc01fg45238967deuvhjyznpkmstqrwx",
+    ],
+    [
+        "bc01fg45238967deuvhjyznpkmstqrwx",
+        "p0r21436x8zb9dcf5h7kjnmqesgutwvy",
+    ],
+    [
+        "14365h7k9dcfesgujnmqp0r2twvyx8zb",
+        "238967debc01fg45kmstqrwxuvhjyznp",
+    ],
+    [
+        "238967debc01fg45kmstqrwxuvhjyznp",
+        "14365h7k9dcfesgujnmqp0r2twvyx8zb",
+    ],
+];
+
+/// Characters on each edge of a cell; moving across them changes the parent cell
+const BORDERS: [[&str; 2]; 4] = [
+    ["prxz", "bcfguvyz"],
+    ["bcfguvyz", "prxz"],
+    ["028b", "0145hjnp"],
+    ["0145hjnp", "028b"],
+];
+
+impl Guest for GeospatialGeohash {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        _request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        Ok(ListToolsResult {
+            tools: vec![
+                Tool {
+                    name: "geohash_encode".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number", "description": "Latitude (-90 to 90)"},
+                            "lon": {"type": "number", "description": "Longitude (-180 to 180)"},
+                            "precision": {"type": "integer", "minimum": 1, "maximum": 12, "description": "Number of geohash characters"}
+                        },
+                        "required": ["lat", "lon", "precision"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Encode a GPS coordinate as a geohash string of the given precision (1-12 characters)."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Geohash Encode".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "geohash_neighbors".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "geohash": {"type": "string", "minLength": 1, "maxLength": 12, "description": "Geohash string"}
+                        },
+                        "required": ["geohash"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Return the eight geohash cells surrounding a geohash at the same precision, keyed by direction \
+                             (n, ne, e, se, s, sw, w, nw). Neighbors beyond a pole are null."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Geohash Neighbors".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "geohash_decode".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "geohash": {"type": "string", "minLength": 1, "maxLength": 12, "description": "Geohash string"}
+                        },
+                        "required": ["geohash"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Decode a geohash string into its bounding box and center coordinate."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Geohash Decode".to_string()),
+                    }),
+                },
+            ],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "geohash_encode" => Some(execute_geohash_encode(&request.arguments)),
+            "geohash_decode" => Some(execute_geohash_decode(&request.arguments)),
+            "geohash_neighbors" => Some(execute_geohash_neighbors(&request.arguments)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// Latitude/longitude extent of a geohash cell
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BoundingBox {
+    min_lat: f64,
+    max_lat: f64,
+    min_lon: f64,
+    max_lon: f64,
+}
+
+impl BoundingBox {
+    pub(crate) fn center(&self) -> (f64, f64) {
+        (
+            (self.min_lat + self.max_lat) / 2.0,
+            (self.min_lon + self.max_lon) / 2.0,
+        )
+    }
+}
+
+fn execute_geohash_encode(arguments: &Option<String>) -> CallToolResult {
+    let (lat, lon, precision) = match parse_encode_args(arguments) {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_coordinate(lat, lon) {
+        return error_result(msg);
+    }
+
+    let geohash = encode(lat, lon, precision);
+
+    structured_result(serde_json::json!({
+        "geohash": geohash,
+        "precision": precision,
+        "lat": lat,
+        "lon": lon
+    }))
+}
+
+fn execute_geohash_decode(arguments: &Option<String>) -> CallToolResult {
+    let geohash = match parse_geohash(arguments) {
+        Ok(g) => g,
+        Err(msg) => return error_result(msg),
+    };
+
+    let bbox = match decode(&geohash) {
+        Ok(b) => b,
+        Err(msg) => return error_result(msg),
+    };
+    let (center_lat, center_lon) = bbox.center();
+
+    structured_result(serde_json::json!({
+        "geohash": geohash,
+        "center": {"lat": center_lat, "lon": center_lon},
+        "bounding_box": {
+            "min_lat": bbox.min_lat,
+            "max_lat": bbox.max_lat,
+            "min_lon": bbox.min_lon,
+            "max_lon": bbox.max_lon
+        },
+        "lat_error": (bbox.max_lat - bbox.min_lat) / 2.0,
+        "lon_error": (bbox.max_lon - bbox.min_lon) / 2.0
+    }))
+}
+
+fn execute_geohash_neighbors(arguments: &Option<String>) -> CallToolResult {
+    let geohash = match parse_geohash(arguments) {
+        Ok(g) => g,
+        Err(msg) => return error_result(msg),
+    };
+
+    let bbox = match decode(&geohash) {
+        Ok(b) => b,
+        Err(msg) => return error_result(msg),
+    };
+
+    // Longitude wraps around the antimeridian, but there is nothing beyond the poles
+    let north = (bbox.max_lat < 90.0).then(|| adjacent(&geohash, Direction::North));
+    let south = (bbox.min_lat > -90.0).then(|| adjacent(&geohash, Direction::South));
+    let east = adjacent(&geohash, Direction::East);
+    let west = adjacent(&geohash, Direction::West);
+
+    let neighbors = serde_json::json!({
+        "n": north,
+        "ne": north.as_deref().map(|n| adjacent(n, Direction::East)),
+        "e": east,
+        "se": south.as_deref().map(|s| adjacent(s, Direction::East)),
+        "s": south,
+        "sw": south.as_deref().map(|s| adjacent(s, Direction::West)),
+        "w": west,
+        "nw": north.as_deref().map(|n| adjacent(n, Direction::West))
+    });
+
+    structured_result(serde_json::json!({
+        "geohash": geohash,
+        "neighbors": neighbors
+    }))
+}
+
+/// Adjacent cell in the given direction at the same precision. Expects a
+/// validated, non-empty geohash. When the last character sits on the border
+/// being crossed, the parent prefix is moved in the same direction first.
+pub(crate) fn adjacent(geohash: &str, direction: Direction) -> String {
+    let (parent, last) = geohash.split_at(geohash.len() - 1);
+    let last = last.chars().next().unwrap_or('0');
+    let parity = geohash.len() % 2;
+    let dir = direction as usize;
+
+    let parent = if BORDERS[dir][parity].contains(last) && !parent.is_empty() {
+        adjacent(parent, direction)
+    } else {
+        parent.to_string()
+    };
+
+    let index = NEIGHBORS[dir][parity].find(last).unwrap_or(0);
+    format!("{}{}", parent, BASE32[index] as char)
+}
+
+/// Standard geohash encoding: alternately bisect the longitude and latitude
+/// ranges (longitude first), emitting one base-32 character per 5 bits
+pub(crate) fn encode(lat: f64, lon: f64, precision: usize) -> String {
+    let (mut lat_lo, mut lat_hi) = (-90.0, 90.0);
+    let (mut lon_lo, mut lon_hi) = (-180.0, 180.0);
+    let mut geohash = String::with_capacity(precision);
+    let mut even_bit = true;
+
+    while geohash.len() < precision {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (value, lo, hi) = if even_bit {
+                (lon, &mut lon_lo, &mut lon_hi)
+            } else {
+                (lat, &mut lat_lo, &mut lat_hi)
+            };
+
+            let mid = (*lo + *hi) / 2.0;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                *lo = mid;
+            } else {
+                *hi = mid;
+            }
+            even_bit = !even_bit;
+        }
+        geohash.push(BASE32[index] as char);
+    }
+
+    geohash
+}
+
+/// Reverse of `encode`: replay the bisections to recover the cell bounds
+pub(crate) fn decode(geohash: &str) -> Result<BoundingBox, String> {
+    let mut bbox = BoundingBox {
+        min_lat: -90.0,
+        max_lat: 90.0,
+        min_lon: -180.0,
+        max_lon: 180.0,
+    };
+    let mut even_bit = true;
+
+    for c in geohash.chars() {
+        let index = base32_index(c)?;
+        for shift in (0..5).rev() {
+            let bit = (index >> shift) & 1;
+            let (lo, hi) = if even_bit {
+                (&mut bbox.min_lon, &mut bbox.max_lon)
+            } else {
+                (&mut bbox.min_lat, &mut bbox.max_lat)
+            };
+
+            let mid = (*lo + *hi) / 2.0;
+            if bit == 1 {
+                *lo = mid;
+            } else {
+                *hi = mid;
+            }
+            even_bit = !even_bit;
+        }
+    }
+
+    Ok(bbox)
+}
+
+pub(crate) fn base32_index(c: char) -> Result<usize, String> {
+    BASE32
+        .iter()
+        .position(|&b| b as char == c)
+        .ok_or_else(|| format!("Invalid geohash character '{}'", c))
+}
+
+pub(crate) fn validate_coordinate(lat: f64, lon: f64) -> Result<(), String> {
+    if !lat.is_finite() || !lon.is_finite() {
+        return Err("Input contains invalid values (NaN or Infinite)".to_string());
+    }
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!(
+            "Invalid latitude: {}. Must be between -90 and 90",
+            lat
+        ));
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return Err(format!(
+            "Invalid longitude: {}. Must be between -180 and 180",
+            lon
+        ));
+    }
+    Ok(())
+}
+
+fn parse_encode_args(arguments: &Option<String>) -> Result<(f64, f64, usize), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let lat = json
+        .get("lat")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'lat'".to_string())?;
+
+    let lon = json
+        .get("lon")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'lon'".to_string())?;
+
+    let precision = json
+        .get("precision")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'precision'".to_string())?;
+
+    if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+        return Err(format!(
+            "Invalid precision: {}. Must be between {} and {}",
+            precision, MIN_PRECISION, MAX_PRECISION
+        ));
+    }
+
+    Ok((lat, lon, precision as usize))
+}
+
+/// Parse `geohash`, normalising to lowercase and checking length and alphabet
+fn parse_geohash(arguments: &Option<String>) -> Result<String, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let geohash = json
+        .get("geohash")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing or invalid parameter 'geohash'".to_string())?
+        .trim()
+        .to_ascii_lowercase();
+
+    let len = geohash.chars().count() as u64;
+    if !(MIN_PRECISION..=MAX_PRECISION).contains(&len) {
+        return Err(format!(
+            "Invalid geohash length: {}. Must be between {} and {} characters",
+            len, MIN_PRECISION, MAX_PRECISION
+        ));
+    }
+
+    for c in geohash.chars() {
+        base32_index(c)?;
+    }
+
+    Ok(geohash)
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(GeospatialGeohash with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_reference_location() {
+        // Jutland reference point from the original geohash description
+        assert_eq!(encode(57.64911, 10.40744, 11), "u4pruydqqvj");
+    }
+
+    #[test]
+    fn decode_contains_encoded_point() {
+        let bbox = decode("u4pruydqqvj").unwrap();
+        assert!(bbox.min_lat <= 57.64911 && 57.64911 <= bbox.max_lat);
+        assert!(bbox.min_lon <= 10.40744 && 10.40744 <= bbox.max_lon);
+
+        let (lat, lon) = bbox.center();
+        assert_eq!(encode(lat, lon, 11), "u4pruydqqvj");
+    }
+
+    #[test]
+    fn decode_rejects_invalid_characters() {
+        // 'a', 'i', 'l' and 'o' are not in the geohash alphabet
+        assert!(decode("u4a").is_err());
+        assert!(base32_index('o').is_err());
+        assert_eq!(base32_index('z'), Ok(31));
+    }
+
+    #[test]
+    fn adjacent_cells() {
+        assert_eq!(adjacent("gbsuv", Direction::North), "gbsvj");
+        assert_eq!(adjacent("gbsuv", Direction::East), "gbsuy");
+        assert_eq!(adjacent("gbsuv", Direction::South), "gbsut");
+        assert_eq!(adjacent("gbsuv", Direction::West), "gbsuu");
+    }
+
+    #[test]
+    fn validate_coordinate_ranges() {
+        assert!(validate_coordinate(-91.0, 0.0).is_err());
+        assert!(validate_coordinate(0.0, f64::NAN).is_err());
+        assert!(validate_coordinate(90.0, -180.0).is_ok());
+    }
+}
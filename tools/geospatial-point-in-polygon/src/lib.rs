@@ -1,7 +1,9 @@
 //! Geospatial Point-in-Polygon Tool
 //!
 //! Check if a GPS point is inside a polygon using ray casting algorithm.
-//! Useful for geofencing and zone detection.
+//! Useful for geofencing and zone detection. Outlines can also be smoothed
+//! with Chaikin's corner-cutting algorithm, and one polygon can be checked for
+//! lying entirely within another.
 
 mod bindings {
     wit_bindgen::generate!({
@@ -13,10 +15,15 @@ mod bindings {
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use std::f64::consts::PI;
 
 struct GeospatialPointInPolygon;
 
 const EPSILON: f64 = 1e-10;
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Upper bound on the ring produced by `smooth_polygon`; each Chaikin pass doubles the vertex count
+const MAX_SMOOTHED_VERTICES: usize = 100_000;
 
 impl Guest for GeospatialPointInPolygon {
     fn list_tools(
@@ -25,48 +32,224 @@ impl Guest for GeospatialPointInPolygon {
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
         Ok(ListToolsResult {
-            tools: vec![Tool {
-                name: "point_in_polygon".to_string(),
-                input_schema: r#"{
-                    "type": "object",
-                    "properties": {
-                        "point": {
-                            "type": "object",
-                            "properties": {
-                                "lat": {"type": "number", "description": "Point latitude"},
-                                "lon": {"type": "number", "description": "Point longitude"}
-                            },
-                            "required": ["lat", "lon"]
-                        },
-                        "polygon": {
-                            "type": "array",
-                            "items": {
+            tools: vec![
+                Tool {
+                    name: "point_in_polygon".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "point": {
                                 "type": "object",
                                 "properties": {
-                                    "lat": {"type": "number"},
-                                    "lon": {"type": "number"}
+                                    "lat": {"type": "number", "description": "Point latitude"},
+                                    "lon": {"type": "number", "description": "Point longitude"}
                                 },
                                 "required": ["lat", "lon"]
                             },
-                            "minItems": 3,
-                            "description": "Polygon vertices (at least 3 points)"
-                        }
-                    },
-                    "required": ["point", "polygon"]
-                }"#
-                .to_string(),
-                options: Some(ToolOptions {
-                    meta: None,
-                    annotations: None,
-                    description: Some(
-                        "Check if a GPS point is inside a polygon using ray casting algorithm. \
-                         Returns whether point is inside, on boundary, and algorithm used. Perfect for geofencing."
-                            .to_string(),
-                    ),
-                    output_schema: None,
-                    title: Some("Point in Polygon Check".to_string()),
-                }),
-            }],
+                            "polygon": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Polygon vertices (at least 3 points)"
+                            }
+                        },
+                        "required": ["point", "polygon"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Check if a GPS point is inside a polygon using ray casting algorithm. \
+                             Returns whether point is inside, on boundary, and algorithm used. Perfect for geofencing."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Point in Polygon Check".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "polygon_area_delta".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "polygon_a": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Original polygon vertices (at least 3 points)"
+                            },
+                            "polygon_b": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Revised polygon vertices (at least 3 points)"
+                            }
+                        },
+                        "required": ["polygon_a", "polygon_b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Compare the spherical areas of two polygons, e.g. two versions of a geofence. \
+                             Returns both areas, the absolute difference, and the percent change from polygon_a to polygon_b."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Polygon Area Delta".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "is_simple_polygon".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "polygon": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Polygon vertices (at least 3 points)"
+                            }
+                        },
+                        "required": ["polygon"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Check that a polygon is simple, i.e. no two non-adjacent edges touch or cross. \
+                             Returns the first crossing edge pair when the polygon is self-intersecting."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Simple Polygon Check".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "smooth_polygon".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "polygon": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Polygon vertices (at least 3 points)"
+                            },
+                            "method": {
+                                "type": "string",
+                                "enum": ["chaikin"],
+                                "default": "chaikin",
+                                "description": "Smoothing algorithm"
+                            },
+                            "iterations": {
+                                "type": "integer",
+                                "minimum": 1,
+                                "default": 1,
+                                "description": "Number of smoothing passes; each pass doubles the vertex count"
+                            }
+                        },
+                        "required": ["polygon"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Smooth a polygon outline with Chaikin's corner-cutting algorithm, treating lat/lon as planar. \
+                             The result is an approximation that shrinks slightly inside the original corners and doubles the vertex count per iteration."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Smooth Polygon".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "polygon_contains_polygon".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "outer": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Containing polygon vertices (at least 3 points)"
+                            },
+                            "inner": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "lat": {"type": "number"},
+                                        "lon": {"type": "number"}
+                                    },
+                                    "required": ["lat", "lon"]
+                                },
+                                "minItems": 3,
+                                "description": "Contained polygon vertices (at least 3 points)"
+                            }
+                        },
+                        "required": ["outer", "inner"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Check that the inner polygon lies strictly within the outer polygon: every inner vertex is inside the outer one \
+                             and no edges touch or cross. Both polygons must be simple. Reports the first outside vertex and the first crossing edge pair."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Polygon Contains Polygon".to_string()),
+                    }),
+                },
+            ],
             next_cursor: None,
             meta: None,
         })
@@ -79,13 +262,19 @@ impl Guest for GeospatialPointInPolygon {
     ) -> Option<CallToolResult> {
         match request.name.as_str() {
             "point_in_polygon" => Some(execute_point_in_polygon(&request.arguments)),
+            "polygon_area_delta" => Some(execute_polygon_area_delta(&request.arguments)),
+            "is_simple_polygon" => Some(execute_is_simple_polygon(&request.arguments)),
+            "smooth_polygon" => Some(execute_smooth_polygon(&request.arguments)),
+            "polygon_contains_polygon" => {
+                Some(execute_polygon_contains_polygon(&request.arguments))
+            }
             _ => None,
         }
     }
 }
 
 #[derive(Debug)]
-struct Point {
+pub(crate) struct Point {
     lat: f64,
     lon: f64,
 }
@@ -128,7 +317,275 @@ fn execute_point_in_polygon(arguments: &Option<String>) -> CallToolResult {
     success_result(result.to_string())
 }
 
-fn ray_casting_algorithm(point: &Point, polygon: &[Point]) -> bool {
+fn execute_polygon_area_delta(arguments: &Option<String>) -> CallToolResult {
+    let polygon_a = match parse_valid_polygon(arguments, "polygon_a") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let polygon_b = match parse_valid_polygon(arguments, "polygon_b") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    // The area formula is meaningless for self-intersecting rings
+    for (name, polygon) in [("polygon_a", &polygon_a), ("polygon_b", &polygon_b)] {
+        if let Some((i, j)) = find_self_intersection(polygon) {
+            return error_result(format!(
+                "{}: polygon is self-intersecting (edges {} and {} cross)",
+                name, i, j
+            ));
+        }
+    }
+
+    let area_a = spherical_polygon_area(&polygon_a);
+    let area_b = spherical_polygon_area(&polygon_b);
+    let difference = area_b - area_a;
+
+    // Percent change is undefined when the original polygon encloses no area
+    let percent_change = if area_a > 0.0 {
+        Some(difference / area_a * 100.0)
+    } else {
+        None
+    };
+
+    structured_result(serde_json::json!({
+        "area_a_km2": area_a,
+        "area_b_km2": area_b,
+        "difference_km2": difference,
+        "absolute_difference_km2": difference.abs(),
+        "percent_change": percent_change
+    }))
+}
+
+fn execute_is_simple_polygon(arguments: &Option<String>) -> CallToolResult {
+    let polygon = match parse_valid_polygon(arguments, "polygon") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let crossing = find_self_intersection(&polygon);
+
+    structured_result(serde_json::json!({
+        "is_simple": crossing.is_none(),
+        "vertex_count": polygon.len(),
+        "crossing_edges": crossing.map(|(i, j)| [i, j])
+    }))
+}
+
+fn execute_smooth_polygon(arguments: &Option<String>) -> CallToolResult {
+    let mut polygon = match parse_valid_polygon(arguments, "polygon") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = parse_smoothing_method(arguments) {
+        return error_result(msg);
+    }
+
+    let iterations = match parse_iterations(arguments) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    // A closing vertex that repeats the first would produce a degenerate edge
+    let n = polygon.len();
+    if n > 3
+        && (polygon[0].lat - polygon[n - 1].lat).abs() < EPSILON
+        && (polygon[0].lon - polygon[n - 1].lon).abs() < EPSILON
+    {
+        polygon.pop();
+    }
+
+    let original_vertex_count = polygon.len();
+    let fits = (0..iterations)
+        .try_fold(original_vertex_count, |count, _| {
+            count.checked_mul(2).filter(|&c| c <= MAX_SMOOTHED_VERTICES)
+        })
+        .is_some();
+    if !fits {
+        return error_result(format!(
+            "Too many iterations: the smoothed polygon would exceed {} vertices",
+            MAX_SMOOTHED_VERTICES
+        ));
+    }
+
+    for _ in 0..iterations {
+        polygon = chaikin_pass(&polygon);
+    }
+
+    let vertices: Vec<serde_json::Value> = polygon
+        .iter()
+        .map(|p| serde_json::json!({"lat": p.lat, "lon": p.lon}))
+        .collect();
+
+    structured_result(serde_json::json!({
+        "method": "chaikin",
+        "iterations": iterations,
+        "original_vertex_count": original_vertex_count,
+        "vertex_count": polygon.len(),
+        "polygon": vertices
+    }))
+}
+
+fn execute_polygon_contains_polygon(arguments: &Option<String>) -> CallToolResult {
+    let outer = match parse_simple_polygon(arguments, "outer") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let inner = match parse_simple_polygon(arguments, "inner") {
+        Ok(p) => p,
+        Err(msg) => return error_result(msg),
+    };
+
+    let outside_vertex = find_vertex_outside(&outer, &inner).map(|i| {
+        serde_json::json!({
+            "index": i,
+            "lat": inner[i].lat,
+            "lon": inner[i].lon,
+            "on_boundary": is_on_boundary(&inner[i], &outer)
+        })
+    });
+    let crossing = find_edge_crossing(&outer, &inner);
+
+    structured_result(serde_json::json!({
+        "contains": outside_vertex.is_none() && crossing.is_none(),
+        "outer_vertex_count": outer.len(),
+        "inner_vertex_count": inner.len(),
+        "outside_vertex": outside_vertex,
+        "crossing_edges": crossing.map(|(i, j)| serde_json::json!({"inner_edge": i, "outer_edge": j}))
+    }))
+}
+
+/// One round of Chaikin corner cutting on a closed ring: every edge is replaced by
+/// the points one quarter and three quarters of the way along it
+pub(crate) fn chaikin_pass(polygon: &[Point]) -> Vec<Point> {
+    let n = polygon.len();
+    let mut smoothed = Vec::with_capacity(2 * n);
+
+    for i in 0..n {
+        let a = &polygon[i];
+        let b = &polygon[(i + 1) % n];
+        smoothed.push(Point {
+            lat: 0.75 * a.lat + 0.25 * b.lat,
+            lon: 0.75 * a.lon + 0.25 * b.lon,
+        });
+        smoothed.push(Point {
+            lat: 0.25 * a.lat + 0.75 * b.lat,
+            lon: 0.25 * a.lon + 0.75 * b.lon,
+        });
+    }
+
+    smoothed
+}
+
+/// First pair of non-adjacent edges that touch or cross, as edge indices
+/// (edge i runs from vertex i to vertex i + 1, wrapping around). A closing
+/// vertex that repeats the first one is ignored.
+pub(crate) fn find_self_intersection(polygon: &[Point]) -> Option<(usize, usize)> {
+    let mut n = polygon.len();
+    if n > 3
+        && (polygon[0].lat - polygon[n - 1].lat).abs() < EPSILON
+        && (polygon[0].lon - polygon[n - 1].lon).abs() < EPSILON
+    {
+        n -= 1;
+    }
+
+    for i in 0..n {
+        for j in (i + 2)..n {
+            // The first and last edges share vertex 0
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let (a1, a2) = (&polygon[i], &polygon[(i + 1) % n]);
+            let (b1, b2) = (&polygon[j], &polygon[(j + 1) % n]);
+            if segments_intersect(a1, a2, b1, b2) {
+                return Some((i, j));
+            }
+        }
+    }
+
+    None
+}
+
+/// Index of the first vertex of `inner` that is not strictly inside `outer`;
+/// a vertex on the boundary counts as outside
+pub(crate) fn find_vertex_outside(outer: &[Point], inner: &[Point]) -> Option<usize> {
+    inner
+        .iter()
+        .position(|p| is_on_boundary(p, outer) || !ray_casting_algorithm(p, outer))
+}
+
+/// First pair of edges, as (inner edge, outer edge) indices, that touch or cross.
+/// Edge i runs from vertex i to vertex i + 1, wrapping around.
+pub(crate) fn find_edge_crossing(outer: &[Point], inner: &[Point]) -> Option<(usize, usize)> {
+    let (n, m) = (inner.len(), outer.len());
+
+    for i in 0..n {
+        let (a1, a2) = (&inner[i], &inner[(i + 1) % n]);
+        for j in 0..m {
+            let (b1, b2) = (&outer[j], &outer[(j + 1) % m]);
+            if segments_intersect(a1, a2, b1, b2) {
+                return Some((i, j));
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether segments a1-a2 and b1-b2 share any point, including touching endpoints
+/// and collinear overlap
+pub(crate) fn segments_intersect(a1: &Point, a2: &Point, b1: &Point, b2: &Point) -> bool {
+    let d1 = orientation(b1, b2, a1);
+    let d2 = orientation(b1, b2, a2);
+    let d3 = orientation(a1, a2, b1);
+    let d4 = orientation(a1, a2, b2);
+
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+
+    (d1 == 0 && is_point_on_segment(a1, b1, b2))
+        || (d2 == 0 && is_point_on_segment(a2, b1, b2))
+        || (d3 == 0 && is_point_on_segment(b1, a1, a2))
+        || (d4 == 0 && is_point_on_segment(b2, a1, a2))
+}
+
+/// Sign of the turn o -> a -> b: 1 counter-clockwise, -1 clockwise, 0 collinear
+pub(crate) fn orientation(o: &Point, a: &Point, b: &Point) -> i32 {
+    let cross = (a.lon - o.lon) * (b.lat - o.lat) - (a.lat - o.lat) * (b.lon - o.lon);
+    if cross.abs() <= EPSILON {
+        0
+    } else if cross > 0.0 {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Area of a polygon on a spherical Earth in km², using the
+/// line-integral formula Σ (λ2 - λ1)(2 + sin φ1 + sin φ2) · R² / 2
+pub(crate) fn spherical_polygon_area(polygon: &[Point]) -> f64 {
+    let n = polygon.len();
+    let mut total = 0.0;
+
+    for i in 0..n {
+        let p1 = &polygon[i];
+        let p2 = &polygon[(i + 1) % n];
+
+        let delta_lon = (p2.lon - p1.lon) * PI / 180.0;
+        let lat1_rad = p1.lat * PI / 180.0;
+        let lat2_rad = p2.lat * PI / 180.0;
+
+        total += delta_lon * (2.0 + lat1_rad.sin() + lat2_rad.sin());
+    }
+
+    (total * EARTH_RADIUS_KM * EARTH_RADIUS_KM / 2.0).abs()
+}
+
+pub(crate) fn ray_casting_algorithm(point: &Point, polygon: &[Point]) -> bool {
     if polygon.len() < 3 {
         return false;
     }
@@ -154,7 +611,7 @@ fn ray_casting_algorithm(point: &Point, polygon: &[Point]) -> bool {
     inside
 }
 
-fn is_on_boundary(point: &Point, polygon: &[Point]) -> bool {
+pub(crate) fn is_on_boundary(point: &Point, polygon: &[Point]) -> bool {
     if polygon.len() < 3 {
         return false;
     }
@@ -171,7 +628,7 @@ fn is_on_boundary(point: &Point, polygon: &[Point]) -> bool {
     false
 }
 
-fn is_point_on_segment(point: &Point, seg_start: &Point, seg_end: &Point) -> bool {
+pub(crate) fn is_point_on_segment(point: &Point, seg_start: &Point, seg_end: &Point) -> bool {
     let cross_product = (point.lat - seg_start.lat) * (seg_end.lon - seg_start.lon)
         - (point.lon - seg_start.lon) * (seg_end.lat - seg_start.lat);
 
@@ -188,7 +645,7 @@ fn is_point_on_segment(point: &Point, seg_start: &Point, seg_end: &Point) -> boo
     dot_product >= 0.0 && dot_product <= squared_length
 }
 
-fn validate_point(point: &Point) -> Result<(), String> {
+pub(crate) fn validate_point(point: &Point) -> Result<(), String> {
     if point.lat.is_nan() || point.lat.is_infinite() {
         return Err("Latitude cannot be NaN or infinite".to_string());
     }
@@ -264,6 +721,113 @@ fn parse_point_in_polygon_args(
     Ok((point, polygon))
 }
 
+/// Parse the polygon under `name` and check it has at least 3 valid vertices
+fn parse_valid_polygon(arguments: &Option<String>, name: &str) -> Result<Vec<Point>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let polygon_arr = json
+        .get(name)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| format!("Missing or invalid '{}' parameter", name))?;
+
+    let mut polygon = Vec::new();
+    for (i, vertex) in polygon_arr.iter().enumerate() {
+        let lat = vertex
+            .get("lat")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid '{}[{}].lat'", name, i))?;
+
+        let lon = vertex
+            .get("lon")
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| format!("Missing or invalid '{}[{}].lon'", name, i))?;
+
+        let point = Point { lat, lon };
+        validate_point(&point).map_err(|msg| format!("{} vertex {}: {}", name, i, msg))?;
+        polygon.push(point);
+    }
+
+    if polygon.len() < 3 {
+        return Err(format!("'{}' must have at least 3 vertices", name));
+    }
+
+    Ok(polygon)
+}
+
+/// Parse the polygon under `name` and check that it does not intersect itself
+fn parse_simple_polygon(arguments: &Option<String>, name: &str) -> Result<Vec<Point>, String> {
+    let polygon = parse_valid_polygon(arguments, name)?;
+
+    match find_self_intersection(&polygon) {
+        Some((i, j)) => Err(format!(
+            "'{}' is self-intersecting: edges {} and {} cross",
+            name, i, j
+        )),
+        None => Ok(polygon),
+    }
+}
+
+/// Parse the optional smoothing `method`; only "chaikin" is supported
+fn parse_smoothing_method(arguments: &Option<String>) -> Result<(), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("method") {
+        None | Some(serde_json::Value::Null) => Ok(()),
+        Some(v) if v.as_str() == Some("chaikin") => Ok(()),
+        Some(v) => Err(format!(
+            "Invalid 'method' parameter: {}. Must be \"chaikin\"",
+            v
+        )),
+    }
+}
+
+/// Parse the optional `iterations` count (default 1), requiring at least 1
+fn parse_iterations(arguments: &Option<String>) -> Result<u32, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("iterations") {
+        None | Some(serde_json::Value::Null) => Ok(1),
+        Some(v) => v
+            .as_u64()
+            .filter(|&n| n >= 1)
+            .map(|n| n.min(u32::MAX as u64) as u32)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid 'iterations' parameter: {}. Must be an integer >= 1",
+                    v
+                )
+            }),
+    }
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
 fn success_result(result: String) -> CallToolResult {
     CallToolResult {
         content: vec![ContentBlock::Text(TextContent {
@@ -289,3 +853,138 @@ fn error_result(message: String) -> CallToolResult {
 }
 
 bindings::export!(GeospatialPointInPolygon with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(lat: f64, lon: f64) -> Point {
+        Point { lat, lon }
+    }
+
+    fn unit_square() -> Vec<Point> {
+        vec![p(0.0, 0.0), p(0.0, 1.0), p(1.0, 1.0), p(1.0, 0.0)]
+    }
+
+    #[test]
+    fn ray_casting_inside_and_outside() {
+        let square = unit_square();
+        assert!(ray_casting_algorithm(&p(0.5, 0.5), &square));
+        assert!(!ray_casting_algorithm(&p(1.5, 0.5), &square));
+        assert!(!ray_casting_algorithm(&p(0.5, -0.1), &square));
+    }
+
+    #[test]
+    fn ray_casting_needs_three_vertices() {
+        assert!(!ray_casting_algorithm(
+            &p(0.0, 0.0),
+            &[p(0.0, 0.0), p(1.0, 1.0)]
+        ));
+    }
+
+    #[test]
+    fn boundary_includes_edges_and_vertices() {
+        let square = unit_square();
+        assert!(is_on_boundary(&p(0.0, 0.5), &square));
+        assert!(is_on_boundary(&p(1.0, 1.0), &square));
+        assert!(!is_on_boundary(&p(0.5, 0.5), &square));
+    }
+
+    #[test]
+    fn point_on_segment_excludes_extension() {
+        assert!(is_point_on_segment(
+            &p(0.5, 0.5),
+            &p(0.0, 0.0),
+            &p(1.0, 1.0)
+        ));
+        assert!(!is_point_on_segment(
+            &p(2.0, 2.0),
+            &p(0.0, 0.0),
+            &p(1.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn bowtie_is_self_intersecting() {
+        let bowtie = vec![p(0.0, 0.0), p(1.0, 1.0), p(1.0, 0.0), p(0.0, 1.0)];
+        assert_eq!(find_self_intersection(&bowtie), Some((0, 2)));
+        assert_eq!(find_self_intersection(&unit_square()), None);
+    }
+
+    #[test]
+    fn closing_vertex_is_not_an_intersection() {
+        let mut closed = unit_square();
+        closed.push(p(0.0, 0.0));
+        assert_eq!(find_self_intersection(&closed), None);
+    }
+
+    #[test]
+    fn one_degree_square_at_equator_area() {
+        // About 111.2 km on each side
+        let area = spherical_polygon_area(&unit_square());
+        assert!((area - 12_364.0).abs() < 10.0, "got {}", area);
+    }
+
+    #[test]
+    fn validate_point_rejects_out_of_range() {
+        assert!(validate_point(&p(95.0, 0.0)).is_err());
+        assert!(validate_point(&p(0.0, f64::INFINITY)).is_err());
+        assert!(validate_point(&p(45.0, 90.0)).is_ok());
+    }
+
+    #[test]
+    fn chaikin_cuts_each_corner() {
+        let smoothed = chaikin_pass(&unit_square());
+        assert_eq!(smoothed.len(), 8);
+        assert!(
+            (smoothed[0].lat - 0.0).abs() < EPSILON && (smoothed[0].lon - 0.25).abs() < EPSILON
+        );
+        assert!(
+            (smoothed[1].lat - 0.0).abs() < EPSILON && (smoothed[1].lon - 0.75).abs() < EPSILON
+        );
+        // The last edge wraps from the final vertex back to the first
+        assert!((smoothed[7].lat - 0.25).abs() < EPSILON && smoothed[7].lon.abs() < EPSILON);
+    }
+
+    #[test]
+    fn smooth_polygon_validates_iterations() {
+        let square = r#"[{"lat": 0, "lon": 0}, {"lat": 0, "lon": 1}, {"lat": 1, "lon": 1}]"#;
+        for extra in [
+            r#""iterations": 0"#,
+            r#""iterations": 40"#,
+            r#""method": "bezier""#,
+        ] {
+            let json = format!(r#"{{"polygon": {}, {}}}"#, square, extra);
+            let result = execute_smooth_polygon(&Some(json));
+            assert_eq!(result.is_error, Some(true), "{}", extra);
+        }
+    }
+
+    #[test]
+    fn nested_square_is_contained() {
+        let inner = vec![p(0.25, 0.25), p(0.25, 0.75), p(0.75, 0.75), p(0.75, 0.25)];
+        assert_eq!(find_vertex_outside(&unit_square(), &inner), None);
+        assert_eq!(find_edge_crossing(&unit_square(), &inner), None);
+    }
+
+    #[test]
+    fn overlapping_square_reports_violations() {
+        let inner = vec![p(0.5, 0.5), p(0.5, 1.5), p(1.5, 1.5), p(1.5, 0.5)];
+        assert_eq!(find_vertex_outside(&unit_square(), &inner), Some(1));
+        assert!(find_edge_crossing(&unit_square(), &inner).is_some());
+
+        // A vertex touching the outer boundary is not strictly inside
+        let touching = vec![p(0.0, 0.5), p(0.5, 0.75), p(0.5, 0.25)];
+        assert_eq!(find_vertex_outside(&unit_square(), &touching), Some(0));
+    }
+
+    #[test]
+    fn polygon_contains_polygon_rejects_self_intersecting_input() {
+        let json = r#"{
+            "outer": [{"lat": 0, "lon": 0}, {"lat": 1, "lon": 1}, {"lat": 0, "lon": 1}, {"lat": 1, "lon": 0}],
+            "inner": [{"lat": 0.2, "lon": 0.2}, {"lat": 0.2, "lon": 0.4}, {"lat": 0.4, "lon": 0.3}]
+        }"#;
+        let result = execute_polygon_contains_polygon(&Some(json.to_string()));
+        assert_eq!(result.is_error, Some(true));
+    }
+}
@@ -0,0 +1,329 @@
+//! Geospatial Time Tool
+//!
+//! Rough time-related estimates derived purely from coordinates.
+//! These are heuristics for scheduling and planning, not authoritative time-zone data.
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "geospatial-time",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+use std::f64::consts::PI;
+
+struct GeospatialTime;
+
+/// Width of a nautical time zone in degrees of longitude
+const DEGREES_PER_HOUR: f64 = 15.0;
+
+/// Axial tilt of the Earth in degrees, the amplitude of the solar declination
+const AXIAL_TILT_DEG: f64 = 23.44;
+
+impl Guest for GeospatialTime {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        _request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        Ok(ListToolsResult {
+            tools: vec![
+                Tool {
+                    name: "longitude_to_timezone_offset".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lon": {"type": "number", "minimum": -180, "maximum": 180, "description": "Longitude in degrees"}
+                        },
+                        "required": ["lon"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Approximate UTC offset for a longitude using nautical time zones (round(lon / 15) hours). \
+                             This is an approximation that ignores political boundaries and daylight saving time."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Longitude to Time Zone Offset".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "daylight_hours".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "lat": {"type": "number", "minimum": -90, "maximum": 90, "description": "Latitude in degrees"},
+                            "day_of_year": {"type": "integer", "minimum": 1, "maximum": 365, "description": "Day of the year (1 = January 1st)"}
+                        },
+                        "required": ["lat", "day_of_year"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Approximate daylight length for a latitude and day of year from the solar declination \
+                             and sunrise hour angle. Returns 24 or 0 hours during polar day or polar night."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Daylight Hours".to_string()),
+                    }),
+                },
+            ],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "longitude_to_timezone_offset" => Some(execute_timezone_offset(&request.arguments)),
+            "daylight_hours" => Some(execute_daylight_hours(&request.arguments)),
+            _ => None,
+        }
+    }
+}
+
+fn execute_timezone_offset(arguments: &Option<String>) -> CallToolResult {
+    let lon = match parse_number(arguments, "lon") {
+        Ok(lon) => lon,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_longitude(lon) {
+        return error_result(msg);
+    }
+
+    let offset_hours = nautical_offset_hours(lon);
+
+    structured_result(serde_json::json!({
+        "lon": lon,
+        "offset_hours": offset_hours,
+        "utc_offset": format_utc_offset(offset_hours),
+        "approximate": true
+    }))
+}
+
+/// Nautical time-zone offset: each 15° band centred on a multiple of 15° is one hour
+pub(crate) fn nautical_offset_hours(lon: f64) -> i64 {
+    (lon / DEGREES_PER_HOUR).round() as i64
+}
+
+pub(crate) fn format_utc_offset(offset_hours: i64) -> String {
+    match offset_hours {
+        0 => "UTC".to_string(),
+        h if h > 0 => format!("UTC+{}", h),
+        h => format!("UTC{}", h),
+    }
+}
+
+fn execute_daylight_hours(arguments: &Option<String>) -> CallToolResult {
+    let lat = match parse_number(arguments, "lat") {
+        Ok(lat) => lat,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = validate_latitude(lat) {
+        return error_result(msg);
+    }
+
+    let day_of_year = match parse_day_of_year(arguments) {
+        Ok(day) => day,
+        Err(msg) => return error_result(msg),
+    };
+
+    let declination = solar_declination_deg(day_of_year);
+    let cos_hour_angle = -(lat * PI / 180.0).tan() * (declination * PI / 180.0).tan();
+
+    // Outside [-1, 1] the sun never crosses the horizon that day
+    let (daylight_hours, hour_angle, condition) = if cos_hour_angle < -1.0 {
+        (24.0, None, "polar_day")
+    } else if cos_hour_angle > 1.0 {
+        (0.0, None, "polar_night")
+    } else {
+        let hour_angle_deg = cos_hour_angle.acos() * 180.0 / PI;
+        (
+            2.0 * hour_angle_deg / DEGREES_PER_HOUR,
+            Some(hour_angle_deg),
+            "normal",
+        )
+    };
+
+    structured_result(serde_json::json!({
+        "lat": lat,
+        "day_of_year": day_of_year,
+        "declination_degrees": declination,
+        "sunrise_hour_angle_degrees": hour_angle,
+        "daylight_hours": daylight_hours,
+        "condition": condition
+    }))
+}
+
+/// Solar declination in degrees using Cooper's approximation
+/// δ = 23.44° · sin(360° · (284 + N) / 365)
+pub(crate) fn solar_declination_deg(day_of_year: u32) -> f64 {
+    AXIAL_TILT_DEG * (2.0 * PI * (284.0 + day_of_year as f64) / 365.0).sin()
+}
+
+pub(crate) fn validate_latitude(lat: f64) -> Result<(), String> {
+    if lat.is_nan() || lat.is_infinite() {
+        return Err("Latitude cannot be NaN or infinite".to_string());
+    }
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!(
+            "Invalid latitude: {}. Must be between -90 and 90",
+            lat
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_longitude(lon: f64) -> Result<(), String> {
+    if lon.is_nan() || lon.is_infinite() {
+        return Err("Longitude cannot be NaN or infinite".to_string());
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return Err(format!(
+            "Invalid longitude: {}. Must be between -180 and 180",
+            lon
+        ));
+    }
+    Ok(())
+}
+
+fn parse_number(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    json.get(name)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+}
+
+fn parse_day_of_year(arguments: &Option<String>) -> Result<u32, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let day = json
+        .get("day_of_year")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'day_of_year'".to_string())?;
+
+    if !(1..=365).contains(&day) {
+        return Err(format!(
+            "Invalid day_of_year: {}. Must be between 1 and 365",
+            day
+        ));
+    }
+
+    Ok(day as u32)
+}
+
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let text = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(text),
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(GeospatialTime with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn structured(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(
+            result
+                .structured_content
+                .as_deref()
+                .expect("structured content"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn nautical_offsets() {
+        assert_eq!(nautical_offset_hours(0.0), 0);
+        assert_eq!(nautical_offset_hours(7.4), 0);
+        assert_eq!(nautical_offset_hours(7.6), 1);
+        assert_eq!(nautical_offset_hours(-74.0), -5);
+        assert_eq!(nautical_offset_hours(180.0), 12);
+    }
+
+    #[test]
+    fn utc_offset_labels() {
+        assert_eq!(format_utc_offset(0), "UTC");
+        assert_eq!(format_utc_offset(9), "UTC+9");
+        assert_eq!(format_utc_offset(-5), "UTC-5");
+    }
+
+    #[test]
+    fn declination_peaks_near_solstices() {
+        // Day 172 is around June 21st, day 355 around December 21st
+        assert!((solar_declination_deg(172) - AXIAL_TILT_DEG).abs() < 0.1);
+        assert!((solar_declination_deg(355) + AXIAL_TILT_DEG).abs() < 0.1);
+    }
+
+    #[test]
+    fn equator_has_about_twelve_hours_of_daylight() {
+        let result = execute_daylight_hours(&args(r#"{"lat": 0, "day_of_year": 100}"#));
+        let hours = structured(&result)["daylight_hours"].as_f64().unwrap();
+        assert!((hours - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn arctic_summer_is_polar_day() {
+        let result = execute_daylight_hours(&args(r#"{"lat": 80, "day_of_year": 172}"#));
+        assert_eq!(structured(&result)["condition"], "polar_day");
+    }
+
+    #[test]
+    fn validation_ranges() {
+        assert!(validate_latitude(90.5).is_err());
+        assert!(validate_longitude(-180.5).is_err());
+        assert!(validate_longitude(f64::INFINITY).is_err());
+        assert!(validate_latitude(-90.0).is_ok());
+    }
+}
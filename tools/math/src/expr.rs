@@ -0,0 +1,322 @@
+//! Arithmetic expression evaluation for the `evaluate` tool
+//!
+//! Recursive-descent evaluator for a restricted grammar, lowest precedence first:
+//!
+//! ```text
+//! expr    := term (("+" | "-") term)*
+//! term    := unary (("*" | "/") unary)*
+//! unary   := ("+" | "-") unary | power
+//! power   := primary ("^" unary)?
+//! primary := number | variable | "(" expr ")"
+//! ```
+//!
+//! `^` is right-associative and binds tighter than unary minus, so `-2^2` is -4
+//! and `2^3^2` is 512. Numbers may use a decimal point and an exponent (`1.5e3`).
+
+use std::collections::HashMap;
+
+/// Upper bound on the length of an expression, in bytes
+pub(crate) const MAX_EXPRESSION_LENGTH: usize = 10_000;
+
+/// Upper bound on nesting (parentheses and chained unary signs), keeping
+/// recursion well clear of the stack limit
+pub(crate) const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Variable(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LeftParen,
+    RightParen,
+}
+
+/// Evaluate `expression`, resolving names from `variables`
+pub(crate) fn evaluate(expression: &str, variables: &HashMap<String, f64>) -> Result<f64, String> {
+    if expression.len() > MAX_EXPRESSION_LENGTH {
+        return Err(format!(
+            "Error: expression is longer than {} bytes",
+            MAX_EXPRESSION_LENGTH
+        ));
+    }
+
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err("Error: expression is empty".to_string());
+    }
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        depth: 0,
+        variables,
+    };
+    let value = parser.expr()?;
+
+    match parser.peek() {
+        None => Ok(value),
+        Some(token) => Err(format!(
+            "Error: unexpected {} after expression",
+            describe(token)
+        )),
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '^' => Token::Caret,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // Exponent only when followed by digits, so `2e` is not swallowed
+                if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let value = literal
+                    .parse::<f64>()
+                    .map_err(|_| format!("Error: invalid number '{}'", literal))?;
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Variable(chars[start..i].iter().collect()));
+                continue;
+            }
+            other => {
+                return Err(format!(
+                    "Error: unexpected character '{}' at position {}",
+                    other, i
+                ));
+            }
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    depth: usize,
+    variables: &'a HashMap<String, f64>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!(
+                "Error: expression is nested more than {} levels deep",
+                MAX_DEPTH
+            ));
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.pos += 1;
+                    value += self.term()?;
+                }
+                Some(Token::Minus) => {
+                    self.pos += 1;
+                    value -= self.term()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Star) => {
+                    self.pos += 1;
+                    value *= self.unary()?;
+                }
+                Some(Token::Slash) => {
+                    self.pos += 1;
+                    let divisor = self.unary()?;
+                    if divisor == 0.0 {
+                        return Err("Error: Division by zero".to_string());
+                    }
+                    value /= divisor;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<f64, String> {
+        let sign = match self.peek() {
+            Some(Token::Minus) => -1.0,
+            Some(Token::Plus) => 1.0,
+            _ => return self.power(),
+        };
+        self.pos += 1;
+        self.descend()?;
+        let value = self.unary()?;
+        self.depth -= 1;
+        Ok(sign * value)
+    }
+
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.primary()?;
+        if self.peek() != Some(&Token::Caret) {
+            return Ok(base);
+        }
+        self.pos += 1;
+        self.descend()?;
+        let exponent = self.unary()?;
+        self.depth -= 1;
+        Ok(base.powf(exponent))
+    }
+
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.next().cloned() {
+            Some(Token::Number(value)) => Ok(value),
+            Some(Token::Variable(name)) => self
+                .variables
+                .get(&name)
+                .copied()
+                .ok_or_else(|| format!("Error: unknown variable '{}'", name)),
+            Some(Token::LeftParen) => {
+                self.descend()?;
+                let value = self.expr()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Token::RightParen) => Ok(value),
+                    Some(token) => {
+                        Err(format!("Error: expected ')' but found {}", describe(token)))
+                    }
+                    None => Err("Error: missing closing ')'".to_string()),
+                }
+            }
+            Some(token) => Err(format!("Error: unexpected {}", describe(&token))),
+            None => Err("Error: unexpected end of expression".to_string()),
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(value) => format!("number {}", value),
+        Token::Variable(name) => format!("variable '{}'", name),
+        Token::Plus => "'+'".to_string(),
+        Token::Minus => "'-'".to_string(),
+        Token::Star => "'*'".to_string(),
+        Token::Slash => "'/'".to_string(),
+        Token::Caret => "'^'".to_string(),
+        Token::LeftParen => "'('".to_string(),
+        Token::RightParen => "')'".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str) -> Result<f64, String> {
+        evaluate(expression, &HashMap::new())
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(eval("2 ^ -1"), Ok(0.5));
+        assert_eq!(eval("1.5e3 / 3"), Ok(500.0));
+    }
+
+    #[test]
+    fn variables_are_resolved() {
+        let variables = HashMap::from([("a".to_string(), 2.0), ("b".to_string(), 3.0)]);
+        assert_eq!(evaluate("(a + b) * b", &variables), Ok(15.0));
+        assert_eq!(
+            evaluate("(a + b) * c_1", &variables),
+            Err("Error: unknown variable 'c_1'".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        assert!(eval("").is_err());
+        assert!(eval("1 +").is_err());
+        assert!(eval("(1 + 2").is_err());
+        assert!(eval("1 2").is_err());
+        assert!(eval("2 % 3").is_err());
+        assert_eq!(
+            eval("1 / (2 - 2)"),
+            Err("Error: Division by zero".to_string())
+        );
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let nested = format!(
+            "{}1{}",
+            "(".repeat(MAX_DEPTH + 1),
+            ")".repeat(MAX_DEPTH + 1)
+        );
+        assert!(eval(&nested).is_err());
+        assert!(eval(&"-".repeat(10 * MAX_DEPTH)).is_err());
+
+        let shallow = format!("{}1{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(eval(&shallow), Ok(1.0));
+    }
+}
@@ -2,7 +2,23 @@
 //!
 //! A tools capability that provides comprehensive mathematical operations including:
 //! - Basic arithmetic: add, subtract, multiply, divide
+//! - Extremes: min, max, clamp, max_of, min_of
 //! - Advanced operations: square, square_root, power
+//! - Rounding and sign: abs, sign, floor, ceil, round, round_sig_figs, trunc
+//! - Logarithms: log, log10, log2, ln
+//! - Trigonometry: sin, cos, tan, asin, acos, atan, atan2
+//! - Linear algebra: solve_2x2
+//! - Polynomials: evaluate_polynomial, polynomial_root
+//! - Root finding: bisection
+//! - Iterative means: agm
+//! - Rational approximation: continued_fraction, decimal_to_fraction
+//! - Notation: scientific_notation, engineering_notation
+//! - Integers: gcd, lcm, simplify_fraction
+//! - Combinatorics: factorial, combinations, permutations
+//! - Calculus: derivative_at
+//! - Expressions: evaluate
+//! - Vectors: linear_combination, norm, cosine_similarity, vector_distance
+//! - Tolerances: within_tolerance
 
 mod bindings {
     wit_bindgen::generate!({
@@ -11,12 +27,53 @@ mod bindings {
     });
 }
 
+mod expr;
+
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use std::collections::HashMap;
+use std::f64::consts::PI;
 
 struct Math;
 
+/// Tolerance below which a value is treated as zero
+const EPSILON: f64 = 1e-12;
+
+/// Defaults and limits for iterative numerical methods
+const DEFAULT_TOLERANCE: f64 = 1e-12;
+const DEFAULT_MAX_ITERATIONS: u32 = 100;
+const MAX_ITERATIONS_CAP: u32 = 10_000;
+
+/// Upper bound on continued-fraction terms; f64 precision runs out well before this
+const MAX_CONTINUED_FRACTION_TERMS: u64 = 64;
+
+/// |cos θ| below which tan θ is treated as undefined
+const TAN_ASYMPTOTE_TOLERANCE: f64 = 1e-12;
+
+/// Display precision for the fixed and percent formats
+const DEFAULT_DECIMALS: usize = 2;
+const MAX_DECIMALS: u64 = 20;
+
+/// f64 carries about 15 significant decimal digits, so finer rounding is meaningless
+const MAX_ROUND_DECIMALS: u32 = 15;
+
+/// 17 significant digits identify any f64 exactly, so more cannot change the value
+const MAX_SIG_FIGS: u32 = 17;
+
+/// SI prefixes from quecto (10⁻³⁰) to quetta (10³⁰), one per power of 1000
+const SI_PREFIXES: [&str; 21] = [
+    "q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+    "R", "Q",
+];
+const SI_MIN_EXPONENT: i32 = -30;
+
+/// Largest magnitude below which every integer is exactly representable as f64 (2⁵³)
+const MAX_SAFE_INTEGER: i64 = 1 << 53;
+
+/// 171! exceeds f64::MAX
+const MAX_FACTORIAL: i64 = 170;
+
 impl Guest for Math {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
@@ -31,7 +88,9 @@ impl Guest for Math {
                         "type": "object",
                         "properties": {
                             "a": {"type": "number", "description": "First number"},
-                            "b": {"type": "number", "description": "Second number"}
+                            "b": {"type": "number", "description": "Second number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["a", "b"]
                     }"#
@@ -40,7 +99,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Add two numbers together".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("Sum a + b")),
                         title: Some("Add".to_string()),
                     }),
                 },
@@ -50,7 +109,9 @@ impl Guest for Math {
                         "type": "object",
                         "properties": {
                             "a": {"type": "number", "description": "Number to subtract from"},
-                            "b": {"type": "number", "description": "Number to subtract"}
+                            "b": {"type": "number", "description": "Number to subtract"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["a", "b"]
                     }"#
@@ -59,7 +120,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Subtract b from a".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("Difference a - b")),
                         title: Some("Subtract".to_string()),
                     }),
                 },
@@ -69,7 +130,9 @@ impl Guest for Math {
                         "type": "object",
                         "properties": {
                             "a": {"type": "number", "description": "First number"},
-                            "b": {"type": "number", "description": "Second number"}
+                            "b": {"type": "number", "description": "Second number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["a", "b"]
                     }"#
@@ -78,7 +141,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Multiply two numbers".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("Product a × b")),
                         title: Some("Multiply".to_string()),
                     }),
                 },
@@ -88,7 +151,9 @@ impl Guest for Math {
                         "type": "object",
                         "properties": {
                             "a": {"type": "number", "description": "Dividend"},
-                            "b": {"type": "number", "description": "Divisor"}
+                            "b": {"type": "number", "description": "Divisor"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["a", "b"]
                     }"#
@@ -97,7 +162,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Divide a by b".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("Quotient a / b. Division by zero is reported as an error result instead")),
                         title: Some("Divide".to_string()),
                     }),
                 },
@@ -106,7 +171,9 @@ impl Guest for Math {
                     input_schema: r#"{
                         "type": "object",
                         "properties": {
-                            "x": {"type": "number", "description": "Number to square"}
+                            "x": {"type": "number", "description": "Number to square"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["x"]
                     }"#
@@ -115,7 +182,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Calculate the square of a number (x²)".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("x²")),
                         title: Some("Square".to_string()),
                     }),
                 },
@@ -124,7 +191,9 @@ impl Guest for Math {
                     input_schema: r#"{
                         "type": "object",
                         "properties": {
-                            "x": {"type": "number", "description": "Number to take square root of"}
+                            "x": {"type": "number", "description": "Number to take square root of"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["x"]
                     }"#
@@ -133,7 +202,7 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Calculate the square root of a number (√x)".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("√x. Negative x is reported as an error result instead")),
                         title: Some("Square Root".to_string()),
                     }),
                 },
@@ -143,7 +212,9 @@ impl Guest for Math {
                         "type": "object",
                         "properties": {
                             "base": {"type": "number", "description": "Base number"},
-                            "exponent": {"type": "number", "description": "Exponent"}
+                            "exponent": {"type": "number", "description": "Exponent"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
                         },
                         "required": ["base", "exponent"]
                     }"#
@@ -152,175 +223,3900 @@ impl Guest for Math {
                         meta: None,
                         annotations: None,
                         description: Some("Calculate base raised to exponent (base^exponent)".to_string()),
-                        output_schema: None,
+                        output_schema: Some(scalar_output_schema("base^exponent")),
                         title: Some("Power".to_string()),
                     }),
                 },
-            ],
-            next_cursor: None,
-            meta: None,
-        })
-    }
-
-    fn call_tool(
-        _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        request: CallToolRequest,
-        _client_stream: Option<&OutputStream>,
-    ) -> Option<CallToolResult> {
-        match request.name.as_str() {
-            "add" => Some(execute_operation(&request.arguments, |a, b| a + b)),
-            "subtract" => Some(execute_operation(&request.arguments, |a, b| a - b)),
-            "multiply" => Some(execute_operation(&request.arguments, |a, b| a * b)),
-            "divide" => Some(execute_divide(&request.arguments)),
-            "square" => Some(execute_square(&request.arguments)),
-            "square_root" => Some(execute_square_root(&request.arguments)),
-            "power" => Some(execute_power(&request.arguments)),
-            _ => None, // We don't handle this tool
-        }
-    }
-}
-
-fn execute_operation<F>(arguments: &Option<String>, op: F) -> CallToolResult
-where
-    F: FnOnce(f64, f64) -> f64,
-{
-    match parse_args(arguments) {
-        Ok((a, b)) => {
-            let result = op(a, b);
-            success_result(result.to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn execute_divide(arguments: &Option<String>) -> CallToolResult {
-    match parse_args(arguments) {
-        Ok((a, b)) => {
-            if b == 0.0 {
-                error_result("Error: Division by zero".to_string())
-            } else {
-                let result = a / b;
-                success_result(result.to_string())
-            }
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn parse_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let a = json
-        .get("a")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'a'".to_string())?;
-
-    let b = json
-        .get("b")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'b'".to_string())?;
-
-    Ok((a, b))
-}
-
-fn execute_square(arguments: &Option<String>) -> CallToolResult {
-    match parse_single_arg(arguments, "x") {
-        Ok(x) => {
-            let result = x * x;
-            success_result(result.to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn execute_square_root(arguments: &Option<String>) -> CallToolResult {
-    match parse_single_arg(arguments, "x") {
-        Ok(x) => {
-            if x < 0.0 {
-                error_result("Error: Cannot take square root of negative number".to_string())
-            } else {
-                let result = x.sqrt();
-                success_result(result.to_string())
-            }
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn execute_power(arguments: &Option<String>) -> CallToolResult {
-    match parse_power_args(arguments) {
-        Ok((base, exponent)) => {
-            let result = base.powf(exponent);
-            success_result(result.to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn parse_single_arg(arguments: &Option<String>, arg_name: &str) -> Result<f64, String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let value = json
-        .get(arg_name)
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| format!("Missing or invalid parameter '{}'", arg_name))?;
-
-    Ok(value)
-}
-
-fn parse_power_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let base = json
-        .get("base")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'base'".to_string())?;
-
-    let exponent = json
-        .get("exponent")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'exponent'".to_string())?;
-
-    Ok((base, exponent))
-}
-
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
-
-bindings::export!(Math with_types_in bindings);
+                Tool {
+                    name: "solve_2x2".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a1": {"type": "number", "description": "Coefficient of x in the first equation"},
+                            "b1": {"type": "number", "description": "Coefficient of y in the first equation"},
+                            "c1": {"type": "number", "description": "Right-hand side of the first equation"},
+                            "a2": {"type": "number", "description": "Coefficient of x in the second equation"},
+                            "b2": {"type": "number", "description": "Coefficient of y in the second equation"},
+                            "c2": {"type": "number", "description": "Right-hand side of the second equation"}
+                        },
+                        "required": ["a1", "b1", "c1", "a2", "b2", "c2"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Solve the linear system a1·x + b1·y = c1, a2·x + b2·y = c2 using Cramer's rule. Reports when there is no solution or infinitely many".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "status": {"type": "string", "enum": ["unique", "no_solution", "infinite_solutions"]},
+                                    "determinant": {"type": "number"},
+                                    "x": {"type": ["number", "null"], "description": "Null unless status is unique"},
+                                    "y": {"type": ["number", "null"], "description": "Null unless status is unique"}
+                                },
+                                "required": ["status", "determinant", "x", "y"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Solve 2x2 Linear System".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "evaluate_polynomial".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "coefficients": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Polynomial coefficients"
+                            },
+                            "order": {
+                                "type": "string",
+                                "enum": ["highest_first", "lowest_first"],
+                                "description": "Coefficient order (default: highest_first)"
+                            },
+                            "x": {"type": "number", "description": "Point at which to evaluate"}
+                        },
+                        "required": ["coefficients", "x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Evaluate a polynomial at x using Horner's method".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "result": {"type": "number"},
+                                    "x": {"type": "number"},
+                                    "degree": {"type": "integer"}
+                                },
+                                "required": ["result", "x", "degree"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Evaluate Polynomial".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "polynomial_root".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "coefficients": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 2,
+                                "description": "Polynomial coefficients"
+                            },
+                            "order": {
+                                "type": "string",
+                                "enum": ["highest_first", "lowest_first"],
+                                "description": "Coefficient order (default: highest_first)"
+                            },
+                            "guess": {"type": "number", "description": "Starting point for the iteration"},
+                            "tolerance": {"type": "number", "exclusiveMinimum": 0, "description": "Convergence tolerance on the step size (default: 1e-12)"},
+                            "max_iterations": {"type": "integer", "minimum": 1, "maximum": 10000, "description": "Iteration cap (default: 100)"}
+                        },
+                        "required": ["coefficients", "guess"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Find a real root of a polynomial near a guess using Newton-Raphson iteration".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "root": {"type": "number"},
+                                    "iterations": {"type": "integer"},
+                                    "value_at_root": {"type": "number"}
+                                },
+                                "required": ["root", "iterations", "value_at_root"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Polynomial Root".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "bisection".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "function": {
+                                "type": "string",
+                                "enum": ["sin", "cos", "exp", "ln", "polynomial"],
+                                "description": "Built-in function whose root to find"
+                            },
+                            "coefficients": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Polynomial coefficients (required when function is 'polynomial')"
+                            },
+                            "order": {
+                                "type": "string",
+                                "enum": ["highest_first", "lowest_first"],
+                                "description": "Coefficient order (default: highest_first)"
+                            },
+                            "a": {"type": "number", "description": "Lower end of the bracket"},
+                            "b": {"type": "number", "description": "Upper end of the bracket"},
+                            "tolerance": {"type": "number", "exclusiveMinimum": 0, "description": "Stop when the bracket is narrower than this (default: 1e-12)"},
+                            "max_iterations": {"type": "integer", "minimum": 1, "maximum": 10000, "description": "Iteration cap (default: 100)"}
+                        },
+                        "required": ["function", "a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Find a root of a built-in function within a bracket [a, b] by bisection. The function must change sign across the bracket".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "root": {"type": "number"},
+                                    "bracket_width": {"type": "number"},
+                                    "iterations": {"type": "integer"},
+                                    "value_at_root": {"type": "number"}
+                                },
+                                "required": ["root", "bracket_width", "iterations", "value_at_root"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Bisection Root Finder".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "derivative_at".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "function": {
+                                "type": "string",
+                                "enum": ["sin", "cos", "exp", "ln", "polynomial"],
+                                "description": "Built-in function to differentiate"
+                            },
+                            "coefficients": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Polynomial coefficients (required when function is 'polynomial')"
+                            },
+                            "order": {
+                                "type": "string",
+                                "enum": ["highest_first", "lowest_first"],
+                                "description": "Coefficient order (default: highest_first)"
+                            },
+                            "x": {"type": "number", "description": "Point at which to estimate f'(x)"}
+                        },
+                        "required": ["function", "x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Estimate the derivative of a built-in function at a point using a central difference".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "derivative": {"type": "number"},
+                                    "x": {"type": "number"},
+                                    "step": {"type": "number", "description": "Finite-difference step h"}
+                                },
+                                "required": ["derivative", "x", "step"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Derivative At Point".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "linear_combination".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "values": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Values x₁..xₙ"
+                            },
+                            "weights": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Weights w₁..wₙ, one per value"
+                            }
+                        },
+                        "required": ["values", "weights"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Compute the weighted sum Σ wᵢ·xᵢ (the dot product of values and weights). Both arrays must have the same length".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "result": {"type": "number"},
+                                    "count": {"type": "integer"}
+                                },
+                                "required": ["result", "count"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Linear Combination".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "norm".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "values": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Vector components"
+                            },
+                            "p": {
+                                "oneOf": [
+                                    {"type": "number", "enum": [1, 2]},
+                                    {"type": "string", "enum": ["inf"]}
+                                ],
+                                "description": "Norm order: 1 (Manhattan), 2 (Euclidean) or \"inf\" (maximum). Default: 2"
+                            }
+                        },
+                        "required": ["values"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Compute the L1 (Σ|xᵢ|), L2 (√Σxᵢ²) or L∞ (max|xᵢ|) norm of a vector".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "norm": {"type": "number"},
+                                    "p": {"oneOf": [{"type": "integer", "enum": [1, 2]}, {"type": "string", "enum": ["inf"]}]},
+                                    "dimension": {"type": "integer"}
+                                },
+                                "required": ["norm", "p", "dimension"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Vector Norm".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "cosine_similarity".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "First vector"
+                            },
+                            "b": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Second vector, same length as a"
+                            }
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Cosine similarity (a·b)/(‖a‖‖b‖) of two equal-length vectors, along with the cosine distance 1 - similarity. Neither vector may have zero magnitude".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "similarity": {"type": "number"},
+                                    "distance": {"type": "number", "description": "1 - similarity"},
+                                    "dimension": {"type": "integer"}
+                                },
+                                "required": ["similarity", "distance", "dimension"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Cosine Similarity".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "vector_distance".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "First point"
+                            },
+                            "b": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Second point, same dimension as a"
+                            },
+                            "metric": {
+                                "type": "string",
+                                "enum": ["euclidean", "manhattan"],
+                                "description": "Distance metric (default: euclidean)"
+                            }
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Euclidean (√Σ(aᵢ-bᵢ)²) or Manhattan (Σ|aᵢ-bᵢ|) distance between two points of any dimension".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "distance": {"type": "number"},
+                                    "metric": {"type": "string", "enum": ["euclidean", "manhattan"]},
+                                    "dimension": {"type": "integer"}
+                                },
+                                "required": ["distance", "metric", "dimension"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Vector Distance".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "agm".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {"type": "number", "minimum": 0, "description": "First non-negative number"},
+                            "b": {"type": "number", "minimum": 0, "description": "Second non-negative number"},
+                            "tolerance": {"type": "number", "exclusiveMinimum": 0, "description": "Stop when the arithmetic and geometric means agree to this relative tolerance (default: 1e-12)"},
+                            "max_iterations": {"type": "integer", "minimum": 1, "maximum": 10000, "description": "Iteration cap (default: 100)"}
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Arithmetic-geometric mean of a and b: iterate (a, b) → ((a + b)/2, √(ab)) until both converge to a common limit. Returns the limit and the iteration count".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "agm": {"type": "number"},
+                                    "iterations": {"type": "integer"}
+                                },
+                                "required": ["agm", "iterations"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Arithmetic-Geometric Mean".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "continued_fraction".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Number to approximate"},
+                            "max_terms": {"type": "integer", "minimum": 1, "maximum": 64, "description": "Maximum number of coefficients to expand"}
+                        },
+                        "required": ["value", "max_terms"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Expand a number as a continued fraction [a₀; a₁, a₂, ...] and return its convergents p/q. The last convergent is the best rational approximation found, reported with its error. Expansion stops early once a convergent is exact or exceeds integer precision".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "value": {"type": "number"},
+                                    "coefficients": {"type": "array", "items": {"type": "integer"}},
+                                    "convergents": {"type": "array", "items": {"type": "object", "properties": {"numerator": {"type": "integer"}, "denominator": {"type": "integer"}, "value": {"type": "number"}, "error": {"type": "number"}}}},
+                                    "best": {"type": ["object", "null"], "description": "Last convergent"}
+                                },
+                                "required": ["value", "coefficients", "convergents"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Continued Fraction".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "gcd".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {"type": "integer", "description": "First whole number, |a| <= 2^53"},
+                            "b": {"type": "integer", "description": "Second whole number, |b| <= 2^53"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Greatest common divisor of two whole numbers using the Euclidean algorithm. Always non-negative; gcd(0, 0) = 0".to_string()),
+                        output_schema: Some(scalar_output_schema("Greatest common divisor")),
+                        title: Some("Greatest Common Divisor".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "lcm".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {"type": "integer", "description": "First whole number, |a| <= 2^53"},
+                            "b": {"type": "integer", "description": "Second whole number, |b| <= 2^53"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Least common multiple of two whole numbers, computed as |a| / gcd(a, b) · |b|. lcm(a, 0) = 0. Errors if the result exceeds 2^53".to_string()),
+                        output_schema: Some(scalar_output_schema("Least common multiple")),
+                        title: Some("Least Common Multiple".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "simplify_fraction".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numerator": {"type": "integer", "description": "Whole-number numerator, |numerator| <= 2^53"},
+                            "denominator": {"type": "integer", "description": "Non-zero whole-number denominator, |denominator| <= 2^53"}
+                        },
+                        "required": ["numerator", "denominator"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Reduce a fraction to lowest terms by dividing by the gcd. The sign is carried by the numerator, so the denominator is always positive. Also returns the decimal value".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "numerator": {"type": "integer"},
+                                    "denominator": {"type": "integer"},
+                                    "value": {"type": "number"}
+                                },
+                                "required": ["numerator", "denominator", "value"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Simplify Fraction".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "log".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "exclusiveMinimum": 0, "description": "Positive number to take the logarithm of"},
+                            "base": {"type": "number", "exclusiveMinimum": 0, "description": "Positive base other than 1 (default: e)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the logarithm of a number in the given base (log_base(value)). Defaults to the natural logarithm".to_string()),
+                        output_schema: Some(scalar_output_schema("Logarithm of value in the given base")),
+                        title: Some("Logarithm".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "log10".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "exclusiveMinimum": 0, "description": "Positive number to take the logarithm of"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the base-10 logarithm of a number (log₁₀ value)".to_string()),
+                        output_schema: Some(scalar_output_schema("Base-10 logarithm of value")),
+                        title: Some("Base-10 Logarithm".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "log2".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "exclusiveMinimum": 0, "description": "Positive number to take the logarithm of"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the base-2 logarithm of a number (log₂ value)".to_string()),
+                        output_schema: Some(scalar_output_schema("Base-2 logarithm of value")),
+                        title: Some("Base-2 Logarithm".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "ln".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "exclusiveMinimum": 0, "description": "Positive number to take the logarithm of"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the natural logarithm of a number (ln value)".to_string()),
+                        output_schema: Some(scalar_output_schema("Natural logarithm of value")),
+                        title: Some("Natural Logarithm".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "decimal_to_fraction".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Decimal number to approximate"},
+                            "max_denominator": {"type": "integer", "minimum": 1, "description": "Largest denominator allowed in the result"}
+                        },
+                        "required": ["value", "max_denominator"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Closest fraction p/q to a decimal with q no larger than max_denominator, chosen from the continued-fraction convergents and semiconvergents. Returns the fraction and its approximation error".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "numerator": {"type": "integer"},
+                                    "denominator": {"type": "integer"},
+                                    "fraction": {"type": "string", "description": "p/q"},
+                                    "value": {"type": "number"},
+                                    "error": {"type": "number", "description": "Absolute approximation error"}
+                                },
+                                "required": ["numerator", "denominator", "fraction", "value", "error"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Decimal to Fraction".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "sin".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "angle": {"type": "number", "description": "Angle"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["angle"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the sine of an angle".to_string()),
+                        output_schema: Some(scalar_output_schema("Sine of the angle")),
+                        title: Some("Sine".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "cos".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "angle": {"type": "number", "description": "Angle"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["angle"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the cosine of an angle".to_string()),
+                        output_schema: Some(scalar_output_schema("Cosine of the angle")),
+                        title: Some("Cosine".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "tan".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "angle": {"type": "number", "description": "Angle, which must not be an odd multiple of 90°"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["angle"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the tangent of an angle. Undefined at odd multiples of 90°".to_string()),
+                        output_schema: Some(scalar_output_schema("Tangent of the angle. Odd multiples of 90° are reported as an error result instead")),
+                        title: Some("Tangent".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "asin".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "minimum": -1, "maximum": 1, "description": "Sine of the angle, in [-1, 1]"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the returned angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the arcsine of a value in [-1, 1]. Result lies in [-π/2, π/2]".to_string()),
+                        output_schema: Some(scalar_output_schema("Arcsine in the requested unit")),
+                        title: Some("Arcsine".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "acos".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "minimum": -1, "maximum": 1, "description": "Cosine of the angle, in [-1, 1]"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the returned angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the arccosine of a value in [-1, 1]. Result lies in [0, π]".to_string()),
+                        output_schema: Some(scalar_output_schema("Arccosine in the requested unit")),
+                        title: Some("Arccosine".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "atan".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Tangent of the angle (any real number)"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the returned angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the arctangent of any real value. Result lies in (-π/2, π/2)".to_string()),
+                        output_schema: Some(scalar_output_schema("Arctangent in the requested unit")),
+                        title: Some("Arctangent".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "atan2".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "y": {"type": "number", "description": "y coordinate"},
+                            "x": {"type": "number", "description": "x coordinate"},
+                            "unit": {"type": "string", "enum": ["radians", "degrees"], "description": "Unit of the returned angle (default: radians)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["y", "x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the angle of the point (x, y) from the positive x-axis. Result lies in (-π, π]; undefined when both x and y are 0".to_string()),
+                        output_schema: Some(scalar_output_schema("Angle of (x, y) in the requested unit")),
+                        title: Some("Two-Argument Arctangent".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "max_of".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "values": {"type": "array", "items": {"type": "number"}, "minItems": 1, "description": "Numbers to search"}
+                        },
+                        "required": ["values"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Find the largest of a list of numbers and its index (first occurrence on ties)".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "value": {"type": "number", "description": "Largest value"},
+                                    "index": {"type": "integer", "description": "Index of its first occurrence"}
+                                },
+                                "required": ["value", "index"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Maximum of Values".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "min_of".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "values": {"type": "array", "items": {"type": "number"}, "minItems": 1, "description": "Numbers to search"}
+                        },
+                        "required": ["values"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Find the smallest of a list of numbers and its index (first occurrence on ties)".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "value": {"type": "number", "description": "Smallest value"},
+                                    "index": {"type": "integer", "description": "Index of its first occurrence"}
+                                },
+                                "required": ["value", "index"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Minimum of Values".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "abs".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the absolute value |x|".to_string()),
+                        output_schema: Some(scalar_output_schema("|x|")),
+                        title: Some("Absolute Value".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "sign".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Sign of a number: -1 if negative, 0 if zero, 1 if positive".to_string()),
+                        output_schema: Some(scalar_output_schema("-1, 0 or 1")),
+                        title: Some("Sign".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "floor".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Round down to the nearest integer (toward -∞)".to_string()),
+                        output_schema: Some(scalar_output_schema("Largest integer ≤ x")),
+                        title: Some("Floor".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "ceil".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Round up to the nearest integer (toward +∞)".to_string()),
+                        output_schema: Some(scalar_output_schema("Smallest integer ≥ x")),
+                        title: Some("Ceiling".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "round".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number to round"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 15, "default": 0, "description": "Decimal places to round to"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Round to the nearest integer, or to a number of decimal places, with halves rounded away from zero".to_string()),
+                        output_schema: Some(scalar_output_schema("x rounded to the requested decimal places")),
+                        title: Some("Round".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "trunc".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "x": {"type": "number", "description": "Number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["x"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Drop the fractional part, rounding toward zero".to_string()),
+                        output_schema: Some(scalar_output_schema("Integer part of x")),
+                        title: Some("Truncate".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "min".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {"type": "number", "description": "First number"},
+                            "b": {"type": "number", "description": "Second number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Return the smaller of two numbers".to_string()),
+                        output_schema: Some(scalar_output_schema("Smaller of a and b")),
+                        title: Some("Minimum".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "max".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "a": {"type": "number", "description": "First number"},
+                            "b": {"type": "number", "description": "Second number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["a", "b"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Return the larger of two numbers".to_string()),
+                        output_schema: Some(scalar_output_schema("Larger of a and b")),
+                        title: Some("Maximum".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "clamp".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Number to limit"},
+                            "min": {"type": "number", "description": "Lower bound"},
+                            "max": {"type": "number", "description": "Upper bound (must be at least min)"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["value", "min", "max"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Limit a value to the range [min, max]".to_string()),
+                        output_schema: Some(scalar_output_schema("value limited to [min, max]")),
+                        title: Some("Clamp".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "evaluate".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "expression": {"type": "string", "maxLength": 10000, "description": "Arithmetic expression using numbers, variable names, + - * / ^ and parentheses, e.g. (a + b) * c"},
+                            "variables": {"type": "object", "additionalProperties": {"type": "number"}, "description": "Values for the variable names used in the expression"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["expression"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Evaluate an arithmetic expression in one call instead of chaining tools. Supports + - * / ^ (right-associative) and parentheses; unknown variables and division by zero are errors".to_string()),
+                        output_schema: Some(scalar_output_schema("Value of the expression")),
+                        title: Some("Evaluate Expression".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "within_tolerance".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "measured": {"type": "number", "description": "Measured value"},
+                            "target": {"type": "number", "description": "Nominal value the measurement is checked against"},
+                            "abs_tolerance": {"type": "number", "minimum": 0, "description": "Allowed deviation in the units of the measurement"},
+                            "rel_tolerance": {"type": "number", "minimum": 0, "description": "Allowed deviation as a fraction of |target|, e.g. 0.05 for 5%"}
+                        },
+                        "required": ["measured", "target"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Check whether a measured value lies within a tolerance band around a target. Give exactly one of abs_tolerance or rel_tolerance; the band edges count as in tolerance. Returns the verdict, the deviation and the band".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "within_tolerance": {"type": "boolean"},
+                                    "deviation": {"type": "number", "description": "measured - target"},
+                                    "relative_deviation": {"type": ["number", "null"], "description": "deviation / |target|, null when target is 0"},
+                                    "tolerance_type": {"type": "string", "enum": ["absolute", "relative"]},
+                                    "allowed_deviation": {"type": "number", "description": "Half-width of the band in the units of the measurement"},
+                                    "lower_bound": {"type": "number"},
+                                    "upper_bound": {"type": "number"}
+                                },
+                                "required": ["within_tolerance", "deviation", "relative_deviation", "tolerance_type", "allowed_deviation", "lower_bound", "upper_bound"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Within Tolerance".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "factorial".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "n": {"type": "integer", "minimum": 0, "maximum": 170, "description": "Non-negative whole number"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["n"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("n! = 1 · 2 · … · n, with 0! = 1. n is limited to 170, the largest factorial that fits in a 64-bit float; results above 2^53 are rounded".to_string()),
+                        output_schema: Some(scalar_output_schema("n factorial")),
+                        title: Some("Factorial".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "combinations".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "n": {"type": "integer", "minimum": 0, "description": "Number of items to choose from"},
+                            "r": {"type": "integer", "minimum": 0, "description": "Number of items chosen, at most n"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["n", "r"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Number of ways to choose r of n items when order does not matter, n! / (r! (n - r)!). Computed with a multiplicative loop so large n does not overflow early; results above 2^53 are rounded".to_string()),
+                        output_schema: Some(scalar_output_schema("Number of r-combinations of n items")),
+                        title: Some("Combinations".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "permutations".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "n": {"type": "integer", "minimum": 0, "description": "Number of items to choose from"},
+                            "r": {"type": "integer", "minimum": 0, "description": "Number of items chosen, at most n"},
+                            "format": {"type": "string", "enum": ["auto", "fixed", "scientific", "percent"], "description": "Presentation of the text result (default: auto). The raw number is always in structured_content"},
+                            "decimals": {"type": "integer", "minimum": 0, "maximum": 20, "description": "Digits after the decimal point for fixed, scientific and percent formats (default: 2 for fixed and percent, shortest for scientific)"}
+                        },
+                        "required": ["n", "r"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Number of ordered arrangements of r of n items, n! / (n - r)!. Errors if the result overflows; results above 2^53 are rounded".to_string()),
+                        output_schema: Some(scalar_output_schema("Number of r-permutations of n items")),
+                        title: Some("Permutations".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "round_sig_figs".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Number to round"},
+                            "sig_figs": {"type": "integer", "minimum": 1, "maximum": 17, "description": "Number of significant digits to keep"}
+                        },
+                        "required": ["value", "sig_figs"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Round to a number of significant figures rather than decimal places, e.g. 0.004567 to 2 significant figures is 0.0046 and 123456 is 120000. Halves are rounded away from zero; zero stays zero".to_string()),
+                        output_schema: Some(scalar_output_schema("value rounded to sig_figs significant figures")),
+                        title: Some("Round to Significant Figures".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "scientific_notation".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Number to decompose"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Split a number into mantissa and exponent so that value = mantissa × 10^exponent with 1 <= |mantissa| < 10. Zero decomposes to mantissa 0 and exponent 0".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "mantissa": {"type": "number", "description": "Signed coefficient with 1 <= |mantissa| < 10, or 0"},
+                                    "exponent": {"type": "integer", "description": "Power of ten"}
+                                },
+                                "required": ["mantissa", "exponent"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Scientific Notation".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "engineering_notation".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Number to express"}
+                        },
+                        "required": ["value"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Express a number as mantissa × 10^exponent with the exponent a multiple of 3 and 1 <= |mantissa| < 1000, labelled with its SI prefix (e.g. 4700 is 4.7 k, 0.00022 is 220 µ). Outside the prefix range (10^-30 to 10^32) it falls back to scientific notation".to_string()),
+                        output_schema: Some(
+                            r#"{
+                                "type": "object",
+                                "properties": {
+                                    "formatted": {"type": "string", "description": "Mantissa followed by the SI prefix, or m e exponent outside the prefix range"},
+                                    "mantissa": {"type": "number"},
+                                    "exponent": {"type": "integer"},
+                                    "prefix": {"type": ["string", "null"], "description": "SI prefix for the exponent, empty for 10^0, null outside the prefix range"},
+                                    "notation": {"type": "string", "enum": ["engineering", "scientific"]}
+                                },
+                                "required": ["formatted", "mantissa", "exponent", "prefix", "notation"]
+                            }"#
+                            .to_string(),
+                        ),
+                        title: Some("Engineering Notation".to_string()),
+                    }),
+                },
+            ],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "add" => Some(execute_operation("add", &request.arguments, |a, b| a + b)),
+            "subtract" => Some(execute_operation("subtract", &request.arguments, |a, b| {
+                a - b
+            })),
+            "multiply" => Some(execute_operation("multiply", &request.arguments, |a, b| {
+                a * b
+            })),
+            "divide" => Some(execute_divide(&request.arguments)),
+            "square" => Some(execute_square(&request.arguments)),
+            "square_root" => Some(execute_square_root(&request.arguments)),
+            "power" => Some(execute_power(&request.arguments)),
+            "solve_2x2" => Some(execute_solve_2x2(&request.arguments)),
+            "evaluate_polynomial" => Some(execute_evaluate_polynomial(&request.arguments)),
+            "polynomial_root" => Some(execute_polynomial_root(&request.arguments)),
+            "bisection" => Some(execute_bisection(&request.arguments)),
+            "derivative_at" => Some(execute_derivative_at(&request.arguments)),
+            "linear_combination" => Some(execute_linear_combination(&request.arguments)),
+            "norm" => Some(execute_norm(&request.arguments)),
+            "cosine_similarity" => Some(execute_cosine_similarity(&request.arguments)),
+            "vector_distance" => Some(execute_vector_distance(&request.arguments)),
+            "agm" => Some(execute_agm(&request.arguments)),
+            "continued_fraction" => Some(execute_continued_fraction(&request.arguments)),
+            "gcd" => Some(execute_gcd(&request.arguments)),
+            "lcm" => Some(execute_lcm(&request.arguments)),
+            "simplify_fraction" => Some(execute_simplify_fraction(&request.arguments)),
+            "log" => Some(execute_log(&request.arguments)),
+            "log10" => Some(execute_logarithm("log10", &request.arguments, f64::log10)),
+            "log2" => Some(execute_logarithm("log2", &request.arguments, f64::log2)),
+            "ln" => Some(execute_logarithm("ln", &request.arguments, f64::ln)),
+            "decimal_to_fraction" => Some(execute_decimal_to_fraction(&request.arguments)),
+            "sin" => Some(execute_trig("sin", &request.arguments, f64::sin)),
+            "cos" => Some(execute_trig("cos", &request.arguments, f64::cos)),
+            "tan" => Some(execute_tan(&request.arguments)),
+            "asin" => Some(execute_inverse_trig(
+                "asin",
+                &request.arguments,
+                f64::asin,
+                true,
+            )),
+            "acos" => Some(execute_inverse_trig(
+                "acos",
+                &request.arguments,
+                f64::acos,
+                true,
+            )),
+            "atan" => Some(execute_inverse_trig(
+                "atan",
+                &request.arguments,
+                f64::atan,
+                false,
+            )),
+            "atan2" => Some(execute_atan2(&request.arguments)),
+            "max_of" => Some(execute_extreme_of(&request.arguments, |x, best| x > best)),
+            "min_of" => Some(execute_extreme_of(&request.arguments, |x, best| x < best)),
+            "abs" => Some(execute_unary("abs", &request.arguments, f64::abs)),
+            "sign" => Some(execute_unary("sign", &request.arguments, sign)),
+            "floor" => Some(execute_unary("floor", &request.arguments, f64::floor)),
+            "ceil" => Some(execute_unary("ceil", &request.arguments, f64::ceil)),
+            "round" => Some(execute_round(&request.arguments)),
+            "trunc" => Some(execute_unary("trunc", &request.arguments, f64::trunc)),
+            "min" => Some(execute_operation("min", &request.arguments, f64::min)),
+            "max" => Some(execute_operation("max", &request.arguments, f64::max)),
+            "clamp" => Some(execute_clamp(&request.arguments)),
+            "evaluate" => Some(execute_evaluate(&request.arguments)),
+            "within_tolerance" => Some(execute_within_tolerance(&request.arguments)),
+            "factorial" => Some(execute_factorial(&request.arguments)),
+            "combinations" => Some(execute_combinations(&request.arguments)),
+            "permutations" => Some(execute_permutations(&request.arguments)),
+            "round_sig_figs" => Some(execute_round_sig_figs(&request.arguments)),
+            "scientific_notation" => Some(execute_scientific_notation(&request.arguments)),
+            "engineering_notation" => Some(execute_engineering_notation(&request.arguments)),
+            _ => None, // We don't handle this tool
+        }
+    }
+}
+
+/// Output schema shared by the single-number tools, whose `structured_content` is
+/// built by `success_result`
+fn scalar_output_schema(result_description: &str) -> String {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "result": {
+                "type": "number",
+                "description": result_description
+            },
+            "operation": {"type": "string", "description": "Name of the tool"},
+            "inputs": {"type": "object", "description": "Arguments the result was computed from"}
+        },
+        "required": ["result", "operation", "inputs"]
+    })
+    .to_string()
+}
+
+fn execute_operation<F>(operation: &str, arguments: &Option<String>, op: F) -> CallToolResult
+where
+    F: FnOnce(f64, f64) -> f64,
+{
+    match parse_args(arguments) {
+        Ok((a, b)) => {
+            let result = op(a, b);
+            success_result(operation, result, arguments)
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_divide(arguments: &Option<String>) -> CallToolResult {
+    match parse_args(arguments) {
+        Ok((a, b)) => {
+            if b == 0.0 {
+                error_result("Error: Division by zero".to_string())
+            } else {
+                let result = a / b;
+                success_result("divide", result, arguments)
+            }
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_clamp(arguments: &Option<String>) -> CallToolResult {
+    let (value, min, max) = match parse_named_args(arguments, &["value", "min", "max"]) {
+        Ok(values) => (values[0], values[1], values[2]),
+        Err(msg) => return error_result(msg),
+    };
+
+    if min > max {
+        return error_result(format!(
+            "Error: min ({}) must not be greater than max ({})",
+            min, max
+        ));
+    }
+
+    success_result("clamp", value.max(min).min(max), arguments)
+}
+
+fn execute_within_tolerance(arguments: &Option<String>) -> CallToolResult {
+    let (measured, target) = match parse_named_args(arguments, &["measured", "target"]) {
+        Ok(values) => (values[0], values[1]),
+        Err(msg) => return error_result(msg),
+    };
+
+    let (tolerance_type, allowed) = match parse_tolerance(arguments, target) {
+        Ok(tolerance) => tolerance,
+        Err(msg) => return error_result(msg),
+    };
+
+    let deviation = measured - target;
+    let relative_deviation = if target == 0.0 {
+        None
+    } else {
+        Some(deviation / target.abs())
+    };
+
+    structured_result(serde_json::json!({
+        "within_tolerance": deviation.abs() <= allowed,
+        "deviation": deviation,
+        "relative_deviation": relative_deviation,
+        "tolerance_type": tolerance_type,
+        "allowed_deviation": allowed,
+        "lower_bound": target - allowed,
+        "upper_bound": target + allowed
+    }))
+}
+
+/// Locate the extreme of the `values` array, where `replaces(x, best)` says
+/// whether `x` should take over as the current extreme
+fn execute_extreme_of<F>(arguments: &Option<String>, replaces: F) -> CallToolResult
+where
+    F: Fn(f64, f64) -> bool,
+{
+    match parse_vector(arguments, "values") {
+        Ok(values) => {
+            let (index, value) = extreme_of(&values, replaces);
+            structured_result(serde_json::json!({
+                "value": value,
+                "index": index
+            }))
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Index and value of the first extreme element. Callers must ensure `values` is non-empty.
+pub(crate) fn extreme_of<F>(values: &[f64], replaces: F) -> (usize, f64)
+where
+    F: Fn(f64, f64) -> bool,
+{
+    values
+        .iter()
+        .copied()
+        .enumerate()
+        .fold((0, values[0]), |best, (i, x)| {
+            if replaces(x, best.1) { (i, x) } else { best }
+        })
+}
+
+fn parse_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let a = json
+        .get("a")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'a'".to_string())?;
+
+    let b = json
+        .get("b")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'b'".to_string())?;
+
+    Ok((a, b))
+}
+
+fn execute_square(arguments: &Option<String>) -> CallToolResult {
+    match parse_single_arg(arguments, "x") {
+        Ok(x) => {
+            let result = x * x;
+            success_result("square", result, arguments)
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_square_root(arguments: &Option<String>) -> CallToolResult {
+    match parse_single_arg(arguments, "x") {
+        Ok(x) => {
+            if x < 0.0 {
+                error_result("Error: Cannot take square root of negative number".to_string())
+            } else {
+                let result = x.sqrt();
+                success_result("square_root", result, arguments)
+            }
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_power(arguments: &Option<String>) -> CallToolResult {
+    match parse_power_args(arguments) {
+        Ok((base, exponent)) => {
+            let result = base.powf(exponent);
+            success_result("power", result, arguments)
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Apply a single-argument function to `x`
+fn execute_unary<F>(operation: &str, arguments: &Option<String>, f: F) -> CallToolResult
+where
+    F: FnOnce(f64) -> f64,
+{
+    match parse_single_arg(arguments, "x") {
+        Ok(x) => success_result(operation, f(x), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// -1, 0 or 1 by the sign of `x`. Unlike `f64::signum`, zero maps to 0.
+pub(crate) fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+// `decimals` here is the rounding precision, so unlike the other scalar tools
+// `round` takes no display format and is always shown in canonical form
+fn execute_round(arguments: &Option<String>) -> CallToolResult {
+    let x = match parse_single_arg(arguments, "x") {
+        Ok(x) => x,
+        Err(msg) => return error_result(msg),
+    };
+
+    let decimals = match parse_round_decimals(arguments) {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    let value = match check_finite(round_to_decimals(x, decimals)) {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    text_and_structured_result(
+        format_number(value),
+        serde_json::json!({
+            "result": value,
+            "operation": "round",
+            "inputs": {"x": x, "decimals": decimals}
+        }),
+    )
+}
+
+/// Round half away from zero to `decimals` places as (x·10^d).round() / 10^d.
+/// Values too large to scale already have no digits at that precision.
+pub(crate) fn round_to_decimals(x: f64, decimals: u32) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    let scaled = x * scale;
+    if scaled.is_finite() {
+        scaled.round() / scale
+    } else {
+        x
+    }
+}
+
+fn execute_round_sig_figs(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_single_arg(arguments, "value") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let sig_figs = match parse_sig_figs(arguments) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    let rounded = match check_finite(round_to_sig_figs(value, sig_figs)) {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    text_and_structured_result(
+        format_number(rounded),
+        serde_json::json!({
+            "result": rounded,
+            "operation": "round_sig_figs",
+            "inputs": {"value": value, "sig_figs": sig_figs}
+        }),
+    )
+}
+
+/// Round half away from zero to `sig_figs` significant digits by shifting the leading
+/// digit to the units place: the order of magnitude fixes how many decimal places
+/// (possibly negative) to keep. Zero has no order of magnitude and is returned as is.
+pub(crate) fn round_to_sig_figs(x: f64, sig_figs: u32) -> f64 {
+    if x == 0.0 || !x.is_finite() {
+        return x;
+    }
+
+    let magnitude = x.abs().log10().floor() as i32;
+    let places = sig_figs as i32 - 1 - magnitude;
+
+    if places > f64::MAX_10_EXP {
+        // Subnormal inputs need a scale beyond f64::MAX, so apply it in two steps
+        let (low, high) = (10f64.powi(places / 2), 10f64.powi(places - places / 2));
+        (x * low * high).round() / high / low
+    } else if places >= 0 {
+        let scale = 10f64.powi(places);
+        (x * scale).round() / scale
+    } else {
+        let scale = 10f64.powi(-places);
+        (x / scale).round() * scale
+    }
+}
+
+fn execute_log(arguments: &Option<String>) -> CallToolResult {
+    let base = match parse_optional_arg(arguments, "base") {
+        Ok(base) => base,
+        Err(msg) => return error_result(msg),
+    };
+
+    match base {
+        None => execute_logarithm("log", arguments, f64::ln),
+        Some(base) if !base.is_finite() || base <= 0.0 || base == 1.0 => {
+            error_result("Error: Logarithm base must be positive and not equal to 1".to_string())
+        }
+        Some(base) => execute_logarithm("log", arguments, |value| value.log(base)),
+    }
+}
+
+/// Apply a logarithm to the positive `value` argument
+fn execute_logarithm<F>(operation: &str, arguments: &Option<String>, log: F) -> CallToolResult
+where
+    F: FnOnce(f64) -> f64,
+{
+    match parse_single_arg(arguments, "value") {
+        Ok(value) => {
+            if value.is_nan() || value <= 0.0 {
+                error_result("Error: logarithm of non-positive number".to_string())
+            } else {
+                success_result(operation, log(value), arguments)
+            }
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Apply a trigonometric function to the `angle` argument, converted to radians
+fn execute_trig<F>(operation: &str, arguments: &Option<String>, trig: F) -> CallToolResult
+where
+    F: FnOnce(f64) -> f64,
+{
+    match parse_angle_args(arguments) {
+        Ok(radians) => success_result(operation, trig(radians), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_tan(arguments: &Option<String>) -> CallToolResult {
+    match parse_angle_args(arguments) {
+        Ok(radians) if is_tan_asymptote(radians) => error_result(
+            "Error: tangent is undefined at odd multiples of 90° (π/2 radians)".to_string(),
+        ),
+        Ok(radians) => success_result("tan", radians.tan(), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Whether `radians` lies on a tangent asymptote, allowing for the rounding
+/// left over from degree conversion (cos(π/2) evaluates to ~6e-17, not 0)
+pub(crate) fn is_tan_asymptote(radians: f64) -> bool {
+    radians.cos().abs() < TAN_ASYMPTOTE_TOLERANCE
+}
+
+/// Apply an inverse trigonometric function to `value`, returning the angle in the
+/// requested unit. `unit_interval` restricts the input to [-1, 1].
+fn execute_inverse_trig<F>(
+    operation: &str,
+    arguments: &Option<String>,
+    inverse: F,
+    unit_interval: bool,
+) -> CallToolResult
+where
+    F: FnOnce(f64) -> f64,
+{
+    let value = match parse_named_args(arguments, &["value"]) {
+        Ok(values) => values[0],
+        Err(msg) => return error_result(msg),
+    };
+
+    if unit_interval && !(-1.0..=1.0).contains(&value) {
+        return error_result(format!("Error: {} is outside the domain [-1, 1]", value));
+    }
+
+    match parse_angle_unit(arguments) {
+        Ok(unit) => success_result(operation, unit.express(inverse(value)), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_atan2(arguments: &Option<String>) -> CallToolResult {
+    let (y, x) = match parse_named_args(arguments, &["y", "x"]) {
+        Ok(values) => (values[0], values[1]),
+        Err(msg) => return error_result(msg),
+    };
+
+    if y == 0.0 && x == 0.0 {
+        return error_result("Error: atan2 is undefined when both y and x are 0".to_string());
+    }
+
+    match parse_angle_unit(arguments) {
+        Ok(unit) => success_result("atan2", unit.express(y.atan2(x)), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_evaluate(arguments: &Option<String>) -> CallToolResult {
+    let expression = match parse_string(arguments, "expression") {
+        Ok(e) => e,
+        Err(msg) => return error_result(msg),
+    };
+
+    let variables = match parse_variables(arguments) {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    match expr::evaluate(&expression, &variables) {
+        Ok(value) => success_result("evaluate", value, arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_solve_2x2(arguments: &Option<String>) -> CallToolResult {
+    let coefficients = match parse_named_args(arguments, &["a1", "b1", "c1", "a2", "b2", "c2"]) {
+        Ok(values) => values,
+        Err(msg) => return error_result(msg),
+    };
+
+    let [a1, b1, c1, a2, b2, c2] = coefficients[..] else {
+        unreachable!("parse_named_args returns one value per name")
+    };
+
+    let det = determinant_2x2(a1, b1, a2, b2);
+
+    if det.abs() < EPSILON {
+        // Either the equations are inconsistent or one is a multiple of the other
+        let det_x = determinant_2x2(c1, b1, c2, b2);
+        let det_y = determinant_2x2(a1, c1, a2, c2);
+        let empty_row =
+            |a: f64, b: f64, c: f64| a.abs() < EPSILON && b.abs() < EPSILON && c.abs() >= EPSILON;
+
+        let status = if det_x.abs() >= EPSILON
+            || det_y.abs() >= EPSILON
+            || empty_row(a1, b1, c1)
+            || empty_row(a2, b2, c2)
+        {
+            "no_solution"
+        } else {
+            "infinite_solutions"
+        };
+
+        return structured_result(serde_json::json!({
+            "status": status,
+            "determinant": det,
+            "x": null,
+            "y": null
+        }));
+    }
+
+    let x = determinant_2x2(c1, b1, c2, b2) / det;
+    let y = determinant_2x2(a1, c1, a2, c2) / det;
+
+    structured_result(serde_json::json!({
+        "status": "unique",
+        "determinant": det,
+        "x": x,
+        "y": y
+    }))
+}
+
+/// Determinant of the 2x2 matrix [[a, b], [c, d]]
+pub(crate) fn determinant_2x2(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    a * d - b * c
+}
+
+fn execute_evaluate_polynomial(arguments: &Option<String>) -> CallToolResult {
+    let coefficients = match parse_polynomial(arguments) {
+        Ok(c) => c,
+        Err(msg) => return error_result(msg),
+    };
+
+    let x = match parse_single_arg(arguments, "x") {
+        Ok(x) => x,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = match check_finite(horner(&coefficients, x)) {
+        Ok(r) => r,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "result": result,
+        "x": x,
+        "degree": coefficients.len() - 1
+    }))
+}
+
+fn execute_polynomial_root(arguments: &Option<String>) -> CallToolResult {
+    let coefficients = match parse_polynomial(arguments) {
+        Ok(c) => c,
+        Err(msg) => return error_result(msg),
+    };
+
+    if coefficients.len() < 2 {
+        return error_result("Error: A constant polynomial has no isolated roots".to_string());
+    }
+
+    let guess = match parse_single_arg(arguments, "guess") {
+        Ok(g) => g,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (tolerance, max_iterations) = match parse_iteration_options(arguments) {
+        Ok(options) => options,
+        Err(msg) => return error_result(msg),
+    };
+
+    match newton_raphson(&coefficients, guess, tolerance, max_iterations) {
+        Ok((root, iterations)) => structured_result(serde_json::json!({
+            "root": root,
+            "iterations": iterations,
+            "value_at_root": horner(&coefficients, root)
+        })),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Newton-Raphson on a polynomial (highest degree first).
+/// Returns the root and the number of iterations used.
+pub(crate) fn newton_raphson(
+    coefficients: &[f64],
+    guess: f64,
+    tolerance: f64,
+    max_iterations: u32,
+) -> Result<(f64, u32), String> {
+    let derivative = polynomial_derivative(coefficients);
+    let mut x = guess;
+
+    for iteration in 1..=max_iterations {
+        let fx = horner(coefficients, x);
+        let dfx = horner(&derivative, x);
+
+        if dfx.abs() < EPSILON {
+            return Err(format!(
+                "Error: Derivative vanished at x = {} after {} iterations; try a different guess",
+                x,
+                iteration - 1
+            ));
+        }
+
+        let step = fx / dfx;
+        x -= step;
+
+        if !x.is_finite() {
+            return Err("Error: Newton iteration diverged".to_string());
+        }
+
+        if step.abs() < tolerance {
+            return Ok((x, iteration));
+        }
+    }
+
+    Err(format!(
+        "Error: Newton iteration did not converge within {} iterations",
+        max_iterations
+    ))
+}
+
+/// Coefficients of the derivative of a polynomial (both highest degree first)
+pub(crate) fn polynomial_derivative(coefficients: &[f64]) -> Vec<f64> {
+    let degree = coefficients.len().saturating_sub(1);
+    if degree == 0 {
+        return vec![0.0];
+    }
+    coefficients[..degree]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c * (degree - i) as f64)
+        .collect()
+}
+
+/// Named single-variable functions usable by the numerical tools
+pub(crate) enum BuiltinFunction {
+    Sin,
+    Cos,
+    Exp,
+    Ln,
+    /// Coefficients ordered highest degree first
+    Polynomial(Vec<f64>),
+}
+
+impl BuiltinFunction {
+    pub(crate) fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            BuiltinFunction::Sin => x.sin(),
+            BuiltinFunction::Cos => x.cos(),
+            BuiltinFunction::Exp => x.exp(),
+            BuiltinFunction::Ln => x.ln(),
+            BuiltinFunction::Polynomial(coefficients) => horner(coefficients, x),
+        }
+    }
+
+    /// Reject points outside the function's domain
+    pub(crate) fn check_domain(&self, x: f64) -> Result<(), String> {
+        match self {
+            BuiltinFunction::Ln if x <= 0.0 => {
+                Err(format!("Error: ln is only defined for x > 0 (got {})", x))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn execute_derivative_at(arguments: &Option<String>) -> CallToolResult {
+    let function = match parse_builtin_function(arguments) {
+        Ok(f) => f,
+        Err(msg) => return error_result(msg),
+    };
+
+    let x = match parse_single_arg(arguments, "x") {
+        Ok(x) => x,
+        Err(msg) => return error_result(msg),
+    };
+
+    if !x.is_finite() {
+        return error_result("Error: x must be a finite number".to_string());
+    }
+
+    if let Err(msg) = function.check_domain(x) {
+        return error_result(msg);
+    }
+
+    let (derivative, h) = central_difference(&function, x);
+    let derivative = match check_finite(derivative) {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "derivative": derivative,
+        "x": x,
+        "step": h
+    }))
+}
+
+/// Central difference (f(x+h) - f(x-h)) / 2h. The step scales with |x| and is
+/// sized to balance truncation against rounding error (cube root of machine
+/// epsilon). It is shrunk if x - h would leave the function's domain.
+pub(crate) fn central_difference(function: &BuiltinFunction, x: f64) -> (f64, f64) {
+    let mut h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+    while function.check_domain(x - h).is_err() {
+        h /= 2.0;
+    }
+
+    let derivative = (function.evaluate(x + h) - function.evaluate(x - h)) / (2.0 * h);
+    (derivative, h)
+}
+
+fn execute_bisection(arguments: &Option<String>) -> CallToolResult {
+    let function = match parse_builtin_function(arguments) {
+        Ok(f) => f,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (a, b) = match parse_args(arguments) {
+        Ok(bracket) => bracket,
+        Err(msg) => return error_result(msg),
+    };
+
+    if a >= b {
+        return error_result("Error: Bracket must satisfy a < b".to_string());
+    }
+
+    if let Err(msg) = function.check_domain(a).and(function.check_domain(b)) {
+        return error_result(msg);
+    }
+
+    let (tolerance, max_iterations) = match parse_iteration_options(arguments) {
+        Ok(options) => options,
+        Err(msg) => return error_result(msg),
+    };
+
+    match bisect(&function, a, b, tolerance, max_iterations) {
+        Ok((root, width, iterations)) => structured_result(serde_json::json!({
+            "root": root,
+            "bracket_width": width,
+            "iterations": iterations,
+            "value_at_root": function.evaluate(root)
+        })),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Bisection on [a, b]. Returns the midpoint of the final bracket, its width and
+/// the number of halvings performed. Stops early on an exact zero.
+pub(crate) fn bisect(
+    function: &BuiltinFunction,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+    max_iterations: u32,
+) -> Result<(f64, f64, u32), String> {
+    let (mut lo, mut hi) = (a, b);
+    let mut f_lo = function.evaluate(lo);
+    let f_hi = function.evaluate(hi);
+
+    if f_lo == 0.0 {
+        return Ok((lo, 0.0, 0));
+    }
+    if f_hi == 0.0 {
+        return Ok((hi, 0.0, 0));
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return Err("Error: Function does not change sign across the bracket [a, b]".to_string());
+    }
+
+    let mut iterations = 0;
+    while hi - lo > tolerance && iterations < max_iterations {
+        let mid = lo + (hi - lo) / 2.0;
+        let f_mid = function.evaluate(mid);
+        iterations += 1;
+
+        if f_mid == 0.0 {
+            return Ok((mid, 0.0, iterations));
+        }
+        if f_mid.signum() == f_lo.signum() {
+            lo = mid;
+            f_lo = f_mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok((lo + (hi - lo) / 2.0, hi - lo, iterations))
+}
+
+fn execute_agm(arguments: &Option<String>) -> CallToolResult {
+    let (a, b) = match parse_named_args(arguments, &["a", "b"]) {
+        Ok(values) => (values[0], values[1]),
+        Err(msg) => return error_result(msg),
+    };
+
+    if a < 0.0 || b < 0.0 {
+        return error_result("Error: AGM requires a >= 0 and b >= 0".to_string());
+    }
+
+    let (tolerance, max_iterations) = match parse_iteration_options(arguments) {
+        Ok(options) => options,
+        Err(msg) => return error_result(msg),
+    };
+
+    match arithmetic_geometric_mean(a, b, tolerance, max_iterations) {
+        Ok((agm, iterations)) => structured_result(serde_json::json!({
+            "agm": agm,
+            "iterations": iterations
+        })),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// AGM of non-negative `a` and `b` and the iterations used. Converges quadratically,
+/// so the cap is only reached with an unreachably small tolerance.
+pub(crate) fn arithmetic_geometric_mean(
+    a: f64,
+    b: f64,
+    tolerance: f64,
+    max_iterations: u32,
+) -> Result<(f64, u32), String> {
+    // A zero term pins the geometric mean at zero, which the relative test never reaches
+    if a == 0.0 || b == 0.0 {
+        return Ok((0.0, 0));
+    }
+
+    let (mut a, mut b) = (a, b);
+
+    for iteration in 0..=max_iterations {
+        if (a - b).abs() <= tolerance * a.max(b) {
+            return Ok((a / 2.0 + b / 2.0, iteration));
+        }
+        if iteration == max_iterations {
+            break;
+        }
+        // Halving and rooting separately keeps large inputs from overflowing
+        (a, b) = (a / 2.0 + b / 2.0, a.sqrt() * b.sqrt());
+    }
+
+    Err(format!(
+        "Error: AGM did not converge within {} iterations",
+        max_iterations
+    ))
+}
+
+fn execute_continued_fraction(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_named_args(arguments, &["value"]) {
+        Ok(values) => values[0],
+        Err(msg) => return error_result(msg),
+    };
+
+    let max_terms = match parse_max_terms(arguments) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (coefficients, convergents) = continued_fraction(value, max_terms);
+    if convergents.is_empty() {
+        return error_result(format!(
+            "Error: {} is too large to expand as a continued fraction",
+            value
+        ));
+    }
+
+    let convergents_json: Vec<serde_json::Value> = convergents
+        .iter()
+        .map(|&(p, q)| {
+            let approximation = p as f64 / q as f64;
+            serde_json::json!({
+                "numerator": p,
+                "denominator": q,
+                "value": approximation,
+                "error": (value - approximation).abs()
+            })
+        })
+        .collect();
+
+    structured_result(serde_json::json!({
+        "value": value,
+        "coefficients": coefficients,
+        "convergents": convergents_json,
+        "best": convergents_json.last()
+    }))
+}
+
+/// Coefficients and convergents p/q of the continued fraction of `value`, at most
+/// `max_terms` of each. Stops early when a convergent is exact or p, q would overflow.
+pub(crate) fn continued_fraction(value: f64, max_terms: usize) -> (Vec<i64>, Vec<(i64, i64)>) {
+    let mut coefficients = Vec::new();
+    let mut convergents = Vec::new();
+    // (p, q) for the two previous convergents, seeded with 1/0 and 0/1
+    let (mut p_prev, mut q_prev, mut p_prev2, mut q_prev2) = (1i64, 0i64, 0i64, 1i64);
+    let mut x = value;
+
+    while coefficients.len() < max_terms {
+        let a = x.floor();
+        if a.abs() >= i64::MAX as f64 {
+            break;
+        }
+        let a = a as i64;
+
+        let next = a
+            .checked_mul(p_prev)
+            .and_then(|v| v.checked_add(p_prev2))
+            .zip(a.checked_mul(q_prev).and_then(|v| v.checked_add(q_prev2)));
+        let Some((p, q)) = next else {
+            break;
+        };
+
+        coefficients.push(a);
+        convergents.push((p, q));
+        (p_prev2, q_prev2, p_prev, q_prev) = (p_prev, q_prev, p, q);
+
+        let remainder = x - a as f64;
+        if remainder == 0.0 || p as f64 / q as f64 == value {
+            break;
+        }
+        x = 1.0 / remainder;
+    }
+
+    (coefficients, convergents)
+}
+
+fn execute_decimal_to_fraction(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_named_args(arguments, &["value"]) {
+        Ok(values) => values[0],
+        Err(msg) => return error_result(msg),
+    };
+
+    let max_denominator = match parse_max_denominator(arguments) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    let Some((p, q)) = best_rational(value, max_denominator) else {
+        return error_result(format!(
+            "Error: {} is too large to approximate as a fraction",
+            value
+        ));
+    };
+
+    let approximation = p as f64 / q as f64;
+    structured_result(serde_json::json!({
+        "numerator": p,
+        "denominator": q,
+        "fraction": format!("{}/{}", p, q),
+        "value": approximation,
+        "error": (value - approximation).abs()
+    }))
+}
+
+/// Closest p/q to `value` with 1 <= q <= `max_denominator`. The answer is either
+/// the last convergent within the bound or the largest semiconvergent that fits
+/// between it and the next convergent.
+pub(crate) fn best_rational(value: f64, max_denominator: i64) -> Option<(i64, i64)> {
+    let (coefficients, convergents) =
+        continued_fraction(value, MAX_CONTINUED_FRACTION_TERMS as usize);
+    let last = convergents
+        .iter()
+        .rposition(|&(_, q)| q <= max_denominator)?;
+    let (p, q) = convergents[last];
+
+    let Some(&a_next) = coefficients.get(last + 1) else {
+        return Some((p, q));
+    };
+    let (p_prev, q_prev) = match last {
+        0 => (1, 0),
+        i => convergents[i - 1],
+    };
+
+    // Semiconvergents (p_prev + k·p) / (q_prev + k·q) for 1 <= k < a_next
+    let k = ((max_denominator - q_prev) / q).min(a_next - 1);
+    if k < 1 {
+        return Some((p, q));
+    }
+    let semi = (p_prev + k * p, q_prev + k * q);
+
+    let error = |(p, q): (i64, i64)| (value - p as f64 / q as f64).abs();
+    if error(semi) < error((p, q)) {
+        Some(semi)
+    } else {
+        Some((p, q))
+    }
+}
+
+fn execute_scientific_notation(arguments: &Option<String>) -> CallToolResult {
+    match parse_single_arg(arguments, "value") {
+        Ok(value) => {
+            let (mantissa, exponent) = scientific_notation(value);
+            structured_result(serde_json::json!({
+                "mantissa": mantissa,
+                "exponent": exponent
+            }))
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_engineering_notation(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_single_arg(arguments, "value") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (mantissa, exponent, prefix) = engineering_notation(value);
+    // Scaling can leave noise in the last bit, e.g. 4.700000000000001
+    let shown = format_number(round_to_sig_figs(mantissa, 15));
+    let formatted = match prefix {
+        Some("") => shown,
+        Some(prefix) => format!("{} {}", shown, prefix),
+        None => format!("{}e{}", shown, exponent),
+    };
+
+    text_and_structured_result(
+        formatted.clone(),
+        serde_json::json!({
+            "formatted": formatted,
+            "mantissa": mantissa,
+            "exponent": exponent,
+            "prefix": prefix,
+            "notation": if prefix.is_some() { "engineering" } else { "scientific" }
+        }),
+    )
+}
+
+fn execute_gcd(arguments: &Option<String>) -> CallToolResult {
+    let (a, b) = match parse_integer_pair(arguments) {
+        Ok(pair) => pair,
+        Err(msg) => return error_result(msg),
+    };
+
+    success_result(
+        "gcd",
+        gcd(a.unsigned_abs(), b.unsigned_abs()) as f64,
+        arguments,
+    )
+}
+
+fn execute_lcm(arguments: &Option<String>) -> CallToolResult {
+    let (a, b) = match parse_integer_pair(arguments) {
+        Ok(pair) => pair,
+        Err(msg) => return error_result(msg),
+    };
+
+    match lcm(a.unsigned_abs(), b.unsigned_abs()) {
+        Some(result) if result <= MAX_SAFE_INTEGER as u64 => {
+            success_result("lcm", result as f64, arguments)
+        }
+        _ => error_result("Error: lcm exceeds the exactly representable range (2^53)".to_string()),
+    }
+}
+
+fn execute_factorial(arguments: &Option<String>) -> CallToolResult {
+    let n = match parse_integer(arguments, "n") {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    if n < 0 {
+        return error_result("Error: 'n' must not be negative".to_string());
+    }
+
+    if n > MAX_FACTORIAL {
+        return error_result(format!(
+            "Error: {}! overflows a 64-bit float; 'n' must be at most {}",
+            n, MAX_FACTORIAL
+        ));
+    }
+
+    success_result("factorial", factorial(n as u64), arguments)
+}
+
+fn execute_combinations(arguments: &Option<String>) -> CallToolResult {
+    match parse_selection(arguments) {
+        Ok((n, r)) => success_result("combinations", combinations(n, r), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_permutations(arguments: &Option<String>) -> CallToolResult {
+    match parse_selection(arguments) {
+        Ok((n, r)) => success_result("permutations", permutations(n, r), arguments),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_simplify_fraction(arguments: &Option<String>) -> CallToolResult {
+    let numerator = match parse_integer(arguments, "numerator") {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    let denominator = match parse_integer(arguments, "denominator") {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    if denominator == 0 {
+        return error_result("Error: Denominator cannot be zero".to_string());
+    }
+
+    let (numerator, denominator) = simplify_fraction(numerator, denominator);
+
+    structured_result(serde_json::json!({
+        "numerator": numerator,
+        "denominator": denominator,
+        "value": numerator as f64 / denominator as f64
+    }))
+}
+
+/// Lowest terms with a positive denominator. Callers must ensure `denominator != 0`
+/// and both magnitudes are within 2⁵³, so negation cannot overflow.
+pub(crate) fn simplify_fraction(numerator: i64, denominator: i64) -> (i64, i64) {
+    let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs()) as i64;
+    let sign = denominator.signum();
+    (sign * numerator / divisor, sign * denominator / divisor)
+}
+
+/// Decompose a finite `x` into (m, e) with x = m · 10^e and 1 <= |m| < 10; zero gives (0, 0).
+/// log10 can land one off either side of an exact power of ten, so the
+/// mantissa is nudged back into range afterwards.
+pub(crate) fn scientific_notation(x: f64) -> (f64, i32) {
+    if x == 0.0 {
+        return (0.0, 0);
+    }
+
+    let mut exponent = x.abs().log10().floor() as i32;
+    let mut mantissa = if -exponent > f64::MAX_10_EXP {
+        // 10^-e overflows for subnormal inputs, so apply it in two steps
+        let shift = -exponent;
+        x * 10f64.powi(shift / 2) * 10f64.powi(shift - shift / 2)
+    } else {
+        x / 10f64.powi(exponent)
+    };
+
+    if mantissa.abs() >= 10.0 {
+        mantissa /= 10.0;
+        exponent += 1;
+    } else if mantissa.abs() < 1.0 {
+        mantissa *= 10.0;
+        exponent -= 1;
+    }
+
+    (mantissa, exponent)
+}
+
+/// Decompose `x` into (m, e, prefix) with x = m · 10^e, e a multiple of 3 and
+/// 1 <= |m| < 1000, where an SI prefix exists for e. Otherwise falls back to the
+/// scientific decomposition with no prefix.
+pub(crate) fn engineering_notation(x: f64) -> (f64, i32, Option<&'static str>) {
+    let (mantissa, exponent) = scientific_notation(x);
+    let engineering_exponent = exponent.div_euclid(3) * 3;
+
+    let index = (engineering_exponent - SI_MIN_EXPONENT) / 3;
+    let Some(&prefix) = usize::try_from(index).ok().and_then(|i| SI_PREFIXES.get(i)) else {
+        return (mantissa, exponent, None);
+    };
+
+    // 10^-n has no exact f64 representation but 10^n does (up to 10^22), so multiply by it
+    let scaled = if engineering_exponent >= 0 {
+        x / 10f64.powi(engineering_exponent)
+    } else {
+        x * 10f64.powi(-engineering_exponent)
+    };
+
+    (scaled, engineering_exponent, Some(prefix))
+}
+
+pub(crate) fn factorial(n: u64) -> f64 {
+    (2..=n).fold(1.0, |product, i| product * i as f64)
+}
+
+/// n choose r as a running product of (n - r + i) / i, each step of which is itself
+/// a binomial coefficient. Stops once the value overflows, which bounds the loop.
+pub(crate) fn combinations(n: u64, r: u64) -> f64 {
+    let r = r.min(n - r);
+    let mut result = 1.0;
+    for i in 1..=r {
+        result = result * (n - r + i) as f64 / i as f64;
+        if result.is_infinite() {
+            break;
+        }
+    }
+    result
+}
+
+/// n! / (n - r)! as the product n · (n - 1) · … · (n - r + 1), stopping on overflow
+pub(crate) fn permutations(n: u64, r: u64) -> f64 {
+    let mut result = 1.0;
+    for i in 0..r {
+        result *= (n - i) as f64;
+        if result.is_infinite() {
+            break;
+        }
+    }
+    result
+}
+
+/// Greatest common divisor by the Euclidean algorithm
+pub(crate) fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` on overflow. Dividing by the gcd before
+/// multiplying keeps the intermediate product as small as the result.
+pub(crate) fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Evaluate a polynomial with coefficients ordered highest degree first
+pub(crate) fn horner(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().fold(0.0, |acc, &c| acc * x + c)
+}
+
+fn execute_linear_combination(arguments: &Option<String>) -> CallToolResult {
+    let values = match parse_vector(arguments, "values") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let weights = match parse_vector(arguments, "weights") {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = check_same_length(("values", &values), ("weights", &weights)) {
+        return error_result(msg);
+    }
+
+    let result = match check_finite(dot(&values, &weights)) {
+        Ok(r) => r,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "result": result,
+        "count": values.len()
+    }))
+}
+
+/// Dot product Σ aᵢ·bᵢ. Callers must ensure the slices have equal length.
+pub(crate) fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn execute_norm(arguments: &Option<String>) -> CallToolResult {
+    let values = match parse_vector(arguments, "values") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let order = match parse_norm_order(arguments) {
+        Ok(order) => order,
+        Err(msg) => return error_result(msg),
+    };
+
+    let norm = match check_finite(order.apply(&values)) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "norm": norm,
+        "p": order.label(),
+        "dimension": values.len()
+    }))
+}
+
+fn execute_cosine_similarity(arguments: &Option<String>) -> CallToolResult {
+    let a = match parse_vector(arguments, "a") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let b = match parse_vector(arguments, "b") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = check_same_length(("a", &a), ("b", &b)) {
+        return error_result(msg);
+    }
+
+    match cosine_similarity(&a, &b) {
+        Some(similarity) => structured_result(serde_json::json!({
+            "similarity": similarity,
+            "distance": 1.0 - similarity,
+            "dimension": a.len()
+        })),
+        None => error_result(
+            "Error: Cosine similarity is undefined for a zero-magnitude vector".to_string(),
+        ),
+    }
+}
+
+/// Cosine of the angle between `a` and `b`, clamped to [-1, 1] against rounding,
+/// or `None` if either vector has zero magnitude. Callers must ensure equal lengths.
+pub(crate) fn cosine_similarity(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (norm_a, norm_b) = (NormOrder::L2.apply(a), NormOrder::L2.apply(b));
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    // Normalizing before the dot product keeps large components from overflowing
+    let unit_a: Vec<f64> = a.iter().map(|x| x / norm_a).collect();
+    let unit_b: Vec<f64> = b.iter().map(|x| x / norm_b).collect();
+    Some(dot(&unit_a, &unit_b).clamp(-1.0, 1.0))
+}
+
+fn execute_vector_distance(arguments: &Option<String>) -> CallToolResult {
+    let a = match parse_vector(arguments, "a") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let b = match parse_vector(arguments, "b") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    if let Err(msg) = check_same_length(("a", &a), ("b", &b)) {
+        return error_result(msg);
+    }
+
+    let (metric, order) = match parse_metric(arguments) {
+        Ok(m) => m,
+        Err(msg) => return error_result(msg),
+    };
+
+    let difference: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+    let distance = match check_finite(order.apply(&difference)) {
+        Ok(d) => d,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "distance": distance,
+        "metric": metric,
+        "dimension": a.len()
+    }))
+}
+
+/// Supported orders for the `norm` tool
+pub(crate) enum NormOrder {
+    L1,
+    L2,
+    Infinity,
+}
+
+impl NormOrder {
+    pub(crate) fn apply(&self, values: &[f64]) -> f64 {
+        match self {
+            NormOrder::L1 => values.iter().map(|x| x.abs()).sum(),
+            // hypot scales as it goes, so large components don't overflow when squared
+            NormOrder::L2 => values.iter().fold(0.0, |acc: f64, x| acc.hypot(*x)),
+            NormOrder::Infinity => values.iter().fold(0.0, |acc: f64, x| acc.max(x.abs())),
+        }
+    }
+
+    fn label(&self) -> serde_json::Value {
+        match self {
+            NormOrder::L1 => serde_json::json!(1),
+            NormOrder::L2 => serde_json::json!(2),
+            NormOrder::Infinity => serde_json::json!("inf"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+impl AngleUnit {
+    pub(crate) fn to_radians(self, angle: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => angle,
+            AngleUnit::Degrees => angle * PI / 180.0,
+        }
+    }
+
+    pub(crate) fn express(self, radians: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => radians,
+            AngleUnit::Degrees => radians * 180.0 / PI,
+        }
+    }
+}
+
+fn parse_single_arg(arguments: &Option<String>, arg_name: &str) -> Result<f64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let value = json
+        .get(arg_name)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", arg_name))?;
+
+    Ok(value)
+}
+
+/// Parse the `angle` argument and optional `unit`, returning the angle in radians
+fn parse_angle_args(arguments: &Option<String>) -> Result<f64, String> {
+    let angle = parse_named_args(arguments, &["angle"])?[0];
+    Ok(parse_angle_unit(arguments)?.to_radians(angle))
+}
+
+fn parse_angle_unit(arguments: &Option<String>) -> Result<AngleUnit, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("unit") {
+        None | Some(serde_json::Value::Null) => Ok(AngleUnit::Radians),
+        Some(v) => match v.as_str() {
+            Some("radians") => Ok(AngleUnit::Radians),
+            Some("degrees") => Ok(AngleUnit::Degrees),
+            _ => Err("Invalid parameter 'unit': expected \"radians\" or \"degrees\"".to_string()),
+        },
+    }
+}
+
+fn parse_power_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let base = json
+        .get("base")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'base'".to_string())?;
+
+    let exponent = json
+        .get("exponent")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'exponent'".to_string())?;
+
+    Ok((base, exponent))
+}
+
+/// Parse `coefficients` (and the optional `order`) into highest-degree-first order
+fn parse_polynomial(arguments: &Option<String>) -> Result<Vec<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let coefficients_array = json
+        .get("coefficients")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid parameter 'coefficients'".to_string())?;
+
+    if coefficients_array.is_empty() {
+        return Err("Error: 'coefficients' must not be empty".to_string());
+    }
+
+    let mut coefficients = coefficients_array
+        .iter()
+        .map(|v| {
+            v.as_f64()
+                .filter(|c| c.is_finite())
+                .ok_or_else(|| format!("Invalid coefficient: {}", v))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+
+    match json
+        .get("order")
+        .and_then(|v| v.as_str())
+        .unwrap_or("highest_first")
+    {
+        "highest_first" => {}
+        "lowest_first" => coefficients.reverse(),
+        other => {
+            return Err(format!(
+                "Invalid order '{}': expected 'highest_first' or 'lowest_first'",
+                other
+            ));
+        }
+    }
+
+    Ok(coefficients)
+}
+
+/// Parse `function` (plus `coefficients`/`order` for polynomials)
+fn parse_builtin_function(arguments: &Option<String>) -> Result<BuiltinFunction, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let name = json
+        .get("function")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing or invalid parameter 'function'".to_string())?;
+
+    match name {
+        "sin" => Ok(BuiltinFunction::Sin),
+        "cos" => Ok(BuiltinFunction::Cos),
+        "exp" => Ok(BuiltinFunction::Exp),
+        "ln" => Ok(BuiltinFunction::Ln),
+        "polynomial" => Ok(BuiltinFunction::Polynomial(parse_polynomial(arguments)?)),
+        other => Err(format!(
+            "Unknown function '{}': expected one of sin, cos, exp, ln, polynomial",
+            other
+        )),
+    }
+}
+
+/// Parse the optional `tolerance` and `max_iterations` for iterative methods
+fn parse_iteration_options(arguments: &Option<String>) -> Result<(f64, u32), String> {
+    let tolerance = parse_optional_arg(arguments, "tolerance")?.unwrap_or(DEFAULT_TOLERANCE);
+    if !tolerance.is_finite() || tolerance <= 0.0 {
+        return Err("Error: 'tolerance' must be a positive number".to_string());
+    }
+
+    let max_iterations =
+        parse_optional_arg(arguments, "max_iterations")?.unwrap_or(DEFAULT_MAX_ITERATIONS as f64);
+    if max_iterations.fract() != 0.0
+        || max_iterations < 1.0
+        || max_iterations > MAX_ITERATIONS_CAP as f64
+    {
+        return Err(format!(
+            "Error: 'max_iterations' must be a whole number between 1 and {}",
+            MAX_ITERATIONS_CAP
+        ));
+    }
+
+    Ok((tolerance, max_iterations as u32))
+}
+
+fn parse_max_terms(arguments: &Option<String>) -> Result<usize, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let max_terms = json
+        .get("max_terms")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'max_terms'".to_string())?;
+
+    if !(1..=MAX_CONTINUED_FRACTION_TERMS).contains(&max_terms) {
+        return Err(format!(
+            "Error: 'max_terms' must be between 1 and {}",
+            MAX_CONTINUED_FRACTION_TERMS
+        ));
+    }
+
+    Ok(max_terms as usize)
+}
+
+fn parse_max_denominator(arguments: &Option<String>) -> Result<i64, String> {
+    let max_denominator = parse_integer(arguments, "max_denominator")?;
+    if max_denominator < 1 {
+        return Err("Error: 'max_denominator' must be at least 1".to_string());
+    }
+    Ok(max_denominator)
+}
+
+fn parse_integer_pair(arguments: &Option<String>) -> Result<(i64, i64), String> {
+    Ok((
+        parse_integer(arguments, "a")?,
+        parse_integer(arguments, "b")?,
+    ))
+}
+
+/// Parse a whole-valued number no larger in magnitude than 2⁵³
+fn parse_integer(arguments: &Option<String>, name: &str) -> Result<i64, String> {
+    let value = parse_named_args(arguments, &[name])?[0];
+
+    if value.fract() != 0.0 {
+        return Err(format!(
+            "Error: '{}' must be a whole number (got {})",
+            name, value
+        ));
+    }
+
+    if value.abs() > MAX_SAFE_INTEGER as f64 {
+        return Err(format!(
+            "Error: '{}' must be at most 2^53 in magnitude",
+            name
+        ));
+    }
+
+    Ok(value as i64)
+}
+
+/// Parse the `n` and `r` of a selection, requiring 0 <= r <= n
+fn parse_selection(arguments: &Option<String>) -> Result<(u64, u64), String> {
+    let n = parse_integer(arguments, "n")?;
+    let r = parse_integer(arguments, "r")?;
+
+    if n < 0 || r < 0 {
+        return Err("Error: 'n' and 'r' must not be negative".to_string());
+    }
+
+    if r > n {
+        return Err(format!(
+            "Error: 'r' ({}) must not be greater than 'n' ({})",
+            r, n
+        ));
+    }
+
+    Ok((n as u64, r as u64))
+}
+
+fn parse_format_options(
+    arguments: &Option<String>,
+) -> Result<(NumberFormat, Option<usize>), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let format = match json.get("format") {
+        None | Some(serde_json::Value::Null) => NumberFormat::Auto,
+        Some(v) => match v.as_str() {
+            Some("auto") => NumberFormat::Auto,
+            Some("fixed") => NumberFormat::Fixed,
+            Some("scientific") => NumberFormat::Scientific,
+            Some("percent") => NumberFormat::Percent,
+            _ => {
+                return Err(
+                    "Invalid parameter 'format': expected \"auto\", \"fixed\", \"scientific\" or \"percent\""
+                        .to_string(),
+                );
+            }
+        },
+    };
+
+    let decimals = match json.get("decimals") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => match v.as_u64() {
+            Some(d) if d <= MAX_DECIMALS => Some(d as usize),
+            _ => {
+                return Err(format!(
+                    "Invalid parameter 'decimals': expected an integer from 0 to {}",
+                    MAX_DECIMALS
+                ));
+            }
+        },
+    };
+
+    if decimals.is_some() && format == NumberFormat::Auto {
+        return Err("Error: 'decimals' requires a fixed, scientific or percent format".to_string());
+    }
+
+    Ok((format, decimals))
+}
+
+/// Parse the optional rounding precision for `round` (default 0)
+fn parse_round_decimals(arguments: &Option<String>) -> Result<u32, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("decimals") {
+        None | Some(serde_json::Value::Null) => Ok(0),
+        Some(v) => match v.as_u64() {
+            Some(d) if d <= MAX_ROUND_DECIMALS as u64 => Ok(d as u32),
+            _ => Err(format!(
+                "Invalid parameter 'decimals': expected an integer from 0 to {}",
+                MAX_ROUND_DECIMALS
+            )),
+        },
+    }
+}
+
+/// Parse exactly one of `abs_tolerance` or `rel_tolerance` and return its kind
+/// with the allowed deviation from `target` in absolute units
+fn parse_tolerance(arguments: &Option<String>, target: f64) -> Result<(&'static str, f64), String> {
+    let absolute = parse_optional_arg(arguments, "abs_tolerance")?;
+    let relative = parse_optional_arg(arguments, "rel_tolerance")?;
+
+    let non_negative = |name: &str, tolerance: f64| {
+        if tolerance.is_finite() && tolerance >= 0.0 {
+            Ok(tolerance)
+        } else {
+            Err(format!(
+                "Invalid parameter '{}': expected a non-negative number",
+                name
+            ))
+        }
+    };
+
+    match (absolute, relative) {
+        (Some(t), None) => Ok(("absolute", non_negative("abs_tolerance", t)?)),
+        (None, Some(t)) => Ok(("relative", non_negative("rel_tolerance", t)? * target.abs())),
+        (Some(_), Some(_)) => {
+            Err("Error: provide only one of 'abs_tolerance' or 'rel_tolerance'".to_string())
+        }
+        (None, None) => Err("Error: provide one of 'abs_tolerance' or 'rel_tolerance'".to_string()),
+    }
+}
+
+fn parse_sig_figs(arguments: &Option<String>) -> Result<u32, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("sig_figs").and_then(|v| v.as_u64()) {
+        Some(n) if (1..=MAX_SIG_FIGS as u64).contains(&n) => Ok(n as u32),
+        _ => Err(format!(
+            "Missing or invalid parameter 'sig_figs': expected an integer from 1 to {}",
+            MAX_SIG_FIGS
+        )),
+    }
+}
+
+fn parse_string(arguments: &Option<String>, name: &str) -> Result<String, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    json.get(name)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+}
+
+/// Parse the optional `variables` object of name to finite number
+fn parse_variables(arguments: &Option<String>) -> Result<HashMap<String, f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let object = match json.get("variables") {
+        None | Some(serde_json::Value::Null) => return Ok(HashMap::new()),
+        Some(v) => v
+            .as_object()
+            .ok_or_else(|| "Invalid parameter 'variables': expected an object".to_string())?,
+    };
+
+    object
+        .iter()
+        .map(|(name, value)| {
+            value
+                .as_f64()
+                .filter(|v| v.is_finite())
+                .map(|v| (name.clone(), v))
+                .ok_or_else(|| format!("Invalid value for variable '{}': {}", name, value))
+        })
+        .collect()
+}
+
+fn parse_optional_arg(arguments: &Option<String>, arg_name: &str) -> Result<Option<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(arg_name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid parameter '{}'", arg_name)),
+    }
+}
+
+fn parse_named_args(arguments: &Option<String>, names: &[&str]) -> Result<Vec<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    names
+        .iter()
+        .map(|name| {
+            json.get(*name)
+                .and_then(|v| v.as_f64())
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+        })
+        .collect()
+}
+
+/// Parse a non-empty array of finite numbers under `name`
+fn parse_vector(arguments: &Option<String>, name: &str) -> Result<Vec<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let array = json
+        .get(name)
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?;
+
+    if array.is_empty() {
+        return Err(format!("Error: '{}' must not be empty", name));
+    }
+
+    array
+        .iter()
+        .map(|v| {
+            v.as_f64()
+                .filter(|x| x.is_finite())
+                .ok_or_else(|| format!("Invalid number in '{}': {}", name, v))
+        })
+        .collect()
+}
+
+/// Parse the optional norm order `p`: 1, 2 or "inf" (default 2)
+fn parse_norm_order(arguments: &Option<String>) -> Result<NormOrder, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("p") {
+        None | Some(serde_json::Value::Null) => Ok(NormOrder::L2),
+        Some(p) if p.as_f64() == Some(1.0) => Ok(NormOrder::L1),
+        Some(p) if p.as_f64() == Some(2.0) => Ok(NormOrder::L2),
+        Some(p) if p.as_str() == Some("inf") => Ok(NormOrder::Infinity),
+        Some(p) => Err(format!(
+            "Invalid parameter 'p': {}. Expected 1, 2 or \"inf\"",
+            p
+        )),
+    }
+}
+
+/// Parse the optional `metric` as its name and the norm it applies to `a - b`
+fn parse_metric(arguments: &Option<String>) -> Result<(&'static str, NormOrder), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("metric") {
+        None | Some(serde_json::Value::Null) => Ok(("euclidean", NormOrder::L2)),
+        Some(v) => match v.as_str() {
+            Some("euclidean") => Ok(("euclidean", NormOrder::L2)),
+            Some("manhattan") => Ok(("manhattan", NormOrder::L1)),
+            _ => Err(format!(
+                "Invalid metric {}: expected 'euclidean' or 'manhattan'",
+                v
+            )),
+        },
+    }
+}
+
+/// Require two named vectors to have the same number of elements
+fn check_same_length(a: (&str, &[f64]), b: (&str, &[f64])) -> Result<(), String> {
+    if a.1.len() != b.1.len() {
+        return Err(format!(
+            "Error: '{}' has {} elements but '{}' has {}",
+            a.0,
+            a.1.len(),
+            b.0,
+            b.1.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Success result for a single number: the text block is rendered in the requested
+/// `format`, while `structured_content` carries the raw value together with the
+/// operation and the inputs it was computed from
+fn success_result(operation: &str, value: f64, arguments: &Option<String>) -> CallToolResult {
+    let value = match check_finite(value) {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let (format, decimals) = match parse_format_options(arguments) {
+        Ok(options) => options,
+        Err(msg) => return error_result(msg),
+    };
+
+    text_and_structured_result(
+        format_result(value, format, decimals),
+        serde_json::json!({
+            "result": value,
+            "operation": operation,
+            "inputs": result_inputs(arguments)
+        }),
+    )
+}
+
+/// Reject overflowed or undefined results so callers never see `inf` or `NaN` text
+fn check_finite(value: f64) -> Result<f64, String> {
+    if value.is_nan() {
+        Err("Error: result is not a number".to_string())
+    } else if value.is_infinite() {
+        Err("Error: result overflowed to infinity".to_string())
+    } else {
+        Ok(value)
+    }
+}
+
+/// Success result with separate display text and `structured_content`
+fn text_and_structured_result(display: String, structured: serde_json::Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(display),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(structured.to_string()),
+    }
+}
+
+/// Tool arguments echoed into `structured_content`, minus presentation options
+fn result_inputs(arguments: &Option<String>) -> serde_json::Value {
+    let mut inputs = arguments
+        .as_deref()
+        .and_then(|args| serde_json::from_str::<serde_json::Value>(args).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if let Some(object) = inputs.as_object_mut() {
+        object.remove("format");
+        object.remove("decimals");
+    }
+    inputs
+}
+
+/// Canonical text for a number that round-trips through `str::parse::<f64>`.
+/// Whole numbers print without a fractional part, everything else uses the shortest
+/// exact decimal, switching to exponent form outside [1e-7, 1e21) as JSON clients do.
+pub(crate) fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        // Also folds -0 into 0
+        "0".to_string()
+    } else if value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER as f64 {
+        (value as i64).to_string()
+    } else if value.is_finite() && !(1e-7..1e21).contains(&value.abs()) {
+        format!("{:e}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NumberFormat {
+    Auto,
+    Fixed,
+    Scientific,
+    Percent,
+}
+
+/// Render `value` for display. `decimals` applies to fixed, scientific and percent;
+/// fixed and percent default to two places, scientific to the shortest exact mantissa.
+pub(crate) fn format_result(value: f64, format: NumberFormat, decimals: Option<usize>) -> String {
+    match (format, decimals) {
+        (NumberFormat::Auto, _) => format_number(value),
+        (NumberFormat::Fixed, d) => format!("{:.*}", d.unwrap_or(DEFAULT_DECIMALS), value),
+        (NumberFormat::Scientific, Some(d)) => format!("{:.*e}", d, value),
+        (NumberFormat::Scientific, None) => format!("{:e}", value),
+        (NumberFormat::Percent, d) => {
+            format!("{:.*}%", d.unwrap_or(DEFAULT_DECIMALS), value * 100.0)
+        }
+    }
+}
+
+/// Success result carrying the JSON value both as text and as `structured_content`
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let json = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(json.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(json),
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(Math with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        match result.content.first() {
+            Some(ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            })) => text.clone(),
+            _ => panic!("expected text content"),
+        }
+    }
+
+    #[test]
+    fn horner_evaluates_highest_degree_first() {
+        // 2x² - 3x + 1 at x = 2
+        assert_eq!(horner(&[2.0, -3.0, 1.0], 2.0), 3.0);
+        assert_eq!(horner(&[], 5.0), 0.0);
+    }
+
+    #[test]
+    fn polynomial_derivative_drops_constant_term() {
+        assert_eq!(polynomial_derivative(&[1.0, 0.0, -2.0]), vec![2.0, 0.0]);
+        assert_eq!(polynomial_derivative(&[7.0]), vec![0.0]);
+    }
+
+    #[test]
+    fn determinant_of_identity_is_one() {
+        assert_eq!(determinant_2x2(1.0, 0.0, 0.0, 1.0), 1.0);
+        assert_eq!(determinant_2x2(1.0, 2.0, 2.0, 4.0), 0.0);
+    }
+
+    #[test]
+    fn newton_raphson_finds_square_root_of_two() {
+        let (root, _) = newton_raphson(&[1.0, 0.0, -2.0], 1.0, 1e-12, 50).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn newton_raphson_reports_vanishing_derivative() {
+        assert!(newton_raphson(&[1.0, 0.0, -2.0], 0.0, 1e-12, 50).is_err());
+    }
+
+    #[test]
+    fn bisection_brackets_cosine_root() {
+        let (root, width, _) = bisect(&BuiltinFunction::Cos, 0.0, 2.0, 1e-10, 200).unwrap();
+        assert!((root - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!(width <= 1e-10);
+    }
+
+    #[test]
+    fn bisection_requires_sign_change() {
+        assert!(bisect(&BuiltinFunction::Exp, 0.0, 1.0, 1e-10, 100).is_err());
+    }
+
+    #[test]
+    fn central_difference_matches_analytic_derivative() {
+        let (derivative, _) = central_difference(&BuiltinFunction::Exp, 0.0);
+        assert!((derivative - 1.0).abs() < 1e-8);
+
+        let (derivative, _) = central_difference(&BuiltinFunction::Sin, 0.0);
+        assert!((derivative - 1.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn ln_domain_excludes_zero() {
+        assert!(BuiltinFunction::Ln.check_domain(0.0).is_err());
+        assert!(BuiltinFunction::Ln.check_domain(1e-300).is_ok());
+    }
+
+    #[test]
+    fn divide_by_zero_is_an_error() {
+        let result = execute_divide(&args(r#"{"a": 1, "b": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn square_root_of_negative_is_an_error() {
+        let result = execute_square_root(&args(r#"{"x": -4}"#));
+        assert_eq!(result.is_error, Some(true));
+
+        let result = execute_square_root(&args(r#"{"x": 9}"#));
+        assert_eq!(text(&result), "3");
+    }
+
+    #[test]
+    fn linear_combination_is_a_dot_product() {
+        assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+
+        let result =
+            execute_linear_combination(&args(r#"{"values": [2, 4], "weights": [0.5, 0.25]}"#));
+        assert_eq!(
+            result.structured_content.as_deref(),
+            Some(r#"{"count":2,"result":2.0}"#)
+        );
+    }
+
+    #[test]
+    fn linear_combination_rejects_length_mismatch() {
+        let result = execute_linear_combination(&args(r#"{"values": [1, 2], "weights": [1]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn norms_of_three_four_vector() {
+        let v = [3.0, -4.0];
+        assert_eq!(NormOrder::L1.apply(&v), 7.0);
+        assert_eq!(NormOrder::L2.apply(&v), 5.0);
+        assert_eq!(NormOrder::Infinity.apply(&v), 4.0);
+        assert_eq!(NormOrder::L2.apply(&[1e200, 1e200]), 2f64.sqrt() * 1e200);
+    }
+
+    #[test]
+    fn norm_rejects_unsupported_order() {
+        let result = execute_norm(&args(r#"{"values": [1, 2], "p": 3}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn cosine_similarity_of_parallel_and_orthogonal_vectors() {
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]), Some(0.0));
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]), Some(-1.0));
+    }
+
+    #[test]
+    fn cosine_similarity_rejects_zero_vector() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), None);
+        let result = execute_cosine_similarity(&args(r#"{"a": [0, 0], "b": [1, 1]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn vector_distance_metrics() {
+        let euclidean = execute_vector_distance(&args(r#"{"a": [0, 0, 0], "b": [1, 2, 2]}"#));
+        assert_eq!(
+            euclidean.structured_content.as_deref(),
+            Some(r#"{"dimension":3,"distance":3.0,"metric":"euclidean"}"#)
+        );
+
+        let manhattan = execute_vector_distance(&args(
+            r#"{"a": [0, 0, 0], "b": [1, -2, 2], "metric": "manhattan"}"#,
+        ));
+        assert_eq!(
+            manhattan.structured_content.as_deref(),
+            Some(r#"{"dimension":3,"distance":5.0,"metric":"manhattan"}"#)
+        );
+    }
+
+    #[test]
+    fn vector_distance_rejects_dimension_mismatch() {
+        let result = execute_vector_distance(&args(r#"{"a": [1, 2], "b": [1, 2, 3]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn agm_of_one_and_root_two() {
+        // AGM(1, √2) is Gauss's constant reciprocal ≈ 1.198140234735592
+        let (agm, iterations) = arithmetic_geometric_mean(1.0, 2f64.sqrt(), 1e-15, 100).unwrap();
+        assert!((agm - 1.198_140_234_735_592).abs() < 1e-14);
+        assert!(iterations < 10);
+
+        assert_eq!(
+            arithmetic_geometric_mean(0.0, 5.0, 1e-12, 100).unwrap().0,
+            0.0
+        );
+        assert_eq!(
+            arithmetic_geometric_mean(3.0, 3.0, 1e-12, 100).unwrap(),
+            (3.0, 0)
+        );
+    }
+
+    #[test]
+    fn agm_rejects_negative_inputs() {
+        let result = execute_agm(&args(r#"{"a": -1, "b": 2}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn continued_fraction_of_pi() {
+        let (coefficients, convergents) = continued_fraction(std::f64::consts::PI, 4);
+        assert_eq!(coefficients, vec![3, 7, 15, 1]);
+        assert_eq!(convergents, vec![(3, 1), (22, 7), (333, 106), (355, 113)]);
+    }
+
+    #[test]
+    fn continued_fraction_stops_when_exact() {
+        let (coefficients, convergents) = continued_fraction(-2.75, 10);
+        assert_eq!(coefficients, vec![-3, 4]);
+        assert_eq!(convergents.last(), Some(&(-11, 4)));
+
+        let result = execute_continued_fraction(&args(r#"{"value": 0.5, "max_terms": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        assert_eq!(text(&execute_gcd(&args(r#"{"a": -12, "b": 18}"#))), "6");
+        assert_eq!(text(&execute_lcm(&args(r#"{"a": -4, "b": 6}"#))), "12");
+    }
+
+    #[test]
+    fn gcd_rejects_fractional_inputs() {
+        let result = execute_gcd(&args(r#"{"a": 2.5, "b": 5}"#));
+        assert_eq!(result.is_error, Some(true));
+        assert!(text(&result).contains("whole number"));
+    }
+
+    #[test]
+    fn simplify_fraction_moves_sign_to_numerator() {
+        assert_eq!(simplify_fraction(6, -8), (-3, 4));
+        assert_eq!(simplify_fraction(-6, -8), (3, 4));
+        assert_eq!(simplify_fraction(0, 5), (0, 1));
+
+        let result = execute_simplify_fraction(&args(r#"{"numerator": 1, "denominator": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn logarithms() {
+        assert_eq!(
+            text(&execute_logarithm(
+                "log10",
+                &args(r#"{"value": 1000}"#),
+                f64::log10
+            )),
+            "3"
+        );
+        assert_eq!(
+            text(&execute_logarithm(
+                "log2",
+                &args(r#"{"value": 8}"#),
+                f64::log2
+            )),
+            "3"
+        );
+        assert_eq!(text(&execute_log(&args(r#"{"value": 1}"#))), "0");
+        assert_eq!(
+            text(&execute_log(&args(r#"{"value": 81, "base": 3}"#))),
+            "4"
+        );
+    }
+
+    #[test]
+    fn logarithm_domain_and_base_are_validated() {
+        let result = execute_logarithm("ln", &args(r#"{"value": 0}"#), f64::ln);
+        assert_eq!(text(&result), "Error: logarithm of non-positive number");
+
+        assert_eq!(
+            execute_log(&args(r#"{"value": 2, "base": 1}"#)).is_error,
+            Some(true)
+        );
+        assert_eq!(
+            execute_log(&args(r#"{"value": 2, "base": -2}"#)).is_error,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn best_rational_within_denominator_bound() {
+        assert_eq!(best_rational(1.0 / 3.0, 10), Some((1, 3)));
+        assert_eq!(best_rational(std::f64::consts::PI, 100), Some((311, 99)));
+        assert_eq!(best_rational(std::f64::consts::PI, 1000), Some((355, 113)));
+        assert_eq!(best_rational(-0.75, 1), Some((-1, 1)));
+        assert_eq!(best_rational(2.0, 5), Some((2, 1)));
+    }
+
+    #[test]
+    fn decimal_to_fraction_requires_positive_bound() {
+        let result = execute_decimal_to_fraction(&args(r#"{"value": 0.5, "max_denominator": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn sine_of_thirty_degrees() {
+        let result = execute_trig(
+            "sin",
+            &args(r#"{"angle": 30, "unit": "degrees"}"#),
+            f64::sin,
+        );
+        assert!((text(&result).parse::<f64>().unwrap() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tangent_asymptotes_are_errors() {
+        for angle in ["90", "270", "-90"] {
+            let json = format!(r#"{{"angle": {}, "unit": "degrees"}}"#, angle);
+            assert_eq!(execute_tan(&args(&json)).is_error, Some(true));
+        }
+        assert!(!is_tan_asymptote(PI / 4.0));
+        assert!(is_tan_asymptote(PI / 2.0));
+    }
+
+    #[test]
+    fn angle_unit_must_be_known() {
+        let result = execute_trig(
+            "cos",
+            &args(r#"{"angle": 1, "unit": "gradians"}"#),
+            f64::cos,
+        );
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn inverse_trig_in_degrees() {
+        let result = execute_inverse_trig(
+            "asin",
+            &args(r#"{"value": 0.5, "unit": "degrees"}"#),
+            f64::asin,
+            true,
+        );
+        assert!((text(&result).parse::<f64>().unwrap() - 30.0).abs() < 1e-9);
+
+        let result = execute_atan2(&args(r#"{"y": 1, "x": -1, "unit": "degrees"}"#));
+        assert!((text(&result).parse::<f64>().unwrap() - 135.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn inverse_trig_rejects_out_of_domain_input() {
+        let result = execute_inverse_trig("acos", &args(r#"{"value": 1.5}"#), f64::acos, true);
+        assert_eq!(result.is_error, Some(true));
+        let result = execute_inverse_trig("atan", &args(r#"{"value": 1.5}"#), f64::atan, false);
+        assert_eq!(result.is_error, None);
+        let result = execute_atan2(&args(r#"{"y": 0, "x": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn format_result_presentations() {
+        assert_eq!(format_result(2.5, NumberFormat::Auto, None), "2.5");
+        assert_eq!(format_result(2.0 / 3.0, NumberFormat::Fixed, None), "0.67");
+        assert_eq!(
+            format_result(2.0 / 3.0, NumberFormat::Fixed, Some(4)),
+            "0.6667"
+        );
+        assert_eq!(
+            format_result(12345.0, NumberFormat::Scientific, None),
+            "1.2345e4"
+        );
+        assert_eq!(
+            format_result(12345.0, NumberFormat::Scientific, Some(1)),
+            "1.2e4"
+        );
+        assert_eq!(
+            format_result(0.125, NumberFormat::Percent, Some(1)),
+            "12.5%"
+        );
+    }
+
+    #[test]
+    fn formatted_text_keeps_raw_structured_value() {
+        let result = execute_divide(&args(r#"{"a": 1, "b": 8, "format": "percent"}"#));
+        assert_eq!(text(&result), "12.50%");
+        let structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(structured["result"], 0.125);
+    }
+
+    #[test]
+    fn format_options_are_validated() {
+        for json in [
+            r#"{"a": 1, "b": 2, "format": "roman"}"#,
+            r#"{"a": 1, "b": 2, "format": "fixed", "decimals": 21}"#,
+            r#"{"a": 1, "b": 2, "decimals": 3}"#,
+        ] {
+            assert_eq!(execute_divide(&args(json)).is_error, Some(true));
+        }
+    }
+
+    #[test]
+    fn format_number_round_trips() {
+        assert_eq!(format_number(5.0), "5");
+        assert_eq!(format_number(-0.0), "0");
+        assert_eq!(format_number(2.5), "2.5");
+        assert_eq!(format_number(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(format_number(1e300), "1e300");
+        assert_eq!(format_number(1.5e-9), "1.5e-9");
+        for value in [
+            5.0,
+            -7.0,
+            2.5,
+            1.0 / 3.0,
+            1e300,
+            -1.5e-9,
+            9007199254740993.0,
+            f64::INFINITY,
+        ] {
+            assert_eq!(format_number(value).parse::<f64>().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn extreme_of_reports_first_occurrence() {
+        let values = [3.0, 9.0, -2.0, 9.0, -2.0];
+        assert_eq!(extreme_of(&values, |x, best| x > best), (1, 9.0));
+        assert_eq!(extreme_of(&values, |x, best| x < best), (2, -2.0));
+    }
+
+    #[test]
+    fn extreme_of_rejects_empty_values() {
+        let result = execute_extreme_of(&args(r#"{"values": []}"#), |x, best| x > best);
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn structured_content_records_operation_and_inputs() {
+        let result = execute_operation("add", &args(r#"{"a": 2, "b": 3}"#), |a, b| a + b);
+        assert_eq!(text(&result), "5");
+        let structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            structured,
+            serde_json::json!({"result": 5.0, "operation": "add", "inputs": {"a": 2, "b": 3}})
+        );
+    }
+
+    #[test]
+    fn scalar_output_schema_matches_structured_content() {
+        let schema: serde_json::Value =
+            serde_json::from_str(&scalar_output_schema("Sum a + b")).unwrap();
+        let result = execute_operation("add", &args(r#"{"a": 2, "b": 3}"#), |a, b| a + b);
+        let structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        for field in schema["required"].as_array().unwrap() {
+            assert!(structured.get(field.as_str().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn overflow_is_an_error_not_inf_text() {
+        let result = execute_power(&args(r#"{"base": 10, "exponent": 400}"#));
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(text(&result), "Error: result overflowed to infinity");
+
+        let result = execute_operation("multiply", &args(r#"{"a": 1e200, "b": 1e200}"#), |a, b| {
+            a * b
+        });
+        assert_eq!(result.is_error, Some(true));
+
+        let result = execute_power(&args(r#"{"base": -8, "exponent": 0.5}"#));
+        assert_eq!(text(&result), "Error: result is not a number");
+
+        let result = execute_derivative_at(&args(r#"{"function": "exp", "x": 710}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn sign_maps_zero_to_zero() {
+        assert_eq!(sign(-3.5), -1.0);
+        assert_eq!(sign(0.0), 0.0);
+        assert_eq!(sign(-0.0), 0.0);
+        assert_eq!(sign(2.0), 1.0);
+    }
+
+    #[test]
+    fn round_to_decimal_places() {
+        assert_eq!(round_to_decimals(2.5, 0), 3.0);
+        assert_eq!(round_to_decimals(-2.5, 0), -3.0);
+        assert_eq!(round_to_decimals(1.23456, 2), 1.23);
+        assert_eq!(round_to_decimals(1e300, 15), 1e300);
+
+        let result = execute_round(&args(r#"{"x": 2.675, "decimals": 1}"#));
+        assert_eq!(text(&result), "2.7");
+        let result = execute_round(&args(r#"{"x": 1, "decimals": 16}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn floor_and_trunc_differ_for_negatives() {
+        let floor = execute_unary("floor", &args(r#"{"x": -1.5}"#), f64::floor);
+        let trunc = execute_unary("trunc", &args(r#"{"x": -1.5}"#), f64::trunc);
+        assert_eq!(text(&floor), "-2");
+        assert_eq!(text(&trunc), "-1");
+    }
+
+    #[test]
+    fn clamp_limits_to_range() {
+        let clamp = |json: &str| text(&execute_clamp(&args(json)));
+        assert_eq!(clamp(r#"{"value": 5, "min": 0, "max": 3}"#), "3");
+        assert_eq!(clamp(r#"{"value": -5, "min": 0, "max": 3}"#), "0");
+        assert_eq!(clamp(r#"{"value": 1.5, "min": 0, "max": 3}"#), "1.5");
+
+        let result = execute_clamp(&args(r#"{"value": 1, "min": 3, "max": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn evaluate_expression_with_variables() {
+        let result = execute_evaluate(&args(
+            r#"{"expression": "(a + b) * c", "variables": {"a": 2, "b": 3, "c": 4}}"#,
+        ));
+        assert_eq!(text(&result), "20");
+
+        let result = execute_evaluate(&args(r#"{"expression": "x * 2"}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn within_tolerance_reports_band_and_deviation() {
+        let result = execute_within_tolerance(&args(
+            r#"{"measured": 10.4, "target": 10, "rel_tolerance": 0.05}"#,
+        ));
+        let structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(structured["within_tolerance"], true);
+        assert_eq!(structured["tolerance_type"], "relative");
+        assert!((structured["allowed_deviation"].as_f64().unwrap() - 0.5).abs() < EPSILON);
+        assert!((structured["deviation"].as_f64().unwrap() - 0.4).abs() < 1e-9);
+
+        let result = execute_within_tolerance(&args(
+            r#"{"measured": -0.2, "target": 0, "abs_tolerance": 0.1}"#,
+        ));
+        let structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(structured["within_tolerance"], false);
+        assert_eq!(structured["relative_deviation"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn within_tolerance_needs_exactly_one_tolerance() {
+        for json in [
+            r#"{"measured": 1, "target": 1}"#,
+            r#"{"measured": 1, "target": 1, "abs_tolerance": 0.1, "rel_tolerance": 0.1}"#,
+            r#"{"measured": 1, "target": 1, "abs_tolerance": -0.1}"#,
+        ] {
+            let result = execute_within_tolerance(&args(json));
+            assert_eq!(result.is_error, Some(true), "{}", json);
+        }
+    }
+
+    #[test]
+    fn factorial_values_and_limit() {
+        assert_eq!(factorial(0), 1.0);
+        assert_eq!(factorial(10), 3_628_800.0);
+        assert!(factorial(170).is_finite());
+        assert!(factorial(171).is_infinite());
+
+        for json in [r#"{"n": 171}"#, r#"{"n": -1}"#, r#"{"n": 2.5}"#] {
+            assert_eq!(
+                execute_factorial(&args(json)).is_error,
+                Some(true),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn combinations_and_permutations() {
+        assert_eq!(combinations(5, 2), 10.0);
+        assert_eq!(combinations(52, 5), 2_598_960.0);
+        assert_eq!(combinations(7, 0), 1.0);
+        assert_eq!(combinations(7, 7), 1.0);
+        // Far beyond 170! but the coefficient itself is small
+        assert_eq!(combinations(1000, 3), 166_167_000.0);
+
+        assert_eq!(permutations(5, 2), 20.0);
+        assert_eq!(permutations(5, 0), 1.0);
+        assert_eq!(permutations(10, 10), factorial(10));
+
+        let result = execute_combinations(&args(r#"{"n": 3, "r": 4}"#));
+        assert_eq!(result.is_error, Some(true));
+        let result = execute_permutations(&args(r#"{"n": 100000, "r": 100000}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn round_to_sig_figs_uses_order_of_magnitude() {
+        assert_eq!(round_to_sig_figs(0.004567, 2), 0.0046);
+        assert_eq!(round_to_sig_figs(123456.0, 2), 120000.0);
+        assert_eq!(round_to_sig_figs(-2.5, 1), -3.0);
+        assert_eq!(round_to_sig_figs(9.96, 2), 10.0);
+        assert_eq!(round_to_sig_figs(1000.0, 1), 1000.0);
+        assert_eq!(round_to_sig_figs(0.0, 3), 0.0);
+        assert_eq!(round_to_sig_figs(1.234e-310, 2), 1.2e-310);
+    }
+
+    #[test]
+    fn round_sig_figs_validates_count() {
+        for json in [
+            r#"{"value": 1.5}"#,
+            r#"{"value": 1.5, "sig_figs": 0}"#,
+            r#"{"value": 1.5, "sig_figs": 18}"#,
+            r#"{"value": 1.5, "sig_figs": 2.5}"#,
+        ] {
+            let result = execute_round_sig_figs(&args(json));
+            assert_eq!(result.is_error, Some(true), "{}", json);
+        }
+        let result = execute_round_sig_figs(&args(r#"{"value": 3.14159, "sig_figs": 3}"#));
+        assert_eq!(text(&result), "3.14");
+    }
+
+    #[test]
+    fn scientific_notation_normalises_mantissa() {
+        assert_eq!(scientific_notation(12345.0), (1.2345, 4));
+        assert_eq!(scientific_notation(-0.00042), (-4.2, -4));
+        assert_eq!(scientific_notation(1000.0), (1.0, 3));
+        assert_eq!(scientific_notation(7.0), (7.0, 0));
+        assert_eq!(scientific_notation(0.0), (0.0, 0));
+
+        for (x, expected_exponent) in [
+            (f64::MAX, 308),
+            (f64::MIN_POSITIVE, -308),
+            (5e-324, -324),
+            (1e23, 23),
+        ] {
+            let (mantissa, exponent) = scientific_notation(x);
+            assert!((1.0..10.0).contains(&mantissa), "{} -> {}", x, mantissa);
+            assert_eq!(exponent, expected_exponent, "{}", x);
+        }
+    }
+
+    #[test]
+    fn engineering_notation_uses_si_prefixes() {
+        assert_eq!(engineering_notation(4700.0), (4.7, 3, Some("k")));
+        assert_eq!(engineering_notation(-0.047), (-47.0, -3, Some("m")));
+        assert_eq!(engineering_notation(12.5), (12.5, 0, Some("")));
+        assert_eq!(engineering_notation(0.0), (0.0, 0, Some("")));
+        assert_eq!(engineering_notation(2.5e40), (2.5, 40, None));
+
+        let result = execute_engineering_notation(&args(r#"{"value": 0.00022}"#));
+        assert_eq!(text(&result), "220 µ");
+        let result = execute_engineering_notation(&args(r#"{"value": 1.5e-40}"#));
+        assert_eq!(text(&result), "1.5e-40");
+    }
+}
@@ -4,6 +4,25 @@
 //! - mean: Calculate average
 //! - sum: Calculate total
 //! - count: Count elements
+//! - local_extrema: Find local maxima and minima
+//! - signal_to_noise: Ratio of mean to standard deviation
+//! - trapezoidal_integral: Numerical integration of sampled values
+//! - numerical_derivative: Numerical differentiation of sampled values
+//! - circular_stats: Mean and dispersion of angles
+//! - resample: Linear interpolation to a target length
+//! - softmax: Normalize scores into a probability distribution
+//! - z_score: Standard score of a value against a dataset
+//! - iqr: Interquartile range
+//! - five_number_summary: Minimum, quartiles, median and maximum
+//! - covariance_matrix: Sample covariances between several series
+//! - rolling_sum: Sums over a sliding window
+//! - rms: Root-mean-square
+//! - mean_squared_error: MSE, RMSE and MAE of predictions
+//! - r_squared: Coefficient of determination of predictions
+//! - fast_summary: Count, sum, mean, extremes and variance in one pass
+//! - mode: Most frequent value(s)
+//! - variance: Σ(x - μ)² / (n - ddof)
+//! - standard_deviation: Square root of the variance
 
 mod bindings {
     wit_bindgen::generate!({
@@ -15,9 +34,16 @@ mod bindings {
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use std::collections::HashMap;
 
 struct Statistics;
 
+/// Upper bound on the length of a resampled series
+const MAX_RESAMPLE_LENGTH: u64 = 100_000;
+
+/// Fewest values for which quartiles are meaningful
+const MIN_QUARTILE_POINTS: usize = 4;
+
 impl Guest for Statistics {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
@@ -92,105 +118,2112 @@ impl Guest for Statistics {
                         title: Some("Count".to_string()),
                     }),
                 },
-            ],
-            next_cursor: None,
-            meta: None,
-        })
-    }
-
-    fn call_tool(
-        _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        request: CallToolRequest,
-        _client_stream: Option<&OutputStream>,
-    ) -> Option<CallToolResult> {
-        match request.name.as_str() {
-            "mean" => Some(execute_mean(&request.arguments)),
-            "sum" => Some(execute_sum(&request.arguments)),
-            "count" => Some(execute_count(&request.arguments)),
-            _ => None, // We don't handle this tool
-        }
-    }
-}
-
-fn execute_mean(arguments: &Option<String>) -> CallToolResult {
-    match parse_numbers(arguments) {
-        Ok(numbers) => {
-            if numbers.is_empty() {
-                return error_result("Error: Cannot calculate mean of empty array".to_string());
-            }
-            let sum: f64 = numbers.iter().sum();
-            let mean = sum / numbers.len() as f64;
-            success_result(mean.to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn execute_sum(arguments: &Option<String>) -> CallToolResult {
-    match parse_numbers(arguments) {
-        Ok(numbers) => {
-            let sum: f64 = numbers.iter().sum();
-            success_result(sum.to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn execute_count(arguments: &Option<String>) -> CallToolResult {
-    match parse_numbers(arguments) {
-        Ok(numbers) => {
-            success_result(numbers.len().to_string())
-        }
-        Err(msg) => error_result(msg),
-    }
-}
-
-fn parse_numbers(arguments: &Option<String>) -> Result<Vec<f64>, String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let numbers_array = json
-        .get("numbers")
-        .and_then(|v| v.as_array())
-        .ok_or_else(|| "Missing or invalid parameter 'numbers'".to_string())?;
-
-    let numbers: Result<Vec<f64>, String> = numbers_array
-        .iter()
-        .map(|v| {
-            v.as_f64()
-                .ok_or_else(|| format!("Invalid number in array: {}", v))
-        })
-        .collect();
-
-    numbers
-}
-
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
-
-bindings::export!(Statistics with_types_in bindings);
+                Tool {
+                    name: "local_extrema".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Array of numbers"
+                            },
+                            "prominence": {
+                                "type": "number",
+                                "minimum": 0,
+                                "description": "Minimum prominence for an extremum to be reported (default: 0)"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Find local maxima and minima (points greater/less than both neighbors). \
+                             A plateau of equal values counts as one extremum reported at its first index. \
+                             Endpoints are never extrema."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Local Extrema".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "signal_to_noise".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Array of numbers"
+                            },
+                            "db": {
+                                "type": "boolean",
+                                "description": "Also return the ratio in decibels, 20·log10(mean/stddev) (default: false)"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Calculate the signal-to-noise ratio mean/stddev (population standard deviation), \
+                             the reciprocal of the coefficient of variation. Optionally in decibels."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Signal-to-Noise Ratio".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "trapezoidal_integral".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "y": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 2,
+                                "description": "Sample values (at least 2)"
+                            },
+                            "dx": {
+                                "type": "number",
+                                "exclusiveMinimum": 0,
+                                "description": "Uniform spacing between samples (default: 1.0)"
+                            },
+                            "x": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Strictly increasing sample positions, same length as y (alternative to dx)"
+                            }
+                        },
+                        "required": ["y"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Integrate sampled values numerically using the trapezoidal rule. \
+                             Spacing is given by a uniform dx or an explicit x array."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Trapezoidal Integral".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "numerical_derivative".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "y": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 2,
+                                "description": "Sample values (at least 2)"
+                            },
+                            "dx": {
+                                "type": "number",
+                                "exclusiveMinimum": 0,
+                                "description": "Uniform spacing between samples (default: 1.0)"
+                            },
+                            "x": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "description": "Strictly increasing sample positions, same length as y (alternative to dx)"
+                            }
+                        },
+                        "required": ["y"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Differentiate sampled values numerically. Uses central differences at interior points \
+                             and forward/backward differences at the ends."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Numerical Derivative".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "circular_stats".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "angles": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Angles or bearings in degrees"
+                            }
+                        },
+                        "required": ["angles"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Circular statistics for angular data such as bearings or wind directions. \
+                             Returns the circular mean, mean resultant length, circular variance and circular standard deviation."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Circular Statistics".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "resample".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 2,
+                                "description": "Evenly spaced series to resample (at least 2 values)"
+                            },
+                            "target_length": {
+                                "type": "integer",
+                                "minimum": 2,
+                                "maximum": 100000,
+                                "description": "Length of the output series"
+                            }
+                        },
+                        "required": ["numbers", "target_length"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Resample an evenly spaced series to a target length using linear interpolation. \
+                             The first and last values are preserved."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Resample".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "softmax".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Scores (logits) to normalize"
+                            },
+                            "temperature": {
+                                "type": "number",
+                                "exclusiveMinimum": 0,
+                                "description": "Temperature T > 0 (default: 1.0). Higher values flatten the distribution"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Softmax of an array: exp(xᵢ/T) / Σ exp(xⱼ/T). \
+                             The maximum is subtracted before exponentiating, so large-magnitude inputs do not overflow."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Softmax".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "z_score".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "number", "description": "Value to score"},
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Reference dataset"
+                            }
+                        },
+                        "required": ["value", "numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Standard score (value - mean) / stddev of a single value against a dataset, \
+                             using the population standard deviation. Also returns the dataset mean and stddev."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Z-Score".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "iqr".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 4,
+                                "description": "Array of at least 4 numbers"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Interquartile range Q3 - Q1 of an array. Quartiles use linear interpolation \
+                             between closest ranks. Returns Q1, Q3 and the IQR."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Interquartile Range".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "five_number_summary".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Five-number summary (min, Q1, median, Q3, max) for box plots. \
+                             Quartiles use the same interpolation as the iqr tool."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Five-Number Summary".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "covariance_matrix".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "series": {
+                                "type": "array",
+                                "items": {
+                                    "type": "array",
+                                    "items": {"type": "number"},
+                                    "minItems": 2
+                                },
+                                "minItems": 1,
+                                "description": "Equal-length series, one array per variable"
+                            }
+                        },
+                        "required": ["series"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Symmetric sample covariance matrix (n - 1 denominator) of several equal-length series. \
+                             Entry [i][j] is the covariance of series i and j; the diagonal holds the variances."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Covariance Matrix".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "rolling_sum".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Series to sum over"
+                            },
+                            "window": {
+                                "type": "integer",
+                                "minimum": 1,
+                                "description": "Window length, at most the length of numbers"
+                            }
+                        },
+                        "required": ["numbers", "window"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Sum of each run of `window` consecutive values, giving n - window + 1 sums. \
+                             Computed with a sliding accumulator in a single pass."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Rolling Sum".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "rms".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the root-mean-square √(Σxᵢ² / n) of an array of numbers".to_string()),
+                        output_schema: None,
+                        title: Some("Root Mean Square".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "mean_squared_error".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "predicted": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Predicted values"
+                            },
+                            "actual": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Observed values, same length as predicted"
+                            }
+                        },
+                        "required": ["predicted", "actual"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Error metrics for predictions against observed values: mean squared error, \
+                             root mean squared error and mean absolute error."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Mean Squared Error".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "r_squared".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "predicted": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Predicted values"
+                            },
+                            "actual": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Observed values, same length as predicted"
+                            }
+                        },
+                        "required": ["predicted", "actual"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Coefficient of determination R² = 1 - SS_res / SS_tot for arbitrary predictions. \
+                             Can be negative when the predictions fit worse than the mean of the actual values."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("R² (Coefficient of Determination)".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "fast_summary".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate count, sum, mean, min, max and variance of an array in a single pass (Welford's algorithm). Suited to large arrays".to_string()),
+                        output_schema: None,
+                        title: Some("Fast Summary".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "mode".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            },
+                            "precision": {
+                                "type": "number",
+                                "exclusiveMinimum": 0,
+                                "description": "Bucket width: values are rounded to the nearest multiple of this before counting. Omit to count exactly equal values"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some(
+                            "Most frequently occurring value(s). Ties return every mode in order of first appearance; \
+                             when all values are unique there is no mode and every value is returned with a note."
+                                .to_string(),
+                        ),
+                        output_schema: None,
+                        title: Some("Mode".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "variance".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            },
+                            "ddof": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "default": 0,
+                                "description": "Delta degrees of freedom: 0 for the population, 1 for the sample estimate"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the variance Σ(x - μ)² / (n - ddof) in one pass. ddof 0 gives the population variance, 1 the sample variance; n must exceed ddof".to_string()),
+                        output_schema: None,
+                        title: Some("Variance".to_string()),
+                    }),
+                },
+                Tool {
+                    name: "standard_deviation".to_string(),
+                    input_schema: r#"{
+                        "type": "object",
+                        "properties": {
+                            "numbers": {
+                                "type": "array",
+                                "items": {"type": "number"},
+                                "minItems": 1,
+                                "description": "Array of numbers"
+                            },
+                            "ddof": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "default": 0,
+                                "description": "Delta degrees of freedom: 0 for the population, 1 for the sample estimate"
+                            }
+                        },
+                        "required": ["numbers"]
+                    }"#
+                    .to_string(),
+                    options: Some(ToolOptions {
+                        meta: None,
+                        annotations: None,
+                        description: Some("Calculate the standard deviation √(Σ(x - μ)² / (n - ddof)) in one pass. ddof 0 gives the population value, 1 the sample value; n must exceed ddof".to_string()),
+                        output_schema: None,
+                        title: Some("Standard Deviation".to_string()),
+                    }),
+                },
+            ],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "mean" => Some(execute_mean(&request.arguments)),
+            "sum" => Some(execute_sum(&request.arguments)),
+            "count" => Some(execute_count(&request.arguments)),
+            "local_extrema" => Some(execute_local_extrema(&request.arguments)),
+            "signal_to_noise" => Some(execute_signal_to_noise(&request.arguments)),
+            "trapezoidal_integral" => Some(execute_trapezoidal_integral(&request.arguments)),
+            "numerical_derivative" => Some(execute_numerical_derivative(&request.arguments)),
+            "circular_stats" => Some(execute_circular_stats(&request.arguments)),
+            "resample" => Some(execute_resample(&request.arguments)),
+            "softmax" => Some(execute_softmax(&request.arguments)),
+            "z_score" => Some(execute_z_score(&request.arguments)),
+            "iqr" => Some(execute_iqr(&request.arguments)),
+            "five_number_summary" => Some(execute_five_number_summary(&request.arguments)),
+            "covariance_matrix" => Some(execute_covariance_matrix(&request.arguments)),
+            "rolling_sum" => Some(execute_rolling_sum(&request.arguments)),
+            "rms" => Some(execute_rms(&request.arguments)),
+            "mean_squared_error" => Some(execute_mean_squared_error(&request.arguments)),
+            "r_squared" => Some(execute_r_squared(&request.arguments)),
+            "fast_summary" => Some(execute_fast_summary(&request.arguments)),
+            "mode" => Some(execute_mode(&request.arguments)),
+            "variance" => Some(execute_variance(&request.arguments, false)),
+            "standard_deviation" => Some(execute_variance(&request.arguments, true)),
+            _ => None, // We don't handle this tool
+        }
+    }
+}
+
+fn execute_mean(arguments: &Option<String>) -> CallToolResult {
+    match parse_numbers(arguments) {
+        Ok(numbers) => {
+            if numbers.is_empty() {
+                return error_result("Error: Cannot calculate mean of empty array".to_string());
+            }
+            success_result(mean(&numbers).to_string())
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_sum(arguments: &Option<String>) -> CallToolResult {
+    match parse_numbers(arguments) {
+        Ok(numbers) => {
+            let sum: f64 = numbers.iter().sum();
+            success_result(sum.to_string())
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_count(arguments: &Option<String>) -> CallToolResult {
+    match parse_numbers(arguments) {
+        Ok(numbers) => {
+            success_result(numbers.len().to_string())
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_signal_to_noise(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    let db = match parse_optional_bool(arguments, "db") {
+        Ok(db) => db.unwrap_or(false),
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result(
+            "Error: Cannot calculate signal-to-noise ratio of empty array".to_string(),
+        );
+    }
+
+    let mu = mean(&numbers);
+    let sigma = std_dev(&numbers);
+
+    if sigma == 0.0 {
+        return error_result(
+            "Error: Standard deviation is zero, signal-to-noise ratio is undefined".to_string(),
+        );
+    }
+
+    let snr = mu / sigma;
+    let mut result = serde_json::json!({
+        "snr": snr,
+        "mean": mu,
+        "stddev": sigma
+    });
+
+    if db {
+        if snr <= 0.0 {
+            return error_result(
+                "Error: Decibel form requires a positive mean/stddev ratio".to_string(),
+            );
+        }
+        result["snr_db"] = serde_json::json!(20.0 * snr.log10());
+    }
+
+    structured_result(result)
+}
+
+fn execute_trapezoidal_integral(arguments: &Option<String>) -> CallToolResult {
+    let y = match parse_number_array(arguments, "y") {
+        Ok(y) => y,
+        Err(msg) => return error_result(msg),
+    };
+
+    if y.len() < 2 {
+        return error_result("Error: At least 2 samples are required to integrate".to_string());
+    }
+
+    let x = match parse_sample_positions(arguments, y.len()) {
+        Ok(x) => x,
+        Err(msg) => return error_result(msg),
+    };
+
+    let integral = trapezoidal_integral(&x, &y);
+
+    structured_result(serde_json::json!({
+        "integral": integral,
+        "samples": y.len(),
+        "method": "trapezoidal"
+    }))
+}
+
+/// Σ (x[i+1] - x[i]) · (y[i] + y[i+1]) / 2
+pub(crate) fn trapezoidal_integral(x: &[f64], y: &[f64]) -> f64 {
+    x.windows(2)
+        .zip(y.windows(2))
+        .map(|(xs, ys)| (xs[1] - xs[0]) * (ys[0] + ys[1]) / 2.0)
+        .sum()
+}
+
+fn execute_numerical_derivative(arguments: &Option<String>) -> CallToolResult {
+    let y = match parse_number_array(arguments, "y") {
+        Ok(y) => y,
+        Err(msg) => return error_result(msg),
+    };
+
+    if y.len() < 2 {
+        return error_result("Error: At least 2 samples are required to differentiate".to_string());
+    }
+
+    let x = match parse_sample_positions(arguments, y.len()) {
+        Ok(x) => x,
+        Err(msg) => return error_result(msg),
+    };
+
+    let derivative = numerical_derivative(&x, &y);
+
+    structured_result(serde_json::json!({
+        "derivative": derivative,
+        "samples": y.len(),
+        "method": "central_difference"
+    }))
+}
+
+/// Central differences at interior points, forward/backward differences at the ends.
+/// Requires at least 2 samples with strictly increasing `x`.
+pub(crate) fn numerical_derivative(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = y.len();
+    (0..n)
+        .map(|i| {
+            let (lo, hi) = if i == 0 {
+                (0, 1)
+            } else if i == n - 1 {
+                (n - 2, n - 1)
+            } else {
+                (i - 1, i + 1)
+            };
+            (y[hi] - y[lo]) / (x[hi] - x[lo])
+        })
+        .collect()
+}
+
+fn execute_circular_stats(arguments: &Option<String>) -> CallToolResult {
+    let angles = match parse_number_array(arguments, "angles") {
+        Ok(angles) => angles,
+        Err(msg) => return error_result(msg),
+    };
+
+    if angles.is_empty() {
+        return error_result(
+            "Error: Cannot calculate circular statistics of empty array".to_string(),
+        );
+    }
+
+    if angles.iter().any(|a| !a.is_finite()) {
+        return error_result("Error: Angles must be finite numbers".to_string());
+    }
+
+    let (mean_sin, mean_cos) = angles.iter().fold((0.0, 0.0), |(s, c), a| {
+        let rad = a.to_radians();
+        (s + rad.sin(), c + rad.cos())
+    });
+    let n = angles.len() as f64;
+    let (mean_sin, mean_cos) = (mean_sin / n, mean_cos / n);
+
+    let resultant_length = (mean_sin * mean_sin + mean_cos * mean_cos).sqrt().min(1.0);
+
+    // With a (near) zero resultant the angles cancel out and have no mean direction
+    let (circular_mean, circular_std) = if resultant_length < 1e-12 {
+        (None, None)
+    } else {
+        let mean_deg = mean_sin.atan2(mean_cos).to_degrees().rem_euclid(360.0);
+        let std_deg = (-2.0 * resultant_length.ln()).sqrt().to_degrees();
+        (Some(mean_deg), Some(std_deg))
+    };
+
+    structured_result(serde_json::json!({
+        "circular_mean_degrees": circular_mean,
+        "mean_resultant_length": resultant_length,
+        "circular_variance": 1.0 - resultant_length,
+        "circular_stddev_degrees": circular_std,
+        "count": angles.len()
+    }))
+}
+
+fn execute_resample(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.len() < 2 {
+        return error_result("Error: At least 2 values are required to resample".to_string());
+    }
+
+    let target_length = match parse_target_length(arguments) {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
+
+    let resampled = resample(&numbers, target_length);
+
+    structured_result(serde_json::json!({
+        "resampled": resampled,
+        "original_length": numbers.len(),
+        "target_length": target_length
+    }))
+}
+
+/// Sample the polyline through `values` (at positions 0..n-1) at `target_length`
+/// evenly spaced positions. Requires at least 2 values and `target_length >= 2`.
+pub(crate) fn resample(values: &[f64], target_length: usize) -> Vec<f64> {
+    let last = values.len() - 1;
+    let step = last as f64 / (target_length - 1) as f64;
+
+    (0..target_length)
+        .map(|i| {
+            let position = i as f64 * step;
+            let lo = (position.floor() as usize).min(last - 1);
+            lerp(values[lo], values[lo + 1], position - lo as f64)
+        })
+        .collect()
+}
+
+/// Linear interpolation between `a` and `b` at fraction `t`
+pub(crate) fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// A local maximum or minimum. For plateaus, `index` is the first index of the run.
+#[derive(Debug)]
+pub(crate) struct Extremum {
+    index: usize,
+    value: f64,
+    prominence: f64,
+}
+
+fn execute_local_extrema(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    let min_prominence = match parse_optional_number(arguments, "prominence") {
+        Ok(p) => p.unwrap_or(0.0),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !min_prominence.is_finite() || min_prominence < 0.0 {
+        return error_result("Error: prominence must be a non-negative number".to_string());
+    }
+
+    let maxima: Vec<Extremum> = find_peaks(&numbers)
+        .into_iter()
+        .filter(|e| e.prominence >= min_prominence)
+        .collect();
+
+    // Minima are the peaks of the negated series
+    let negated: Vec<f64> = numbers.iter().map(|x| -x).collect();
+    let minima: Vec<Extremum> = find_peaks(&negated)
+        .into_iter()
+        .map(|e| Extremum {
+            index: e.index,
+            value: -e.value,
+            prominence: e.prominence,
+        })
+        .filter(|e| e.prominence >= min_prominence)
+        .collect();
+
+    let to_json = |extrema: &[Extremum]| -> Vec<serde_json::Value> {
+        extrema
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "index": e.index,
+                    "value": e.value,
+                    "prominence": e.prominence
+                })
+            })
+            .collect()
+    };
+
+    structured_result(serde_json::json!({
+        "maxima": to_json(&maxima),
+        "minima": to_json(&minima),
+        "plateau_convention": "first_index"
+    }))
+}
+
+/// Find local maxima: runs of equal values strictly greater than the values on
+/// both sides of the run. Endpoints are never peaks.
+///
+/// Prominence is the height of the peak above the higher of the two lowest points
+/// reached on each side before encountering a strictly higher value (or the array end).
+pub(crate) fn find_peaks(values: &[f64]) -> Vec<Extremum> {
+    let n = values.len();
+    let mut peaks = Vec::new();
+    let mut i = 1;
+
+    while i + 1 < n {
+        // Extend over a plateau of equal values
+        let start = i;
+        let mut end = i;
+        while end + 1 < n && values[end + 1] == values[start] {
+            end += 1;
+        }
+
+        if end + 1 < n && values[start] > values[start - 1] && values[end] > values[end + 1] {
+            let peak = values[start];
+
+            let mut left_min = peak;
+            for &v in values[..start].iter().rev() {
+                if v > peak {
+                    break;
+                }
+                left_min = left_min.min(v);
+            }
+
+            let mut right_min = peak;
+            for &v in &values[end + 1..] {
+                if v > peak {
+                    break;
+                }
+                right_min = right_min.min(v);
+            }
+
+            peaks.push(Extremum {
+                index: start,
+                value: peak,
+                prominence: peak - left_min.max(right_min),
+            });
+        }
+
+        i = end + 1;
+    }
+
+    peaks
+}
+
+fn execute_softmax(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot calculate softmax of empty array".to_string());
+    }
+
+    if numbers.iter().any(|x| !x.is_finite()) {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let temperature = match parse_optional_number(arguments, "temperature") {
+        Ok(t) => t.unwrap_or(1.0),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !temperature.is_finite() || temperature <= 0.0 {
+        return error_result("Error: 'temperature' must be a positive finite number".to_string());
+    }
+
+    let probabilities = softmax(&numbers, temperature);
+
+    structured_result(serde_json::json!({
+        "probabilities": probabilities,
+        "temperature": temperature,
+        "count": numbers.len()
+    }))
+}
+
+/// Softmax at temperature `t`. Shifting by the maximum leaves the result unchanged
+/// but keeps every exponent ≤ 0, so nothing overflows. Callers must ensure
+/// `numbers` is non-empty and finite and `t > 0`.
+pub(crate) fn softmax(numbers: &[f64], t: f64) -> Vec<f64> {
+    let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = numbers.iter().map(|x| ((x - max) / t).exp()).collect();
+    let total: f64 = exps.iter().sum();
+    exps.iter().map(|e| e / total).collect()
+}
+
+fn execute_z_score(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_number(arguments, "value") {
+        Ok(v) => v,
+        Err(msg) => return error_result(msg),
+    };
+
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot calculate z-score against empty array".to_string());
+    }
+
+    if !value.is_finite() || numbers.iter().any(|x| !x.is_finite()) {
+        return error_result("Error: Values must be finite numbers".to_string());
+    }
+
+    let mu = mean(&numbers);
+    let sigma = std_dev(&numbers);
+
+    if sigma == 0.0 {
+        return error_result("Error: Standard deviation is zero, z-score is undefined".to_string());
+    }
+
+    structured_result(serde_json::json!({
+        "z_score": (value - mu) / sigma,
+        "value": value,
+        "mean": mu,
+        "stddev": sigma
+    }))
+}
+
+fn execute_iqr(arguments: &Option<String>) -> CallToolResult {
+    let sorted = match parse_numbers(arguments).and_then(sorted_finite) {
+        Ok(sorted) => sorted,
+        Err(msg) => return error_result(msg),
+    };
+
+    if sorted.len() < MIN_QUARTILE_POINTS {
+        return error_result(format!(
+            "Error: At least {} values are required for quartiles",
+            MIN_QUARTILE_POINTS
+        ));
+    }
+
+    let q1 = quantile(&sorted, 0.25);
+    let q3 = quantile(&sorted, 0.75);
+
+    structured_result(serde_json::json!({
+        "q1": q1,
+        "q3": q3,
+        "iqr": q3 - q1,
+        "count": sorted.len()
+    }))
+}
+
+fn execute_five_number_summary(arguments: &Option<String>) -> CallToolResult {
+    let sorted = match parse_numbers(arguments).and_then(sorted_finite) {
+        Ok(sorted) => sorted,
+        Err(msg) => return error_result(msg),
+    };
+
+    if sorted.is_empty() {
+        return error_result(
+            "Error: Cannot calculate five-number summary of empty array".to_string(),
+        );
+    }
+
+    structured_result(serde_json::json!({
+        "min": sorted[0],
+        "q1": quantile(&sorted, 0.25),
+        "median": quantile(&sorted, 0.5),
+        "q3": quantile(&sorted, 0.75),
+        "max": sorted[sorted.len() - 1],
+        "count": sorted.len()
+    }))
+}
+
+/// Quantile `q` in [0, 1] of ascending `sorted`, interpolating linearly between
+/// the closest ranks (position q·(n - 1)). Callers must ensure `sorted` is non-empty.
+pub(crate) fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lo = position.floor() as usize;
+    match sorted.get(lo + 1) {
+        Some(&next) => lerp(sorted[lo], next, position - lo as f64),
+        None => sorted[lo],
+    }
+}
+
+/// Sort ascending, rejecting NaN and infinite values
+fn sorted_finite(mut numbers: Vec<f64>) -> Result<Vec<f64>, String> {
+    if numbers.iter().any(|x| !x.is_finite()) {
+        return Err("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    }
+    numbers.sort_by(f64::total_cmp);
+    Ok(numbers)
+}
+
+fn execute_covariance_matrix(arguments: &Option<String>) -> CallToolResult {
+    let series = match parse_series(arguments) {
+        Ok(series) => series,
+        Err(msg) => return error_result(msg),
+    };
+
+    let length = series[0].len();
+    if let Some(i) = series.iter().position(|s| s.len() != length) {
+        return error_result(format!(
+            "Error: series[{}] has {} values but series[0] has {}",
+            i,
+            series[i].len(),
+            length
+        ));
+    }
+
+    if length < 2 {
+        return error_result(
+            "Error: Each series needs at least 2 values for a sample covariance".to_string(),
+        );
+    }
+
+    structured_result(serde_json::json!({
+        "matrix": covariance_matrix(&series),
+        "series_count": series.len(),
+        "length": length
+    }))
+}
+
+/// Sample covariance matrix of equal-length series with at least 2 values each
+pub(crate) fn covariance_matrix(series: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let means: Vec<f64> = series.iter().map(|s| mean(s)).collect();
+    let denominator = (series[0].len() - 1) as f64;
+    let k = series.len();
+    let mut matrix = vec![vec![0.0; k]; k];
+
+    for i in 0..k {
+        for j in i..k {
+            let cov = sum_of_products(&series[i], means[i], &series[j], means[j]) / denominator;
+            matrix[i][j] = cov;
+            matrix[j][i] = cov;
+        }
+    }
+
+    matrix
+}
+
+/// Σxᵢ²
+pub(crate) fn sum_of_squares(numbers: &[f64]) -> f64 {
+    numbers.iter().map(|x| x * x).sum()
+}
+
+/// Σ(xᵢ - mean_x)(yᵢ - mean_y) over paired values
+pub(crate) fn sum_of_products(x: &[f64], mean_x: f64, y: &[f64], mean_y: f64) -> f64 {
+    x.iter()
+        .zip(y)
+        .map(|(a, b)| (a - mean_x) * (b - mean_y))
+        .sum()
+}
+
+fn execute_rolling_sum(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.iter().any(|x| !x.is_finite()) {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let window = match parse_window(arguments, numbers.len()) {
+        Ok(w) => w,
+        Err(msg) => return error_result(msg),
+    };
+
+    let sums = rolling_sum(&numbers, window);
+
+    structured_result(serde_json::json!({
+        "sums": sums,
+        "window": window,
+        "count": sums.len()
+    }))
+}
+
+/// Sums of every `window`-length run. The accumulator drops the value leaving the
+/// window and adds the one entering it. Callers must ensure `1 <= window <= len`.
+pub(crate) fn rolling_sum(numbers: &[f64], window: usize) -> Vec<f64> {
+    let mut sum: f64 = numbers[..window].iter().sum();
+    let mut sums = Vec::with_capacity(numbers.len() - window + 1);
+    sums.push(sum);
+
+    for i in window..numbers.len() {
+        sum += numbers[i] - numbers[i - window];
+        sums.push(sum);
+    }
+
+    sums
+}
+
+fn execute_rms(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot calculate RMS of empty array".to_string());
+    }
+
+    if numbers.iter().any(|x| !x.is_finite()) {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let rms = (sum_of_squares(&numbers) / numbers.len() as f64).sqrt();
+    if !rms.is_finite() {
+        return error_result("Error: Result is not a finite number".to_string());
+    }
+
+    structured_result(serde_json::json!({
+        "rms": rms,
+        "count": numbers.len()
+    }))
+}
+
+fn execute_mean_squared_error(arguments: &Option<String>) -> CallToolResult {
+    let (predicted, actual) = match parse_predictions(arguments) {
+        Ok(pair) => pair,
+        Err(msg) => return error_result(msg),
+    };
+
+    let residuals = residuals(&predicted, &actual);
+    let n = residuals.len() as f64;
+    let mse = sum_of_squares(&residuals) / n;
+    let mae = residuals.iter().map(|r| r.abs()).sum::<f64>() / n;
+
+    structured_result(serde_json::json!({
+        "mse": mse,
+        "rmse": mse.sqrt(),
+        "mae": mae,
+        "count": residuals.len()
+    }))
+}
+
+fn execute_r_squared(arguments: &Option<String>) -> CallToolResult {
+    let (predicted, actual) = match parse_predictions(arguments) {
+        Ok(pair) => pair,
+        Err(msg) => return error_result(msg),
+    };
+
+    let ss_res = sum_of_squares(&residuals(&predicted, &actual));
+    let mu = mean(&actual);
+    let ss_tot = sum_of_products(&actual, mu, &actual, mu);
+
+    if ss_tot == 0.0 {
+        return error_result("Error: Actual values are constant, R² is undefined".to_string());
+    }
+
+    structured_result(serde_json::json!({
+        "r_squared": 1.0 - ss_res / ss_tot,
+        "ss_res": ss_res,
+        "ss_tot": ss_tot,
+        "count": actual.len()
+    }))
+}
+
+fn execute_fast_summary(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot summarize empty array".to_string());
+    }
+
+    let Some(summary) = RunningSummary::from_values(&numbers) else {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    };
+
+    let variance = summary.variance();
+    structured_result(serde_json::json!({
+        "count": summary.count,
+        "sum": summary.sum,
+        "mean": summary.mean,
+        "min": summary.min,
+        "max": summary.max,
+        "variance": variance,
+        "sample_variance": summary.sample_variance(),
+        "std_dev": variance.sqrt()
+    }))
+}
+
+fn execute_mode(arguments: &Option<String>) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot calculate mode of empty array".to_string());
+    }
+
+    if numbers.iter().any(|x| !x.is_finite()) {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let precision = match parse_optional_number(arguments, "precision") {
+        Ok(Some(p)) if p.is_finite() && p > 0.0 => Some(p),
+        Ok(None) => None,
+        Ok(Some(_)) => {
+            return error_result("Error: precision must be a positive number".to_string());
+        }
+        Err(msg) => return error_result(msg),
+    };
+
+    let Some((modes, frequency)) = modes(&numbers, precision) else {
+        return error_result(
+            "Error: precision is too small for the magnitude of the values".to_string(),
+        );
+    };
+
+    let listed = modes
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut structured = serde_json::json!({
+        "modes": modes,
+        "frequency": frequency,
+        "count": numbers.len(),
+        "has_mode": frequency > 1
+    });
+
+    if frequency > 1 {
+        return text_and_structured_result(listed, structured);
+    }
+
+    let note = "All values are unique, so there is no mode; every value is returned";
+    structured["note"] = serde_json::json!(note);
+    text_and_structured_result(format!("{} ({})", listed, note), structured)
+}
+
+/// Values sharing the highest frequency, in order of first appearance, and that frequency.
+/// With a `precision`, values are counted by their nearest multiple of it and that
+/// multiple is reported; otherwise equal values are counted, with -0 folded into 0.
+/// `None` if scaling by the precision overflows.
+pub(crate) fn modes(numbers: &[f64], precision: Option<f64>) -> Option<(Vec<f64>, usize)> {
+    let mut buckets: Vec<(f64, usize)> = Vec::new();
+    let mut index: HashMap<u64, usize> = HashMap::new();
+
+    for &x in numbers {
+        let value = match precision {
+            Some(p) => {
+                // Dividing by the reciprocal keeps decimal widths exact, e.g. 3 / 10 = 0.3
+                let scale = 1.0 / p;
+                let steps = (x * scale).round();
+                if !steps.is_finite() {
+                    return None;
+                }
+                steps / scale
+            }
+            None => x,
+        } + 0.0;
+
+        match index.get(&value.to_bits()) {
+            Some(&i) => buckets[i].1 += 1,
+            None => {
+                index.insert(value.to_bits(), buckets.len());
+                buckets.push((value, 1));
+            }
+        }
+    }
+
+    let frequency = buckets.iter().map(|&(_, count)| count).max().unwrap_or(0);
+    let modes = buckets
+        .into_iter()
+        .filter(|&(_, count)| count == frequency)
+        .map(|(value, _)| value)
+        .collect();
+
+    Some((modes, frequency))
+}
+
+/// Variance with `ddof` delta degrees of freedom, or its square root when `root` is set
+fn execute_variance(arguments: &Option<String>, root: bool) -> CallToolResult {
+    let numbers = match parse_numbers(arguments) {
+        Ok(numbers) => numbers,
+        Err(msg) => return error_result(msg),
+    };
+
+    if numbers.is_empty() {
+        return error_result("Error: Cannot calculate variance of empty array".to_string());
+    }
+
+    let ddof = match parse_ddof(arguments) {
+        Ok(ddof) => ddof,
+        Err(msg) => return error_result(msg),
+    };
+
+    let Some(summary) = RunningSummary::from_values(&numbers) else {
+        return error_result("Error: Array contains invalid values (NaN or Infinite)".to_string());
+    };
+
+    let Some(variance) = summary.variance_with_ddof(ddof) else {
+        return error_result(format!(
+            "Error: ddof ({}) must be less than the number of values ({})",
+            ddof, summary.count
+        ));
+    };
+
+    if !variance.is_finite() {
+        return error_result("Error: Result is not a finite number".to_string());
+    }
+
+    let (name, value) = if root {
+        ("standard_deviation", variance.sqrt())
+    } else {
+        ("variance", variance)
+    };
+
+    structured_result(serde_json::json!({
+        name: value,
+        "mean": summary.mean,
+        "count": summary.count,
+        "ddof": ddof
+    }))
+}
+
+/// Aggregates accumulated in a single pass, with variance via Welford's algorithm
+#[derive(Debug)]
+pub(crate) struct RunningSummary {
+    pub(crate) count: usize,
+    pub(crate) sum: f64,
+    pub(crate) mean: f64,
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    /// Running Σ(x - mean)²
+    m2: f64,
+}
+
+impl RunningSummary {
+    /// Summarize `values` in one iteration; `None` if any value is not finite
+    pub(crate) fn from_values(values: &[f64]) -> Option<Self> {
+        let mut summary = RunningSummary {
+            count: 0,
+            sum: 0.0,
+            mean: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            m2: 0.0,
+        };
+
+        for &x in values {
+            if !x.is_finite() {
+                return None;
+            }
+            summary.count += 1;
+            summary.sum += x;
+            summary.min = summary.min.min(x);
+            summary.max = summary.max.max(x);
+            let delta = x - summary.mean;
+            summary.mean += delta / summary.count as f64;
+            summary.m2 += delta * (x - summary.mean);
+        }
+
+        Some(summary)
+    }
+
+    /// Population variance Σ(x - μ)² / n
+    pub(crate) fn variance(&self) -> f64 {
+        self.m2 / self.count as f64
+    }
+
+    /// Sample variance Σ(x - μ)² / (n - 1), undefined for fewer than two values
+    pub(crate) fn sample_variance(&self) -> Option<f64> {
+        self.variance_with_ddof(1)
+    }
+
+    /// Σ(x - μ)² / (n - ddof), undefined unless n > ddof
+    pub(crate) fn variance_with_ddof(&self, ddof: u64) -> Option<f64> {
+        let n = self.count as u64;
+        (n > ddof).then(|| self.m2 / (n - ddof) as f64)
+    }
+}
+
+/// Residuals actualᵢ - predictedᵢ
+pub(crate) fn residuals(predicted: &[f64], actual: &[f64]) -> Vec<f64> {
+    actual.iter().zip(predicted).map(|(a, p)| a - p).collect()
+}
+
+/// Arithmetic mean. Callers must ensure `numbers` is non-empty.
+pub(crate) fn mean(numbers: &[f64]) -> f64 {
+    numbers.iter().sum::<f64>() / numbers.len() as f64
+}
+
+/// Population standard deviation √(Σ(x - μ)² / n). Callers must ensure `numbers` is non-empty.
+pub(crate) fn std_dev(numbers: &[f64]) -> f64 {
+    let mu = mean(numbers);
+    let sum_sq: f64 = numbers.iter().map(|x| (x - mu) * (x - mu)).sum();
+    (sum_sq / numbers.len() as f64).sqrt()
+}
+
+fn parse_numbers(arguments: &Option<String>) -> Result<Vec<f64>, String> {
+    parse_number_array(arguments, "numbers")
+}
+
+fn parse_number_array(arguments: &Option<String>, name: &str) -> Result<Vec<f64>, String> {
+    parse_optional_number_array(arguments, name)?
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+}
+
+fn parse_optional_number_array(
+    arguments: &Option<String>,
+    name: &str,
+) -> Result<Option<Vec<f64>>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let numbers_array = match json.get(name) {
+        None | Some(serde_json::Value::Null) => return Ok(None),
+        Some(v) => v
+            .as_array()
+            .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))?,
+    };
+
+    let numbers: Result<Vec<f64>, String> = numbers_array
+        .iter()
+        .map(|v| {
+            v.as_f64()
+                .ok_or_else(|| format!("Invalid number in array: {}", v))
+        })
+        .collect();
+
+    numbers.map(Some)
+}
+
+/// Parse `series` as a non-empty array of arrays of finite numbers
+fn parse_series(arguments: &Option<String>) -> Result<Vec<Vec<f64>>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let series_array = json
+        .get("series")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| "Missing or invalid parameter 'series'".to_string())?;
+
+    if series_array.is_empty() {
+        return Err("Error: 'series' must not be empty".to_string());
+    }
+
+    series_array
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            s.as_array()
+                .ok_or_else(|| format!("Invalid 'series[{}]': expected an array", i))?
+                .iter()
+                .map(|v| {
+                    v.as_f64()
+                        .filter(|x| x.is_finite())
+                        .ok_or_else(|| format!("Invalid number in 'series[{}]': {}", i, v))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse `predicted` and `actual` as non-empty, equal-length arrays of finite numbers
+fn parse_predictions(arguments: &Option<String>) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let predicted = parse_number_array(arguments, "predicted")?;
+    let actual = parse_number_array(arguments, "actual")?;
+
+    if predicted.is_empty() {
+        return Err("Error: 'predicted' and 'actual' must not be empty".to_string());
+    }
+
+    if predicted.len() != actual.len() {
+        return Err(format!(
+            "Error: 'predicted' has {} values but 'actual' has {}",
+            predicted.len(),
+            actual.len()
+        ));
+    }
+
+    if predicted.iter().chain(&actual).any(|x| !x.is_finite()) {
+        return Err("Error: Arrays contain invalid values (NaN or Infinite)".to_string());
+    }
+
+    Ok((predicted, actual))
+}
+
+/// Sample positions for `n` values, from either a uniform `dx` (default 1.0)
+/// or an explicit `x` array. Positions must be finite and strictly increasing.
+fn parse_sample_positions(arguments: &Option<String>, n: usize) -> Result<Vec<f64>, String> {
+    let dx = parse_optional_number(arguments, "dx")?;
+    let x = parse_optional_number_array(arguments, "x")?;
+
+    match (dx, x) {
+        (Some(_), Some(_)) => Err("Error: Provide either 'dx' or 'x', not both".to_string()),
+        (None, Some(x)) => {
+            if x.len() != n {
+                return Err(format!(
+                    "Error: 'x' has {} values but 'y' has {}",
+                    x.len(),
+                    n
+                ));
+            }
+            if x.iter().any(|v| !v.is_finite()) {
+                return Err("Error: 'x' contains invalid values (NaN or Infinite)".to_string());
+            }
+            if x.windows(2).any(|w| w[1] <= w[0]) {
+                return Err("Error: 'x' must be strictly increasing".to_string());
+            }
+            Ok(x)
+        }
+        (dx, None) => {
+            let dx = dx.unwrap_or(1.0);
+            if !dx.is_finite() || dx <= 0.0 {
+                return Err("Error: 'dx' must be a positive finite number".to_string());
+            }
+            Ok((0..n).map(|i| i as f64 * dx).collect())
+        }
+    }
+}
+
+fn parse_target_length(arguments: &Option<String>) -> Result<usize, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let target_length = json
+        .get("target_length")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'target_length'".to_string())?;
+
+    if !(2..=MAX_RESAMPLE_LENGTH).contains(&target_length) {
+        return Err(format!(
+            "Error: target_length must be between 2 and {}",
+            MAX_RESAMPLE_LENGTH
+        ));
+    }
+
+    Ok(target_length as usize)
+}
+
+fn parse_number(arguments: &Option<String>, name: &str) -> Result<f64, String> {
+    parse_optional_number(arguments, name)?
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", name))
+}
+
+/// Parse `window` as an integer between 1 and `n`
+fn parse_window(arguments: &Option<String>, n: usize) -> Result<usize, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let window = json
+        .get("window")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "Missing or invalid parameter 'window'".to_string())?;
+
+    if window < 1 || window > n as u64 {
+        return Err(format!(
+            "Error: window must be between 1 and the number of values ({})",
+            n
+        ));
+    }
+
+    Ok(window as usize)
+}
+
+/// Parse the optional `ddof` as a non-negative integer, defaulting to 0
+fn parse_ddof(arguments: &Option<String>) -> Result<u64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("ddof") {
+        None | Some(serde_json::Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .ok_or_else(|| "Invalid parameter 'ddof': expected a non-negative integer".to_string()),
+    }
+}
+
+fn parse_optional_number(arguments: &Option<String>, name: &str) -> Result<Option<f64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid parameter '{}'", name)),
+    }
+}
+
+fn parse_optional_bool(arguments: &Option<String>, name: &str) -> Result<Option<bool>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_bool()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid parameter '{}': expected a boolean", name)),
+    }
+}
+
+fn success_result(result: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(result),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: None,
+    }
+}
+
+/// Success result carrying the JSON value both as text and as `structured_content`
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let json = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(json.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(json),
+    }
+}
+
+/// Success result with a human-readable text block and JSON `structured_content`
+fn text_and_structured_result(text: String, structured: serde_json::Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(structured.to_string()),
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(Statistics with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(json: &str) -> Option<String> {
+        Some(json.to_string())
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        match result.content.first() {
+            Some(ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            })) => text.clone(),
+            _ => panic!("expected text content"),
+        }
+    }
+
+    fn structured(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(
+            result
+                .structured_content
+                .as_deref()
+                .expect("structured content"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn mean_and_population_std_dev() {
+        let numbers = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(&numbers), 5.0);
+        assert_eq!(std_dev(&numbers), 2.0);
+    }
+
+    #[test]
+    fn trapezoidal_integral_of_line_is_exact() {
+        // ∫₀² x dx = 2
+        assert_eq!(
+            trapezoidal_integral(&[0.0, 1.0, 2.0], &[0.0, 1.0, 2.0]),
+            2.0
+        );
+    }
+
+    #[test]
+    fn numerical_derivative_of_parabola() {
+        let x = [0.0, 1.0, 2.0, 3.0];
+        let y: Vec<f64> = x.iter().map(|v| v * v).collect();
+        // Central differences are exact for quadratics at interior points
+        assert_eq!(numerical_derivative(&x, &y), vec![1.0, 2.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn resample_keeps_endpoints_and_interpolates() {
+        assert_eq!(resample(&[0.0, 10.0], 3), vec![0.0, 5.0, 10.0]);
+        assert_eq!(resample(&[1.0, 2.0, 3.0], 2), vec![1.0, 3.0]);
+        assert_eq!(lerp(2.0, 4.0, 0.25), 2.5);
+    }
+
+    #[test]
+    fn find_peaks_reports_plateau_start_and_prominence() {
+        let peaks = find_peaks(&[0.0, 3.0, 3.0, 1.0, 2.0, 0.0]);
+        assert_eq!(peaks.len(), 2);
+        assert_eq!((peaks[0].index, peaks[0].prominence), (1, 3.0));
+        assert_eq!((peaks[1].index, peaks[1].prominence), (4, 1.0));
+    }
+
+    #[test]
+    fn find_peaks_ignores_endpoints() {
+        assert!(find_peaks(&[5.0, 1.0, 0.0]).is_empty());
+    }
+
+    #[test]
+    fn circular_mean_wraps_around_north() {
+        let result = execute_circular_stats(&args(r#"{"angles": [350, 10]}"#));
+        let mean = structured(&result)["circular_mean_degrees"]
+            .as_f64()
+            .unwrap();
+        assert!(mean.abs() < 1e-9 || (mean - 360.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_input_is_an_error() {
+        let result = execute_circular_stats(&args(r#"{"angles": []}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn softmax_is_stable_for_large_inputs() {
+        let probabilities = softmax(&[1000.0, 1000.0], 1.0);
+        assert_eq!(probabilities, vec![0.5, 0.5]);
+
+        let probabilities = softmax(&[1.0, 2.0, 3.0], 1.0);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(probabilities[2] > probabilities[1] && probabilities[1] > probabilities[0]);
+    }
+
+    #[test]
+    fn softmax_temperature_must_be_positive() {
+        let result = execute_softmax(&args(r#"{"numbers": [1, 2], "temperature": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn z_score_against_dataset() {
+        let result = execute_z_score(&args(
+            r#"{"value": 9, "numbers": [2, 4, 4, 4, 5, 5, 7, 9]}"#,
+        ));
+        assert_eq!(structured(&result)["z_score"], 2.0);
+
+        let constant = execute_z_score(&args(r#"{"value": 1, "numbers": [3, 3, 3]}"#));
+        assert_eq!(constant.is_error, Some(true));
+    }
+
+    #[test]
+    fn quantiles_interpolate_between_ranks() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.25), 1.75);
+        assert_eq!(quantile(&sorted, 0.5), 2.5);
+        assert_eq!(quantile(&sorted, 1.0), 4.0);
+    }
+
+    #[test]
+    fn iqr_sorts_input_and_requires_four_values() {
+        let result = execute_iqr(&args(r#"{"numbers": [9, 1, 5, 3, 7]}"#));
+        let value = structured(&result);
+        assert_eq!(value["q1"], 3.0);
+        assert_eq!(value["q3"], 7.0);
+        assert_eq!(value["iqr"], 4.0);
+
+        let result = execute_iqr(&args(r#"{"numbers": [1, 2, 3]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn five_number_summary_of_unsorted_input() {
+        let result = execute_five_number_summary(&args(r#"{"numbers": [5, 1, 3, 2, 4]}"#));
+        let value = structured(&result);
+        assert_eq!(value["min"], 1.0);
+        assert_eq!(value["q1"], 2.0);
+        assert_eq!(value["median"], 3.0);
+        assert_eq!(value["q3"], 4.0);
+        assert_eq!(value["max"], 5.0);
+
+        let single = execute_five_number_summary(&args(r#"{"numbers": [7]}"#));
+        assert_eq!(structured(&single)["median"], 7.0);
+    }
+
+    #[test]
+    fn covariance_matrix_is_symmetric_with_variances_on_diagonal() {
+        let series = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 4.0, 6.0],
+            vec![3.0, 2.0, 1.0],
+        ];
+        let matrix = covariance_matrix(&series);
+        assert_eq!(matrix[0][0], 1.0);
+        assert_eq!(matrix[1][1], 4.0);
+        assert_eq!(matrix[0][1], 2.0);
+        assert_eq!(matrix[1][0], 2.0);
+        assert_eq!(matrix[0][2], -1.0);
+    }
+
+    #[test]
+    fn covariance_matrix_rejects_ragged_series() {
+        let result = execute_covariance_matrix(&args(r#"{"series": [[1, 2, 3], [1, 2]]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn rolling_sum_slides_the_window() {
+        assert_eq!(
+            rolling_sum(&[1.0, 2.0, 3.0, 4.0, 5.0], 2),
+            vec![3.0, 5.0, 7.0, 9.0]
+        );
+        assert_eq!(rolling_sum(&[1.0, 2.0, 3.0], 3), vec![6.0]);
+    }
+
+    #[test]
+    fn rolling_sum_window_must_fit() {
+        let result = execute_rolling_sum(&args(r#"{"numbers": [1, 2], "window": 3}"#));
+        assert_eq!(result.is_error, Some(true));
+        let result = execute_rolling_sum(&args(r#"{"numbers": [1, 2], "window": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn rms_of_symmetric_values() {
+        let result = execute_rms(&args(r#"{"numbers": [3, -3, 3, -3]}"#));
+        assert_eq!(structured(&result)["rms"], 3.0);
+        assert_eq!(sum_of_squares(&[1.0, 2.0, 2.0]), 9.0);
+
+        let empty = execute_rms(&args(r#"{"numbers": []}"#));
+        assert_eq!(empty.is_error, Some(true));
+    }
+
+    #[test]
+    fn mean_squared_error_metrics() {
+        let result = execute_mean_squared_error(&args(
+            r#"{"predicted": [1, 2, 3, 4], "actual": [1, 2, 5, 2]}"#,
+        ));
+        let value = structured(&result);
+        assert_eq!(value["mse"], 2.0);
+        assert_eq!(value["rmse"], 2f64.sqrt());
+        assert_eq!(value["mae"], 1.0);
+    }
+
+    #[test]
+    fn mean_squared_error_requires_equal_lengths() {
+        let result = execute_mean_squared_error(&args(r#"{"predicted": [1, 2], "actual": [1]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn r_squared_of_perfect_and_mean_predictions() {
+        let perfect = execute_r_squared(&args(r#"{"predicted": [1, 2, 3], "actual": [1, 2, 3]}"#));
+        assert_eq!(structured(&perfect)["r_squared"], 1.0);
+
+        let mean_only =
+            execute_r_squared(&args(r#"{"predicted": [2, 2, 2], "actual": [1, 2, 3]}"#));
+        let value = structured(&mean_only);
+        assert_eq!(value["r_squared"], 0.0);
+        assert_eq!(value["ss_tot"], 2.0);
+    }
+
+    #[test]
+    fn r_squared_rejects_constant_actuals() {
+        let result = execute_r_squared(&args(r#"{"predicted": [1, 2], "actual": [5, 5]}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn running_summary_matches_two_pass_results() {
+        let numbers = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let summary = RunningSummary::from_values(&numbers).unwrap();
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.sum, 40.0);
+        assert_eq!(summary.mean, mean(&numbers));
+        assert_eq!((summary.min, summary.max), (2.0, 9.0));
+        assert!((summary.variance().sqrt() - std_dev(&numbers)).abs() < 1e-12);
+        assert!((summary.sample_variance().unwrap() - 32.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fast_summary_of_single_value_has_no_sample_variance() {
+        let result = execute_fast_summary(&args(r#"{"numbers": [3]}"#));
+        let value = structured(&result);
+        assert_eq!(value["variance"], 0.0);
+        assert!(value["sample_variance"].is_null());
+    }
+
+    #[test]
+    fn mode_returns_ties_in_order_of_appearance() {
+        assert_eq!(
+            modes(&[3.0, 1.0, 3.0, 2.0, 1.0], None),
+            Some((vec![3.0, 1.0], 2))
+        );
+        assert_eq!(modes(&[0.0, -0.0, 5.0], None), Some((vec![0.0], 2)));
+        assert_eq!(
+            modes(&[0.31, 0.29, 0.52, 0.7], Some(0.1)),
+            Some((vec![0.3], 2))
+        );
+        assert_eq!(modes(&[1e300], Some(1e-300)), None);
+
+        let result = execute_mode(&args(r#"{"numbers": [2, 7, 2, 7, 1]}"#));
+        assert_eq!(text(&result), "2, 7");
+        assert_eq!(structured(&result)["modes"], serde_json::json!([2.0, 7.0]));
+    }
+
+    #[test]
+    fn mode_of_unique_values_returns_all_with_note() {
+        let result = execute_mode(&args(r#"{"numbers": [4, 5, 6]}"#));
+        let value = structured(&result);
+        assert_eq!(value["has_mode"], false);
+        assert_eq!(value["modes"], serde_json::json!([4.0, 5.0, 6.0]));
+        assert!(value["note"].is_string());
+
+        let result = execute_mode(&args(r#"{"numbers": [1, 2], "precision": 0}"#));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn variance_and_standard_deviation_with_ddof() {
+        let numbers = r#"[2, 4, 4, 4, 5, 5, 7, 9]"#;
+
+        let result = execute_variance(&args(&format!(r#"{{"numbers": {}}}"#, numbers)), false);
+        assert_eq!(structured(&result)["variance"], 4.0);
+
+        let result = execute_variance(&args(&format!(r#"{{"numbers": {}}}"#, numbers)), true);
+        assert_eq!(structured(&result)["standard_deviation"], 2.0);
+
+        let result = execute_variance(
+            &args(&format!(r#"{{"numbers": {}, "ddof": 1}}"#, numbers)),
+            false,
+        );
+        let sample = structured(&result)["variance"].as_f64().unwrap();
+        assert!((sample - 32.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn variance_rejects_ddof_not_below_count() {
+        for json in [
+            r#"{"numbers": [1, 2], "ddof": 2}"#,
+            r#"{"numbers": [1], "ddof": 1}"#,
+            r#"{"numbers": [1, 2], "ddof": -1}"#,
+        ] {
+            assert_eq!(
+                execute_variance(&args(json), false).is_error,
+                Some(true),
+                "{}",
+                json
+            );
+        }
+    }
+}
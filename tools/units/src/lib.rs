@@ -0,0 +1,554 @@
+//! Units Tool
+//!
+//! Convert values between units of the same physical dimension (length, mass,
+//! time, area, volume, speed, pressure, energy and temperature) using a lookup
+//! table of conversion factors. Temperature scales have different zero points,
+//! so they are converted through Celsius rather than by a single factor.
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "units",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+
+struct Units;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Area,
+    Volume,
+    Speed,
+    Pressure,
+    Energy,
+    Temperature,
+}
+
+impl Dimension {
+    fn name(self) -> &'static str {
+        match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Time => "time",
+            Dimension::Area => "area",
+            Dimension::Volume => "volume",
+            Dimension::Speed => "speed",
+            Dimension::Pressure => "pressure",
+            Dimension::Energy => "energy",
+            Dimension::Temperature => "temperature",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Scale {
+    /// Amount of the dimension's base unit (m, kg, s, m², m³, m/s, Pa, J) in one unit
+    Linear(f64),
+    /// Temperature scale: degrees per degree Celsius and the reading at 0 °C
+    Affine {
+        per_celsius: f64,
+        at_zero_celsius: f64,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct Unit {
+    /// Accepted spellings; the first is the canonical symbol
+    names: &'static [&'static str],
+    dimension: Dimension,
+    scale: Scale,
+}
+
+impl Unit {
+    fn symbol(&self) -> &'static str {
+        self.names[0]
+    }
+}
+
+const fn linear(names: &'static [&'static str], dimension: Dimension, factor: f64) -> Unit {
+    Unit {
+        names,
+        dimension,
+        scale: Scale::Linear(factor),
+    }
+}
+
+const fn affine(names: &'static [&'static str], per_celsius: f64, at_zero_celsius: f64) -> Unit {
+    Unit {
+        names,
+        dimension: Dimension::Temperature,
+        scale: Scale::Affine {
+            per_celsius,
+            at_zero_celsius,
+        },
+    }
+}
+
+/// Supported units. Imperial volumes are US customary; factors are exact by
+/// definition except where noted.
+const UNITS: &[Unit] = &[
+    linear(
+        &["m", "meter", "meters", "metre", "metres"],
+        Dimension::Length,
+        1.0,
+    ),
+    linear(
+        &["km", "kilometer", "kilometers", "kilometre", "kilometres"],
+        Dimension::Length,
+        1000.0,
+    ),
+    linear(
+        &[
+            "cm",
+            "centimeter",
+            "centimeters",
+            "centimetre",
+            "centimetres",
+        ],
+        Dimension::Length,
+        0.01,
+    ),
+    linear(
+        &[
+            "mm",
+            "millimeter",
+            "millimeters",
+            "millimetre",
+            "millimetres",
+        ],
+        Dimension::Length,
+        0.001,
+    ),
+    linear(
+        &["µm", "um", "micrometer", "micrometers", "micron", "microns"],
+        Dimension::Length,
+        1e-6,
+    ),
+    linear(&["nm", "nanometer", "nanometers"], Dimension::Length, 1e-9),
+    linear(&["in", "inch", "inches"], Dimension::Length, 0.0254),
+    linear(&["ft", "foot", "feet"], Dimension::Length, 0.3048),
+    linear(&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    linear(&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    linear(
+        &["nmi", "nautical_mile", "nautical_miles"],
+        Dimension::Length,
+        1852.0,
+    ),
+    linear(&["kg", "kilogram", "kilograms"], Dimension::Mass, 1.0),
+    linear(&["g", "gram", "grams"], Dimension::Mass, 0.001),
+    linear(&["mg", "milligram", "milligrams"], Dimension::Mass, 1e-6),
+    linear(
+        &["t", "tonne", "tonnes", "metric_ton"],
+        Dimension::Mass,
+        1000.0,
+    ),
+    linear(
+        &["lb", "lbs", "pound", "pounds"],
+        Dimension::Mass,
+        0.45359237,
+    ),
+    linear(&["oz", "ounce", "ounces"], Dimension::Mass, 0.028349523125),
+    linear(&["st", "stone", "stones"], Dimension::Mass, 6.35029318),
+    linear(&["s", "sec", "second", "seconds"], Dimension::Time, 1.0),
+    linear(
+        &["ms", "millisecond", "milliseconds"],
+        Dimension::Time,
+        0.001,
+    ),
+    linear(&["min", "minute", "minutes"], Dimension::Time, 60.0),
+    linear(&["h", "hr", "hour", "hours"], Dimension::Time, 3600.0),
+    linear(&["d", "day", "days"], Dimension::Time, 86_400.0),
+    linear(&["wk", "week", "weeks"], Dimension::Time, 604_800.0),
+    linear(
+        &["m2", "m²", "square_meter", "square_meters"],
+        Dimension::Area,
+        1.0,
+    ),
+    linear(
+        &["km2", "km²", "square_kilometer", "square_kilometers"],
+        Dimension::Area,
+        1e6,
+    ),
+    linear(
+        &["cm2", "cm²", "square_centimeter", "square_centimeters"],
+        Dimension::Area,
+        1e-4,
+    ),
+    linear(&["ha", "hectare", "hectares"], Dimension::Area, 1e4),
+    linear(&["ac", "acre", "acres"], Dimension::Area, 4046.8564224),
+    linear(
+        &["ft2", "ft²", "square_foot", "square_feet"],
+        Dimension::Area,
+        0.09290304,
+    ),
+    linear(
+        &["mi2", "mi²", "square_mile", "square_miles"],
+        Dimension::Area,
+        2_589_988.110336,
+    ),
+    linear(
+        &["m3", "m³", "cubic_meter", "cubic_meters"],
+        Dimension::Volume,
+        1.0,
+    ),
+    linear(
+        &["L", "l", "liter", "liters", "litre", "litres"],
+        Dimension::Volume,
+        0.001,
+    ),
+    linear(
+        &[
+            "mL",
+            "ml",
+            "milliliter",
+            "milliliters",
+            "millilitre",
+            "millilitres",
+        ],
+        Dimension::Volume,
+        1e-6,
+    ),
+    linear(&["cm3", "cm³", "cc"], Dimension::Volume, 1e-6),
+    linear(
+        &["ft3", "ft³", "cubic_foot", "cubic_feet"],
+        Dimension::Volume,
+        0.028316846592,
+    ),
+    linear(
+        &["gal", "gallon", "gallons"],
+        Dimension::Volume,
+        0.003785411784,
+    ),
+    linear(
+        &["qt", "quart", "quarts"],
+        Dimension::Volume,
+        0.000946352946,
+    ),
+    linear(&["pt", "pint", "pints"], Dimension::Volume, 0.000473176473),
+    linear(
+        &["fl_oz", "floz", "fluid_ounce", "fluid_ounces"],
+        Dimension::Volume,
+        2.95735295625e-5,
+    ),
+    linear(&["m/s", "mps", "meters_per_second"], Dimension::Speed, 1.0),
+    linear(
+        &["km/h", "kph", "kmh", "kilometers_per_hour"],
+        Dimension::Speed,
+        1.0 / 3.6,
+    ),
+    linear(
+        &["mph", "mi/h", "miles_per_hour"],
+        Dimension::Speed,
+        0.44704,
+    ),
+    linear(
+        &["kn", "kt", "knot", "knots"],
+        Dimension::Speed,
+        1852.0 / 3600.0,
+    ),
+    linear(
+        &["ft/s", "fps", "feet_per_second"],
+        Dimension::Speed,
+        0.3048,
+    ),
+    linear(&["Pa", "pascal", "pascals"], Dimension::Pressure, 1.0),
+    linear(
+        &["kPa", "kilopascal", "kilopascals"],
+        Dimension::Pressure,
+        1000.0,
+    ),
+    linear(
+        &["MPa", "megapascal", "megapascals"],
+        Dimension::Pressure,
+        1e6,
+    ),
+    linear(&["bar"], Dimension::Pressure, 1e5),
+    linear(
+        &["atm", "atmosphere", "atmospheres"],
+        Dimension::Pressure,
+        101_325.0,
+    ),
+    // psi and mmHg are rounded to the precision of f64
+    linear(&["psi"], Dimension::Pressure, 6894.757293168361),
+    linear(&["mmHg", "torr"], Dimension::Pressure, 133.322387415),
+    linear(&["J", "joule", "joules"], Dimension::Energy, 1.0),
+    linear(
+        &["kJ", "kilojoule", "kilojoules"],
+        Dimension::Energy,
+        1000.0,
+    ),
+    linear(&["MJ", "megajoule", "megajoules"], Dimension::Energy, 1e6),
+    linear(
+        &["Wh", "watt_hour", "watt_hours"],
+        Dimension::Energy,
+        3600.0,
+    ),
+    linear(
+        &["kWh", "kilowatt_hour", "kilowatt_hours"],
+        Dimension::Energy,
+        3.6e6,
+    ),
+    linear(&["cal", "calorie", "calories"], Dimension::Energy, 4.184),
+    linear(
+        &["kcal", "kilocalorie", "kilocalories"],
+        Dimension::Energy,
+        4184.0,
+    ),
+    linear(&["BTU", "btu"], Dimension::Energy, 1055.05585262),
+    linear(
+        &["eV", "electronvolt", "electronvolts"],
+        Dimension::Energy,
+        1.602176634e-19,
+    ),
+    affine(&["C", "°C", "celsius"], 1.0, 0.0),
+    affine(&["F", "°F", "fahrenheit"], 1.8, 32.0),
+    affine(&["K", "kelvin"], 1.0, 273.15),
+    affine(&["R", "°R", "rankine"], 1.8, 491.67),
+];
+
+impl Guest for Units {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        _request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        Ok(ListToolsResult {
+            tools: vec![Tool {
+                name: "convert_units".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "value": {"type": "number", "description": "Value to convert"},
+                        "from": {"type": "string", "description": "Unit of the value, e.g. km, lb, h, ha, L, mph, psi, kWh or F"},
+                        "to": {"type": "string", "description": "Unit to convert to; must measure the same dimension as 'from'"}
+                    },
+                    "required": ["value", "from", "to"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: None,
+                    description: Some(
+                        "Convert a value between units of length, mass, time, area, volume, speed, pressure, energy or temperature. \
+                         Units are given by symbol or name (e.g. 'km' or 'kilometers'); converting across dimensions is an error. \
+                         Temperatures (C, F, K, R) are treated as readings, not differences."
+                            .to_string(),
+                    ),
+                    output_schema: None,
+                    title: Some("Convert Units".to_string()),
+                }),
+            }],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        match request.name.as_str() {
+            "convert_units" => Some(execute_convert_units(&request.arguments)),
+            _ => None,
+        }
+    }
+}
+
+fn execute_convert_units(arguments: &Option<String>) -> CallToolResult {
+    let (value, from, to) = match parse_convert_args(arguments) {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = match convert(value, from, to) {
+        Ok(result) => result,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_result(serde_json::json!({
+        "value": value,
+        "from": from.symbol(),
+        "to": to.symbol(),
+        "result": result,
+        "dimension": from.dimension.name()
+    }))
+}
+
+/// Convert `value` from one unit to another of the same dimension
+pub(crate) fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64, String> {
+    if from.dimension != to.dimension {
+        return Err(format!(
+            "Error: cannot convert {} ({}) to {} ({})",
+            from.dimension.name(),
+            from.symbol(),
+            to.dimension.name(),
+            to.symbol()
+        ));
+    }
+
+    let result = match (from.scale, to.scale) {
+        (Scale::Linear(from_factor), Scale::Linear(to_factor)) => value * from_factor / to_factor,
+        (
+            Scale::Affine {
+                per_celsius: from_per_celsius,
+                at_zero_celsius: from_zero,
+            },
+            Scale::Affine {
+                per_celsius: to_per_celsius,
+                at_zero_celsius: to_zero,
+            },
+        ) => {
+            let celsius = (value - from_zero) / from_per_celsius;
+            celsius * to_per_celsius + to_zero
+        }
+        _ => unreachable!("units of one dimension share a scale kind"),
+    };
+
+    if !result.is_finite() {
+        return Err("Error: result is not a finite number".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Look up a unit by symbol or name. Symbols must match exactly since case
+/// distinguishes prefixes (mJ vs MJ); only spelled-out names ignore case.
+pub(crate) fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    UNITS
+        .iter()
+        .find(|unit| unit.names.contains(&name))
+        .or_else(|| {
+            UNITS.iter().find(|unit| {
+                unit.names
+                    .iter()
+                    .any(|n| is_spelled_out(n) && n.eq_ignore_ascii_case(name))
+            })
+        })
+}
+
+/// Whole-word names like `kilometers`, as opposed to symbols and short aliases
+fn is_spelled_out(name: &str) -> bool {
+    name.len() > 3 && name.bytes().all(|b| b.is_ascii_lowercase() || b == b'_')
+}
+
+fn parse_unit(json: &serde_json::Value, param: &str) -> Result<&'static Unit, String> {
+    let name = json
+        .get(param)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", param))?;
+    find_unit(name).ok_or_else(|| format!("Error: unknown unit '{}' in '{}'", name, param))
+}
+
+fn parse_convert_args(
+    arguments: &Option<String>,
+) -> Result<(f64, &'static Unit, &'static Unit), String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let value = json
+        .get("value")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "Missing or invalid parameter 'value'".to_string())?;
+
+    let from = parse_unit(&json, "from")?;
+    let to = parse_unit(&json, "to")?;
+
+    Ok((value, from, to))
+}
+
+/// Success result carrying the JSON value both as text and as `structured_content`
+fn structured_result(value: serde_json::Value) -> CallToolResult {
+    let json = value.to_string();
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(json.clone()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(json),
+    }
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(message),
+            options: None,
+        })],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+bindings::export!(Units with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_named(value: f64, from: &str, to: &str) -> Result<f64, String> {
+        convert(value, find_unit(from).unwrap(), find_unit(to).unwrap())
+    }
+
+    #[test]
+    fn linear_conversions() {
+        assert_eq!(convert_named(1.0, "km", "m"), Ok(1000.0));
+        assert_eq!(convert_named(1.0, "mi", "ft"), Ok(5280.0));
+        assert_eq!(convert_named(2.0, "h", "min"), Ok(120.0));
+        assert_eq!(convert_named(1.0, "lb", "oz"), Ok(16.0));
+        assert!(
+            (convert_named(100.0, "km/h", "m/s").unwrap() - 27.777_777_777_777_78).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn temperature_conversions_are_affine() {
+        assert_eq!(convert_named(100.0, "C", "F"), Ok(212.0));
+        assert_eq!(convert_named(32.0, "F", "C"), Ok(0.0));
+        assert_eq!(convert_named(0.0, "K", "C"), Ok(-273.15));
+        assert_eq!(convert_named(-40.0, "celsius", "fahrenheit"), Ok(-40.0));
+        assert!((convert_named(0.0, "C", "R").unwrap() - 491.67).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lookup_prefers_exact_case() {
+        assert_eq!(find_unit("MPa").unwrap().symbol(), "MPa");
+        assert_eq!(find_unit("KILOMETERS").unwrap().symbol(), "km");
+        assert!(find_unit("furlong").is_none());
+    }
+
+    #[test]
+    fn symbols_do_not_fold_case() {
+        assert!(find_unit("mJ").is_none());
+        assert!(find_unit("mPa").is_none());
+        assert!(find_unit("Mm").is_none());
+        assert_eq!(find_unit("MJ").unwrap().symbol(), "MJ");
+        assert_eq!(find_unit("Megajoules").unwrap().symbol(), "MJ");
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_rejected() {
+        assert!(convert_named(1.0, "kg", "m").is_err());
+        assert!(convert_named(1.0, "C", "J").is_err());
+
+        let result = execute_convert_units(&Some(
+            r#"{"value": 1, "from": "parsec", "to": "m"}"#.to_string(),
+        ));
+        assert_eq!(result.is_error, Some(true));
+    }
+}